@@ -0,0 +1,188 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::util::value_to_string;
+use crate::{AqaraClient, AqaraError, ErrorKind};
+
+/// 审计记录中会被替换的请求字段 / Request fields replaced in audit records
+pub(crate) const REDACTED_KEYS: &[&str] = &[
+    "accessToken",
+    "refreshToken",
+    "authCode",
+    "appKey",
+    "password",
+];
+
+/// 视为调用目标的请求字段 / Request fields treated as call targets
+const TARGET_KEYS: &[&str] = &[
+    "did",
+    "dids",
+    "subjectId",
+    "subjectIds",
+    "positionId",
+    "positionIds",
+    "sceneId",
+    "linkageId",
+    "parentDid",
+];
+
+/// 调用结果 (Call outcome)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum AuditOutcome {
+    /// 调用成功 / The call succeeded
+    Success,
+    /// Aqara 返回非 0 业务码 / Aqara returned a non-zero business code
+    ApiError { code: i32, message: String },
+    /// 请求未完成 (网络、超时、关闭等) / The request did not complete (network, timeout, shutdown, ...)
+    Failed { kind: ErrorKind, message: String },
+}
+
+/// 审计记录 (Audit record)
+///
+/// 每次调用 (不含重试) 生成一条，请求参数中的令牌等敏感字段已被替换。
+/// One per call (retries are not recorded separately); tokens and other
+/// secrets in the request params have already been replaced.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditRecord {
+    /// 调用开始时间 / When the call started
+    pub timestamp: DateTime<Utc>,
+    /// 接口 intent / Intent name
+    pub intent: String,
+    /// 调用方提供的操作者 (见 `with_actor`) / Caller-supplied actor (see `with_actor`)
+    pub actor: Option<String>,
+    /// 令牌表中的用户键 (见 `as_user`) / User key in the token map (see `as_user`)
+    pub user: Option<String>,
+    /// 目标ID (设备、位置、场景等) / Target ids (devices, positions, scenes, ...)
+    pub target_ids: Vec<String>,
+    /// 已脱敏的请求参数 / Redacted request params
+    pub params: Value,
+    /// 调用结果 / Call outcome
+    pub outcome: AuditOutcome,
+    /// Aqara 请求ID (若有) / Aqara request id, if any
+    pub request_id: Option<String>,
+}
+
+/// 审计日志接收器 (Audit sink)
+///
+/// 在调用完成后同步调用，实现应尽快返回 (例如写入通道或缓冲区)。
+/// Invoked synchronously once a call completes; implementations should return
+/// quickly (e.g. push onto a channel or buffer).
+pub trait AuditSink: Send + Sync {
+    /// 接收一条审计记录 / Receive an audit record
+    fn record(&self, record: &AuditRecord);
+}
+
+impl<F> AuditSink for F
+where
+    F: Fn(&AuditRecord) + Send + Sync,
+{
+    fn record(&self, record: &AuditRecord) {
+        self(record)
+    }
+}
+
+impl AuditRecord {
+    pub(crate) fn new(
+        timestamp: DateTime<Utc>,
+        intent: &str,
+        client: &AqaraClient,
+        params: &Value,
+        result: &Result<String, AqaraError>,
+    ) -> Self {
+        let mut target_ids = Vec::new();
+        collect_targets(params, &mut target_ids);
+        let (outcome, request_id) = match result {
+            Ok(body) => {
+                let body: Value = serde_json::from_str(body).unwrap_or(Value::Null);
+                let request_id =
+                    Some(value_to_string(body.get("requestId"))).filter(|id| !id.is_empty());
+                let outcome = match body.get("code").and_then(Value::as_i64) {
+                    Some(0) | None => AuditOutcome::Success,
+                    Some(code) => AuditOutcome::ApiError {
+                        code: code as i32,
+                        message: value_to_string(body.get("message")),
+                    },
+                };
+                (outcome, request_id)
+            }
+            Err(e) => (
+                AuditOutcome::Failed {
+                    kind: e.kind(),
+                    message: e.to_string(),
+                },
+                None,
+            ),
+        };
+        AuditRecord {
+            timestamp,
+            intent: intent.to_string(),
+            actor: client.actor.clone(),
+            user: client.user.clone(),
+            target_ids,
+            params: redact(params),
+            outcome,
+            request_id,
+        }
+    }
+}
+
+fn collect_targets(value: &Value, targets: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                if TARGET_KEYS.contains(&key.as_str()) {
+                    match value {
+                        Value::Array(ids) => {
+                            targets.extend(ids.iter().map(|id| value_to_string(Some(id))))
+                        }
+                        id => targets.push(value_to_string(Some(id))),
+                    }
+                } else {
+                    collect_targets(value, targets);
+                }
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|item| collect_targets(item, targets)),
+        _ => {}
+    }
+}
+
+pub(crate) fn redact(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| match REDACTED_KEYS.contains(&key.as_str()) {
+                    true => (key.clone(), Value::String("***".to_string())),
+                    false => (key.clone(), redact(value)),
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redact).collect()),
+        value => value.clone(),
+    }
+}
+
+impl AqaraClient {
+    /// 以指定操作者身份调用 (Act on behalf of an actor)
+    ///
+    /// 返回共享连接池的客户端视图，其调用的审计记录携带该操作者。
+    /// Returns a client view sharing the connection pool whose calls carry the
+    /// actor in their audit records.
+    ///
+    /// # Parameters 参数
+    /// - `actor`: 操作者标识 (如员工账号) / Actor identifier (e.g. a staff account)
+    pub fn with_actor(&self, actor: &str) -> AqaraClient {
+        AqaraClient {
+            actor: Some(actor.to_string()),
+            ..self.clone()
+        }
+    }
+
+    /// 当前视图的操作者 / Actor of this view
+    pub fn actor(&self) -> Option<&str> {
+        self.actor.as_deref()
+    }
+}
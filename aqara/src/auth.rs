@@ -0,0 +1,284 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{AqaraClient, AqaraError, AqaraResponse, ConfigProblem, ErrorCode, TokenBundle};
+
+/// 应用模式 (App mode)
+///
+/// Aqara 区分项目应用 (虚拟账号) 与用户授权应用，两者可用的授权接口不同。
+/// Aqara distinguishes project apps (virtual accounts) from user-authorized apps;
+/// each has its own set of auth intents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AppMode {
+    /// 用户授权应用：通过授权码换取 token / User-authorized app: exchange an auth code for tokens
+    User,
+    /// 项目应用：创建虚拟账号直接获取 token / Project app: create virtual accounts that receive tokens directly
+    Project,
+}
+
+impl AppMode {
+    // 该模式下不可用的授权接口 / Auth intents not available in this mode
+    fn unsupported_intents(self) -> &'static [&'static str] {
+        match self {
+            AppMode::User => &["config.auth.createAccount"],
+            AppMode::Project => &["config.auth.getAuthCode", "config.auth.getToken"],
+        }
+    }
+
+    pub(crate) fn check_intent(self, intent: &str) -> Result<(), AqaraError> {
+        if self.unsupported_intents().contains(&intent) {
+            return Err(AqaraError::WrongAppMode {
+                intent: intent.to_string(),
+                mode: self,
+            });
+        }
+        Ok(())
+    }
+
+    // 该模式下新 token 的默认有效期 / Default validity of new tokens in this mode
+    pub(crate) fn default_token_validity(self) -> &'static str {
+        match self {
+            AppMode::User => "7d",
+            AppMode::Project => "30d",
+        }
+    }
+}
+
+impl AqaraClient {
+    /// 当前应用模式 (未配置时为 `None`，不做检查)
+    /// Current app mode (`None` when not configured, in which case nothing is checked)
+    pub fn app_mode(&self) -> Option<AppMode> {
+        self.inner.app_mode
+    }
+
+    /// 获取Token (Get token)
+    ///
+    /// intent: config.auth.getToken
+    ///
+    /// # Parameters 参数
+    /// - `auth_code`: 授权码 / Auth code
+    /// - `account`: 用户账户 / User account
+    /// - `account_type`: 账户类型 / Account type
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn config_auth_get_token(
+        &self,
+        auth_code: &str,
+        account: &str,
+        account_type: i32,
+    ) -> Result<String, AqaraError> {
+        let data = json!({
+            "authCode": auth_code,
+            "account": account,
+            "accountType": account_type
+        });
+        self.send_api_request("config.auth.getToken", data, false)
+            .await
+    }
+
+    /// 获取Token (类型化) (Get token, typed)
+    ///
+    /// intent: config.auth.getToken
+    ///
+    /// 开启 `auto_install_tokens` 时，令牌成为当前视图签名所用的令牌 (参见 `set_tokens`)，
+    /// 并以返回的 openId 为键写入令牌表，随后也可用 `as_user(&open_id)` 调用。
+    /// With `auto_install_tokens` on, the tokens become the ones this view signs
+    /// with (see `set_tokens`) and are also written into the token map under the
+    /// returned openId, ready for `as_user(&open_id)`.
+    ///
+    /// # Parameters 参数
+    /// - `auth_code`: 授权码 / Auth code
+    /// - `account`: 用户账户 / User account
+    /// - `account_type`: 账户类型 / Account type
+    ///
+    /// # Returns
+    /// 成功返回令牌 / Returns the tokens on success
+    pub async fn config_auth_get_token_typed(
+        &self,
+        auth_code: &str,
+        account: &str,
+        account_type: i32,
+    ) -> Result<TokenBundle, AqaraError> {
+        let data = json!({
+            "authCode": auth_code,
+            "account": account,
+            "accountType": account_type
+        });
+        let response = self
+            .call_api::<Value>("config.auth.getToken", data, false)
+            .await?;
+        let bundle = self.token_bundle(response)?;
+        self.install_tokens(&bundle);
+        Ok(bundle)
+    }
+
+    /// 刷新Token (类型化) (Refresh token, typed)
+    ///
+    /// intent: config.auth.refreshToken
+    ///
+    /// 开启 `auto_install_tokens` 时的写入方式同 `config_auth_get_token_typed`。
+    /// With `auto_install_tokens` on, the tokens are installed as in `config_auth_get_token_typed`.
+    ///
+    /// # Parameters 参数
+    /// - `refresh_token`: 需要刷新的RefreshToken / The refresh token to be used
+    ///
+    /// # Returns
+    /// 成功返回令牌 / Returns the tokens on success
+    pub async fn config_auth_refresh_token_typed(
+        &self,
+        refresh_token: &str,
+    ) -> Result<TokenBundle, AqaraError> {
+        let data = json!({ "refreshToken": refresh_token });
+        let response = self
+            .call_api::<Value>("config.auth.refreshToken", data, false)
+            .await?;
+        let mut bundle = self.token_bundle(response)?;
+        if bundle.refresh_token.is_empty() {
+            bundle.refresh_token = refresh_token.to_string();
+        }
+        self.install_tokens(&bundle);
+        Ok(bundle)
+    }
+
+    // 解析令牌，缺少访问令牌时报错 / Parse the tokens, failing when the access token is missing
+    fn token_bundle(&self, response: AqaraResponse<Value>) -> Result<TokenBundle, AqaraError> {
+        let intent = response.intent.clone();
+        let request_id = response.request_id.clone();
+        let bundle = TokenBundle::from_result(&response.into_result()?);
+        if bundle.access_token.is_empty() {
+            return Err(AqaraError::MissingResult { intent, request_id });
+        }
+        Ok(bundle)
+    }
+
+    // 按 `auto_install_tokens` 写入当前视图及 openId 对应的令牌
+    // Install into this view and under the openId when `auto_install_tokens` is on
+    pub(crate) fn install_tokens(&self, bundle: &TokenBundle) {
+        if !self.inner.auto_install_tokens {
+            return;
+        }
+        let tokens = bundle.to_user_tokens();
+        if !bundle.open_id.is_empty() && self.user.as_deref() != Some(bundle.open_id.as_str()) {
+            self.inner.tokens.insert(&bundle.open_id, tokens.clone());
+        }
+        self.store_tokens(tokens);
+    }
+
+    /// 创建虚拟账号 (Create virtual account)
+    ///
+    /// intent: config.auth.createAccount
+    ///
+    /// # Parameters 参数
+    /// - `account_id`: 虚拟账号ID / Virtual account ID
+    /// - `remark`: 备注 (可选) / Remark (optional)
+    /// - `access_token_validity`: AccessToken有效期 (可选) / Validity of the access token (optional)
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn config_auth_create_account(
+        &self,
+        account_id: &str,
+        remark: Option<&str>,
+        access_token_validity: Option<&str>,
+    ) -> Result<String, AqaraError> {
+        let mode = self.inner.app_mode.unwrap_or(AppMode::Project);
+        let mut data = json!({
+            "accountId": account_id,
+            "needAccessToken": true,
+            "accessTokenValidity": access_token_validity.unwrap_or(mode.default_token_validity())
+        });
+        if let Some(remark) = remark {
+            data["remark"] = json!(remark);
+        }
+        self.send_api_request("config.auth.createAccount", data, false)
+            .await
+    }
+
+    /// 校验凭据 (Validate credentials)
+    ///
+    /// intent: query.position.info
+    ///
+    /// 先不带 token 发送一次签名请求，确认地址、应用ID、密钥ID和应用密钥可用；
+    /// 配置了 token 时再带 token 请求一次。失败时返回 `AqaraError::InvalidConfig`，
+    /// 指出需要修正的配置项，便于引导用户完成设置。
+    /// Sends one signed request without a token to confirm the endpoint, app id,
+    /// key id and app key, then one with the token if one is set. Failures come
+    /// back as `AqaraError::InvalidConfig` naming the setting to fix, for setup flows.
+    pub async fn validate_credentials(&self) -> Result<(), AqaraError> {
+        let data = json!({ "parentPositionId": "", "pageNum": 1, "pageSize": 1 });
+        match self
+            .call_api::<Value>("query.position.info", data.clone(), false)
+            .await
+        {
+            Ok(_) => {}
+            Err(e) => {
+                match e.code_enum() {
+                    Some(ErrorCode::TokenAbsent) => {}
+                    Some(ErrorCode::InvalidSignature) => {
+                        return Err(credential_problem(
+                            "app_key",
+                            "the signature was rejected; check the app key",
+                        ))
+                    }
+                    Some(ErrorCode::IllegalAppKey) => return Err(credential_problem(
+                        "key_id",
+                        "the app id and key id were not recognized; check both match the app key",
+                    )),
+                    Some(_) => return Err(e),
+                    None if is_transport(&e) => {
+                        return Err(credential_problem(
+                            "region",
+                            &format!(
+                                "{} could not be reached ({}); check the region or base URL",
+                                self.inner.base_url, e
+                            ),
+                        ))
+                    }
+                    None => return Err(e),
+                }
+            }
+        }
+
+        if self.access_token()?.is_empty() {
+            return Ok(());
+        }
+        match self
+            .call_api::<Value>("query.position.info", data, true)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) => match e.code_enum() {
+                Some(ErrorCode::TokenExpired) => Err(credential_problem(
+                    "access_token",
+                    "the access token has expired; refresh it or sign in again",
+                )),
+                Some(ErrorCode::InvalidSignature) | Some(ErrorCode::TokenAbsent) => {
+                    Err(credential_problem(
+                        "access_token",
+                        "the access token was rejected; check it belongs to this app",
+                    ))
+                }
+                _ => Err(e),
+            },
+        }
+    }
+}
+
+// 传输失败 (穿过重试与合并) / Transport failure (through retries and coalescing)
+fn is_transport(error: &AqaraError) -> bool {
+    match error {
+        AqaraError::Http(_) => true,
+        AqaraError::RetriesExhausted { last, .. } => is_transport(last),
+        AqaraError::Shared(e) => is_transport(e),
+        _ => false,
+    }
+}
+
+fn credential_problem(field: &str, message: &str) -> AqaraError {
+    AqaraError::InvalidConfig(vec![ConfigProblem {
+        field: field.to_string(),
+        message: message.to_string(),
+    }])
+}
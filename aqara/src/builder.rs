@@ -0,0 +1,649 @@
+use std::sync::Arc;
+
+use reqwest::header::HeaderValue;
+use reqwest::{Certificate, Client, Proxy, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::circuit_breaker::CircuitBreaker;
+use crate::clock_skew::ClockSkew;
+use crate::dns::CachingResolver;
+use crate::policy::IntentPolicy;
+use crate::quota::QuotaTracker;
+use crate::retry::RetryThrottle;
+use crate::scheduler::Scheduler;
+use crate::single_flight::SingleFlight;
+use crate::util::IntentMap;
+use crate::{
+    AppMode, AqaraClient, AqaraConfig, AqaraError, AuditSink, CircuitBreakerConfig, ClientInner,
+    ConfigProblem, DnsCacheConfig, HedgingConfig, Middleware, Priority, QuotaLimits, Region,
+    RetryBudget, RetryConfig, RetryPolicy, Signer, TagMap, TimeoutConfig, TokenMap,
+};
+
+/// 客户端级默认参数 (Client-level defaults)
+///
+/// 当调用方未显式传入时使用，适合大型安装统一调整分页大小等参数。
+/// Used whenever a call site does not pass the value explicitly, so large
+/// installations can tune page sizes and the like in one place.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClientDefaults {
+    /// 默认每页数量 (也用于自动翻页) / Default page size (also used when paging automatically)
+    pub page_size: Option<i32>,
+    /// 默认语言 (请求头 `Lang`，默认 "en") / Default language (`Lang` header, "en" if unset)
+    pub lang: Option<String>,
+    /// 默认位置ID，用于限定列表查询范围 / Default position ID scoping list queries
+    pub position_id: Option<String>,
+}
+
+// 额外信任的根证书 / Extra trusted root certificate
+enum RootCertificate {
+    Pem(Vec<u8>),
+    Der(Vec<u8>),
+}
+
+impl RootCertificate {
+    fn parse(&self) -> reqwest::Result<Certificate> {
+        match self {
+            RootCertificate::Pem(pem) => Certificate::from_pem(pem),
+            RootCertificate::Der(der) => Certificate::from_der(der),
+        }
+    }
+}
+
+/// 客户端构建器 (Client builder)
+pub struct AqaraClientBuilder {
+    config: AqaraConfig,
+    defaults: ClientDefaults,
+    timeout: TimeoutConfig,
+    intent_timeouts: IntentMap<TimeoutConfig>,
+    retry: RetryConfig,
+    retry_policy: Option<Arc<dyn RetryPolicy>>,
+    retry_budget: Option<RetryBudget>,
+    intent_retries: IntentMap<RetryConfig>,
+    intent_idempotency: IntentMap<bool>,
+    max_concurrency: Option<usize>,
+    intent_priorities: IntentMap<Priority>,
+    dns_cache: Option<DnsCacheConfig>,
+    app_mode: Option<AppMode>,
+    intent_policy: IntentPolicy,
+    base_url: Option<String>,
+    quota_limits: Option<QuotaLimits>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    compression_threshold: Option<usize>,
+    coalesce_requests: bool,
+    hedging: Option<HedgingConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    correct_clock_skew: bool,
+    signer: Option<Arc<dyn Signer>>,
+    auto_install_tokens: bool,
+    allow_insecure_http: bool,
+    proxy: Option<String>,
+    proxy_auth: Option<(String, String)>,
+    no_proxy: bool,
+    root_certificates: Vec<RootCertificate>,
+    tls_built_in_roots: bool,
+}
+
+impl AqaraClientBuilder {
+    pub(crate) fn new(config: AqaraConfig) -> Self {
+        AqaraClientBuilder {
+            config,
+            defaults: ClientDefaults::default(),
+            timeout: TimeoutConfig::default(),
+            intent_timeouts: IntentMap::default(),
+            retry: RetryConfig::disabled(),
+            retry_policy: None,
+            retry_budget: None,
+            intent_retries: IntentMap::default(),
+            intent_idempotency: IntentMap::default(),
+            max_concurrency: None,
+            intent_priorities: IntentMap::default(),
+            dns_cache: None,
+            app_mode: None,
+            intent_policy: IntentPolicy::default(),
+            base_url: None,
+            quota_limits: None,
+            audit_sink: None,
+            middleware: Vec::new(),
+            compression_threshold: None,
+            coalesce_requests: false,
+            hedging: None,
+            circuit_breaker: None,
+            correct_clock_skew: false,
+            signer: None,
+            auto_install_tokens: false,
+            allow_insecure_http: false,
+            proxy: None,
+            proxy_auth: None,
+            no_proxy: false,
+            root_certificates: Vec::new(),
+            tls_built_in_roots: true,
+        }
+    }
+
+    /// 设置客户端级默认参数 / Set client-level defaults
+    pub fn defaults(mut self, defaults: ClientDefaults) -> Self {
+        self.defaults = defaults;
+        self
+    }
+
+    /// 设置全局超时 / Set the global timeout
+    pub fn timeout(mut self, timeout: TimeoutConfig) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// 按 intent 设置超时 (Set a timeout for matching intents)
+    ///
+    /// `pattern` 为完整 intent 或以 `*` 结尾的前缀，例如 `fetch.*`、`write.ota.*`。
+    /// `pattern` is a full intent or a prefix ending in `*`, e.g. `fetch.*`, `write.ota.*`.
+    pub fn intent_timeout(mut self, pattern: &str, timeout: TimeoutConfig) -> Self {
+        self.intent_timeouts.insert(pattern, timeout);
+        self
+    }
+
+    /// 设置全局重试配置 (默认不重试) / Set the global retry config (no retries by default)
+    pub fn retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// 设置自定义重试策略 (Set a custom retry policy)
+    ///
+    /// 取代全局 `retry` 配置；`intent_retry` 与调用选项中的重试配置仍优先。
+    /// Replaces the global `retry` config; `intent_retry` and per-call retry
+    /// options still take precedence.
+    pub fn retry_policy(mut self, policy: Arc<dyn RetryPolicy>) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// 设置客户端级重试预算 (Set a client-wide retry budget)
+    ///
+    /// 对所有 intent 的重试统一限流；参见 `RetryBudget`。
+    /// Throttles retries across all intents; see `RetryBudget`.
+    pub fn retry_budget(mut self, budget: RetryBudget) -> Self {
+        self.retry_budget = Some(budget);
+        self
+    }
+
+    /// 按 intent 覆盖重试配置 (Override the retry config for matching intents)
+    ///
+    /// 例如为配额敏感的租户传入 `RetryConfig::disabled()`。
+    /// E.g. pass `RetryConfig::disabled()` for a quota-sensitive tenant.
+    pub fn intent_retry(mut self, pattern: &str, retry: RetryConfig) -> Self {
+        self.intent_retries.insert(pattern, retry);
+        self
+    }
+
+    /// 按 intent 覆盖幂等分类 (Override the idempotency classification for matching intents)
+    ///
+    /// 默认仅 `query.*`、`fetch.*` 幂等；非幂等 intent 从不重试。
+    /// By default only `query.*` and `fetch.*` are idempotent; non-idempotent
+    /// intents are never retried.
+    pub fn intent_idempotent(mut self, pattern: &str, idempotent: bool) -> Self {
+        self.intent_idempotency.insert(pattern, idempotent);
+        self
+    }
+
+    /// 限制并发请求数并启用优先级调度 (Limit concurrent requests and enable priority scheduling)
+    ///
+    /// 并发已满时交互式请求优先获得名额，后台批量任务无法饿死设备控制。
+    /// When saturated, interactive calls get slots first so background bulk jobs
+    /// cannot starve device commands.
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = Some(max_concurrency);
+        self
+    }
+
+    /// 按 intent 设置优先级 (Set the priority for matching intents)
+    ///
+    /// 默认 `fetch.*` 与 `query.ota.*` 为后台，其余为交互；单次调用可用 `CallOptions::priority` 覆盖。
+    /// By default `fetch.*` and `query.ota.*` are background, the rest interactive;
+    /// a single call can override it with `CallOptions::priority`.
+    pub fn intent_priority(mut self, pattern: &str, priority: Priority) -> Self {
+        self.intent_priorities.insert(pattern, priority);
+        self
+    }
+
+    /// 启用带 TTL 的 DNS 缓存 / Enable DNS caching with TTL control
+    pub fn dns_cache(mut self, dns_cache: DnsCacheConfig) -> Self {
+        self.dns_cache = Some(dns_cache);
+        self
+    }
+
+    /// 设置应用模式 (Set the app mode)
+    ///
+    /// 设置后，调用当前模式不支持的授权接口会直接返回 `AqaraError::WrongAppMode`，
+    /// 并据此选择新 token 的默认有效期。
+    /// Once set, calling an auth intent the mode does not support fails fast with
+    /// `AqaraError::WrongAppMode`, and the default validity of new tokens follows the mode.
+    pub fn app_mode(mut self, app_mode: AppMode) -> Self {
+        self.app_mode = Some(app_mode);
+        self
+    }
+
+    /// 允许匹配的 intent (Allow matching intents)
+    ///
+    /// 配置任一允许规则后，未匹配任何规则的 intent 都会被拒绝，返回 `AqaraError::PolicyDenied`。
+    /// Once any allow rule is configured, intents that match no rule are denied
+    /// with `AqaraError::PolicyDenied`.
+    pub fn allow_intent(mut self, pattern: &str) -> Self {
+        self.intent_policy.insert(pattern, true);
+        self
+    }
+
+    /// 拒绝匹配的 intent (Deny matching intents)
+    ///
+    /// 例如只读监控服务可拒绝 `write.*` 与 `config.*`；更具体的允许规则可覆盖。
+    /// E.g. a read-only monitoring service denies `write.*` and `config.*`; a more
+    /// specific allow rule takes precedence.
+    pub fn deny_intent(mut self, pattern: &str) -> Self {
+        self.intent_policy.insert(pattern, false);
+        self
+    }
+
+    /// 选择服务区域 (覆盖编译特性选择的区域)
+    /// Select the service region (overrides the one selected by features)
+    pub fn region(mut self, region: Region) -> Self {
+        self.base_url = Some(region.base_url().to_string());
+        self
+    }
+
+    /// 自定义接口地址 (覆盖区域特性选择的地址)
+    /// Custom endpoint (overrides the one selected by the region feature)
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(base_url.to_string());
+        self
+    }
+
+    /// 设置配额软限制 (Set quota soft limits)
+    ///
+    /// 在触发服务端硬配额前告警或限速，参见 `QuotaLimits`。
+    /// Warns or throttles before the server's hard quota is hit; see `QuotaLimits`.
+    pub fn quota_limits(mut self, limits: QuotaLimits) -> Self {
+        self.quota_limits = Some(limits);
+        self
+    }
+
+    /// 设置审计日志接收器 (Set the audit sink)
+    ///
+    /// 每次调用完成后接收一条已脱敏的 `AuditRecord`。
+    /// Receives a redacted `AuditRecord` once every call completes.
+    pub fn audit_sink(mut self, sink: Arc<dyn AuditSink>) -> Self {
+        self.audit_sink = Some(sink);
+        self
+    }
+
+    /// 安装请求中间件 (Install request middleware)
+    ///
+    /// 可多次调用，按安装顺序执行；参见 `Middleware`。
+    /// May be called repeatedly; runs in install order, see `Middleware`.
+    pub fn with_middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// 压缩较大的请求体 (Compress large request bodies)
+    ///
+    /// 请求体不小于 `min_size` 字节时以 gzip 压缩并设置 `Content-Encoding: gzip`，
+    /// 适合带宽受限的蜂窝网关批量写入资源或创建大场景。默认不压缩。
+    /// Bodies of at least `min_size` bytes are gzipped and sent with
+    /// `Content-Encoding: gzip`, for bandwidth-constrained cellular gateways doing
+    /// bulk resource writes or creating large scenes. Off by default.
+    pub fn compress_requests(mut self, min_size: usize) -> Self {
+        self.compression_threshold = Some(min_size);
+        self
+    }
+
+    /// 合并并发的相同请求 (Coalesce identical concurrent requests)
+    ///
+    /// 开启后，同一用户以相同参数并发调用同一幂等 intent 时只发出一次请求，
+    /// 所有调用方共享结果；带调用选项的请求不参与合并。默认关闭。
+    /// When on, concurrent calls of the same idempotent intent with the same
+    /// params by the same user send one request and share its result; calls with
+    /// call options are not coalesced. Off by default.
+    pub fn coalesce_requests(mut self, enabled: bool) -> Self {
+        self.coalesce_requests = enabled;
+        self
+    }
+
+    /// 对冲慢请求 (Hedge slow requests)
+    ///
+    /// 幂等 intent 的请求超过阈值仍未返回时再发出一次，采用先成功的结果；参见 `HedgingConfig`。
+    /// Idempotent requests still pending after the threshold are sent once more
+    /// and the first success wins; see `HedgingConfig`.
+    pub fn hedging(mut self, hedging: HedgingConfig) -> Self {
+        self.hedging = Some(hedging);
+        self
+    }
+
+    /// 启用熔断器 (Enable a circuit breaker)
+    ///
+    /// Aqara 故障期间快速失败并返回 `AqaraError::CircuitOpen`，而不是让大量请求等待超时；
+    /// 参见 `CircuitBreakerConfig`。
+    /// Fails fast with `AqaraError::CircuitOpen` during Aqara outages instead of
+    /// letting many requests wait for timeouts; see `CircuitBreakerConfig`.
+    pub fn circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(config);
+        self
+    }
+
+    /// 自动校正时钟偏移 (Correct clock skew automatically)
+    ///
+    /// 本地时钟漂移导致签名时间戳被拒 (业务码 105) 时，按服务器 `Date` 响应头计算偏移，
+    /// 并应用到后续请求的 `Time`；被拒的请求本身不会重发。
+    /// When a drifting local clock gets the signature timestamp rejected (code 105),
+    /// the offset is computed from the server's `Date` header and applied to the
+    /// `Time` of subsequent requests; the rejected request itself is not resent.
+    pub fn correct_clock_skew(mut self, enabled: bool) -> Self {
+        self.correct_clock_skew = enabled;
+        self
+    }
+
+    /// 替换请求签名策略 (Replace the request signing strategy)
+    ///
+    /// 设置后不再要求配置 appKey；参见 `Signer`。
+    /// The appKey is no longer required once a signer is set; see `Signer`.
+    pub fn signer(mut self, signer: Arc<dyn Signer>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// 自动写入获取到的令牌 (Install fetched tokens automatically)
+    ///
+    /// 开启后 `config_auth_get_token_typed` 与 `config_auth_refresh_token_typed` 成功时，
+    /// 新令牌即成为发起调用的视图签名所用的令牌，并按 openId 写入令牌表。
+    /// When enabled, a successful `config_auth_get_token_typed` or
+    /// `config_auth_refresh_token_typed` makes the new tokens the ones the calling
+    /// view signs with, and also stores them in the token map under the openId.
+    pub fn auto_install_tokens(mut self, enabled: bool) -> Self {
+        self.auto_install_tokens = enabled;
+        self
+    }
+
+    /// 允许使用明文 HTTP 接口地址 (Allow plain HTTP base URLs)
+    ///
+    /// 仅用于本地模拟服务或内网网关；未开启时 `http://` 地址会被拒绝。
+    /// Meant for local mock servers and on-prem gateways only; without it
+    /// `http://` base URLs are rejected.
+    pub fn allow_insecure_http(mut self, allow: bool) -> Self {
+        self.allow_insecure_http = allow;
+        self
+    }
+
+    /// 通过代理访问 (Send requests through a proxy)
+    ///
+    /// 未设置时使用系统代理环境变量 (`HTTPS_PROXY` 等)。
+    /// Without it the system proxy environment variables (`HTTPS_PROXY` etc.) apply.
+    ///
+    /// # Parameters 参数
+    /// - `url`: 代理地址 (http 或 https) / Proxy URL (http or https)
+    pub fn proxy(mut self, url: &str) -> Self {
+        self.proxy = Some(url.to_string());
+        self
+    }
+
+    /// 设置代理认证 (Set proxy credentials)
+    ///
+    /// 以 Basic 认证发送到 `proxy` 设置的代理。
+    /// Sent as Basic auth to the proxy set with `proxy`.
+    pub fn proxy_auth(mut self, username: &str, password: &str) -> Self {
+        self.proxy_auth = Some((username.to_string(), password.to_string()));
+        self
+    }
+
+    /// 不使用任何代理 (包括系统代理) / Use no proxy at all (including the system proxy)
+    pub fn no_proxy(mut self) -> Self {
+        self.no_proxy = true;
+        self
+    }
+
+    /// 信任 PEM 格式的根证书 (Trust a PEM root certificate)
+    ///
+    /// 用于使用私有 CA 的内网 Aqara 接口网关。
+    /// For on-prem Aqara API gateways that use a private CA.
+    pub fn add_root_certificate_pem(mut self, pem: &[u8]) -> Self {
+        self.root_certificates
+            .push(RootCertificate::Pem(pem.to_vec()));
+        self
+    }
+
+    /// 信任 DER 格式的根证书 / Trust a DER root certificate
+    pub fn add_root_certificate_der(mut self, der: &[u8]) -> Self {
+        self.root_certificates
+            .push(RootCertificate::Der(der.to_vec()));
+        self
+    }
+
+    /// 是否信任系统内置根证书 (默认信任) (Whether to trust the built-in root certificates, on by default)
+    ///
+    /// 关闭后只信任 `add_root_certificate_*` 添加的证书，可用于将接口地址限定到私有 CA。
+    /// When off only certificates added with `add_root_certificate_*` are trusted,
+    /// which pins the endpoint to a private CA.
+    pub fn tls_built_in_root_certs(mut self, enabled: bool) -> Self {
+        self.tls_built_in_roots = enabled;
+        self
+    }
+
+    /// 校验配置 (Validate the configuration)
+    ///
+    /// 一次性收集所有问题而不是在第一个问题处失败。
+    /// Collects every problem instead of stopping at the first one.
+    pub fn validate(&self) -> Result<(), AqaraError> {
+        let mut problems = Vec::new();
+        let mut problem = |field: &str, message: String| {
+            problems.push(ConfigProblem {
+                field: field.to_string(),
+                message,
+            })
+        };
+
+        if let Some(base_url) = &self.base_url {
+            match Url::parse(base_url) {
+                Ok(url) if url.scheme() == "http" && !self.allow_insecure_http => problem(
+                    "base_url",
+                    "plain http requires allow_insecure_http(true)".to_string(),
+                ),
+                Ok(url) if url.scheme() != "https" && url.scheme() != "http" => {
+                    problem("base_url", format!("unsupported scheme {}", url.scheme()))
+                }
+                Ok(_) => {}
+                Err(e) => problem("base_url", format!("invalid url: {}", e)),
+            }
+        }
+
+        if let Some(proxy) = &self.proxy {
+            match Url::parse(proxy) {
+                Ok(url) if url.scheme() != "https" && url.scheme() != "http" => {
+                    problem("proxy", format!("unsupported scheme {}", url.scheme()))
+                }
+                Ok(_) => {}
+                Err(e) => problem("proxy", format!("invalid url: {}", e)),
+            }
+            if self.no_proxy {
+                problem("proxy", "conflicts with no_proxy".to_string());
+            }
+        } else if self.proxy_auth.is_some() {
+            problem("proxy_auth", "requires proxy to be set".to_string());
+        }
+
+        for (i, certificate) in self.root_certificates.iter().enumerate() {
+            if let Err(e) = certificate.parse() {
+                problem(
+                    &format!("root_certificates[{}]", i),
+                    format!("invalid certificate: {}", e),
+                );
+            }
+        }
+        if !self.tls_built_in_roots && self.root_certificates.is_empty() {
+            problem(
+                "tls_built_in_root_certs",
+                "disabling built-in roots requires at least one added root certificate".to_string(),
+            );
+        }
+
+        for (field, value) in [
+            ("app_id", &self.config.app_id),
+            ("key_id", &self.config.key_id),
+            ("access_token", &self.config.access_token),
+        ] {
+            if HeaderValue::from_str(value).is_err() {
+                problem(field, "not a valid header value".to_string());
+            }
+        }
+        for (field, value) in [
+            ("app_id", &self.config.app_id),
+            ("key_id", &self.config.key_id),
+            ("app_key", &self.config.app_key),
+        ] {
+            // 自定义签名器自行持有密钥 / A custom signer holds its own key
+            if value.is_empty() && !(field == "app_key" && self.signer.is_some()) {
+                problem(field, "must not be empty".to_string());
+            }
+        }
+        if let Some(lang) = &self.defaults.lang {
+            if HeaderValue::from_str(lang).is_err() {
+                problem("defaults.lang", "not a valid header value".to_string());
+            }
+        }
+        if self.defaults.page_size.is_some_and(|size| size <= 0) {
+            problem("defaults.page_size", "must be positive".to_string());
+        }
+
+        let retries = std::iter::once(("retry".to_string(), &self.retry)).chain(
+            self.intent_retries
+                .iter()
+                .map(|(pattern, retry)| (format!("intent_retry[{}]", pattern), retry)),
+        );
+        for (field, retry) in retries {
+            if retry.max_retries > 0 && retry.initial_backoff.is_zero() {
+                problem(&field, "initial_backoff must be positive".to_string());
+            }
+            if retry.max_backoff < retry.initial_backoff {
+                problem(
+                    &field,
+                    "max_backoff is shorter than initial_backoff".to_string(),
+                );
+            }
+        }
+
+        if self
+            .retry_budget
+            .is_some_and(|budget| budget.window.is_zero())
+        {
+            problem("retry_budget.window", "must be positive".to_string());
+        }
+
+        let timeouts = std::iter::once(("timeout".to_string(), &self.timeout)).chain(
+            self.intent_timeouts
+                .iter()
+                .map(|(pattern, timeout)| (format!("intent_timeout[{}]", pattern), timeout)),
+        );
+        for (field, timeout) in timeouts {
+            if timeout.connect.is_some_and(|t| t.is_zero())
+                || timeout.request.is_some_and(|t| t.is_zero())
+            {
+                problem(&field, "timeouts must be positive".to_string());
+            }
+        }
+
+        if self.max_concurrency == Some(0) {
+            problem("max_concurrency", "must be at least 1".to_string());
+        }
+        if self.dns_cache.is_some_and(|dns| dns.ttl.is_zero()) {
+            problem("dns_cache.ttl", "must be positive".to_string());
+        }
+
+        if let Some(limits) = &self.quota_limits {
+            if limits.daily == Some(0) {
+                problem("quota_limits.daily", "must be positive".to_string());
+            }
+            if limits.qps == Some(0) {
+                problem("quota_limits.qps", "must be positive".to_string());
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(AqaraError::InvalidConfig(problems))
+        }
+    }
+
+    /// 构建客户端并校验凭据 (Build the client and validate its credentials)
+    ///
+    /// intent: query.position.info
+    ///
+    /// 构建后调用 `AqaraClient::validate_credentials`，适用于初次设置流程。
+    /// Calls `AqaraClient::validate_credentials` after building; meant for setup flows.
+    pub async fn build_and_validate(self) -> Result<AqaraClient, AqaraError> {
+        let client = self.build()?;
+        client.validate_credentials().await?;
+        Ok(client)
+    }
+
+    /// 构建客户端 (Build the client)
+    ///
+    /// 配置有误时返回列出全部问题的 `AqaraError::InvalidConfig`。
+    /// Returns `AqaraError::InvalidConfig` listing every problem when the configuration is invalid.
+    pub fn build(self) -> Result<AqaraClient, AqaraError> {
+        self.validate()?;
+        let mut client = Client::builder();
+        if let Some(connect) = self.timeout.connect {
+            client = client.connect_timeout(connect);
+        }
+        if let Some(dns_cache) = self.dns_cache {
+            client = client.dns_resolver(Arc::new(CachingResolver::new(dns_cache)));
+        }
+        for certificate in &self.root_certificates {
+            client = client.add_root_certificate(certificate.parse()?);
+        }
+        if !self.tls_built_in_roots {
+            client = client.tls_built_in_root_certs(false);
+        }
+        if self.no_proxy {
+            client = client.no_proxy();
+        } else if let Some(proxy) = &self.proxy {
+            let mut proxy = Proxy::all(proxy)?;
+            if let Some((username, password)) = &self.proxy_auth {
+                proxy = proxy.basic_auth(username, password);
+            }
+            client = client.proxy(proxy);
+        }
+        let client = client.build()?;
+        Ok(AqaraClient::from_inner(ClientInner {
+            primary_tokens: crate::tokens::primary_slot(&self.config),
+            config: self.config,
+            client,
+            base_url: self
+                .base_url
+                .unwrap_or_else(|| crate::default_base_url().to_string()),
+            defaults: self.defaults,
+            timeout: self.timeout,
+            intent_timeouts: self.intent_timeouts,
+            retry: self.retry,
+            retry_policy: self.retry_policy,
+            retry_throttle: self.retry_budget.map(RetryThrottle::new),
+            intent_retries: self.intent_retries,
+            intent_idempotency: self.intent_idempotency,
+            scheduler: self.max_concurrency.map(Scheduler::new),
+            intent_priorities: self.intent_priorities,
+            lifecycle: Default::default(),
+            app_mode: self.app_mode,
+            intent_policy: self.intent_policy,
+            tokens: TokenMap::default(),
+            tags: TagMap::default(),
+            quota: QuotaTracker::new(self.quota_limits),
+            audit_sink: self.audit_sink,
+            middleware: self.middleware,
+            compression_threshold: self.compression_threshold,
+            single_flight: self.coalesce_requests.then(SingleFlight::default),
+            hedging: self.hedging,
+            circuit_breaker: self.circuit_breaker.map(CircuitBreaker::new),
+            clock_skew: self.correct_clock_skew.then(ClockSkew::default),
+            signer: self.signer,
+            auto_install_tokens: self.auto_install_tokens,
+        }))
+    }
+}
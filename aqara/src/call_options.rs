@@ -0,0 +1,71 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{AqaraClient, Priority, RetryConfig};
+
+/// 单次调用选项 (Per-call options)
+///
+/// 覆盖构建时的语言、请求超时、重试配置与优先级，并可附加请求头，适用于多语言服务端等场景。
+/// Overrides the build-time language, request timeout, retry config and priority
+/// and adds extra headers, e.g. for servers answering users in several locales.
+#[derive(Debug, Clone, Default)]
+pub struct CallOptions {
+    pub(crate) lang: Option<String>,
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) retry: Option<RetryConfig>,
+    pub(crate) priority: Option<Priority>,
+}
+
+impl CallOptions {
+    /// 设置 `Lang` 请求头 / Set the `Lang` header
+    pub fn lang(mut self, lang: &str) -> Self {
+        self.lang = Some(lang.to_string());
+        self
+    }
+
+    /// 附加请求头 / Add an extra header
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// 设置请求超时 (优先于按 intent 的超时) / Set the request timeout (takes precedence over per-intent timeouts)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// 设置重试配置 (非幂等 intent 仍不重试) / Set the retry config (non-idempotent intents are still never retried)
+    pub fn retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// 设置请求优先级 (优先于按 intent 的优先级) / Set the request priority (takes precedence over per-intent priorities)
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+}
+
+impl AqaraClient {
+    /// 使用单次调用选项 (Use per-call options)
+    ///
+    /// 返回共享连接池的客户端视图，其调用均应用这些选项。
+    /// Returns a client view sharing the connection pool whose calls apply the options.
+    ///
+    /// # Parameters 参数
+    /// - `options`: 调用选项 / Call options
+    pub fn with_options(&self, options: CallOptions) -> AqaraClient {
+        AqaraClient {
+            options: Some(Arc::new(options)),
+            ..self.clone()
+        }
+    }
+
+    /// 当前视图的调用选项 / Call options of this view
+    pub fn options(&self) -> Option<&CallOptions> {
+        self.options.as_deref()
+    }
+}
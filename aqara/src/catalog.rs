@@ -0,0 +1,196 @@
+//! 资源ID目录 (Resource id catalog)
+//!
+//! 按型号族整理常用资源ID，避免在代码中硬编码 `"4.1.85"` 这类字符串；
+//! `ResourceCatalog` 再用 `query.resource.info` 的结果确认型号确实提供该资源。
+//! Maps common resources to their ids per model family so code does not
+//! hard-code strings such as `"4.1.85"`; `ResourceCatalog` then confirms with
+//! `query.resource.info` that a model actually exposes the resource.
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    AqaraClient, AqaraError, BATTERY_RESOURCE_ID, COVER_CONTROL_RESOURCE_ID,
+    COVER_POSITION_RESOURCE_ID, ENERGY_RESOURCE_ID, HUMIDITY_RESOURCE_ID,
+    LIGHT_BRIGHTNESS_RESOURCE_ID, LIGHT_COLOR_RESOURCE_ID, LIGHT_COLOR_TEMPERATURE_RESOURCE_ID,
+    LIGHT_POWER_RESOURCE_ID, PRESSURE_RESOURCE_ID, SWITCH_POWER_RESOURCE_ID,
+    TEMPERATURE_RESOURCE_ID, THERMOSTAT_TARGET_RESOURCE_ID,
+};
+
+/// 光照度资源ID (lux) / Illuminance resource ID (lux)
+pub const ILLUMINANCE_RESOURCE_ID: &str = "0.3.85";
+
+/// 资源种类 (Resource kind)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceKind {
+    /// 开关 / Power state
+    Power,
+    /// 亮度 / Brightness
+    Brightness,
+    /// 色温 / Color temperature
+    ColorTemperature,
+    /// 颜色 / Color
+    Color,
+    /// 温度 / Temperature
+    Temperature,
+    /// 湿度 / Humidity
+    Humidity,
+    /// 气压 / Pressure
+    Pressure,
+    /// 光照度 / Illuminance
+    Illuminance,
+    /// 电量 / Battery level
+    Battery,
+    /// 用电量 / Energy consumption
+    Energy,
+    /// 窗帘位置 / Curtain position
+    CoverPosition,
+    /// 窗帘控制 / Curtain control
+    CoverControl,
+    /// 目标温度 / Target temperature
+    TargetTemperature,
+}
+
+/// 型号族 (Model family)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelFamily {
+    /// 灯 / Light
+    Light,
+    /// 墙壁开关与继电器 / Wall switch or relay
+    Switch,
+    /// 插座 / Plug
+    Plug,
+    /// 窗帘电机 / Curtain motor
+    Cover,
+    /// 温湿度传感器 / Temperature and humidity sensor
+    ClimateSensor,
+    /// 光照度传感器 / Light sensor
+    LightSensor,
+    /// 温控器 / Thermostat
+    Thermostat,
+}
+
+impl ModelFamily {
+    /// 按型号前缀识别型号族，未知时返回 `None`
+    /// Identify the family from the model prefix; `None` when unknown
+    pub fn for_model(model: &str) -> Option<Self> {
+        let family = match model {
+            m if m.starts_with("lumi.light.") => ModelFamily::Light,
+            m if m.starts_with("lumi.switch.") || m.starts_with("lumi.relay.") => {
+                ModelFamily::Switch
+            }
+            m if m.starts_with("lumi.plug.") => ModelFamily::Plug,
+            m if m.starts_with("lumi.curtain.") => ModelFamily::Cover,
+            m if m.starts_with("lumi.weather.") || m.starts_with("lumi.sensor_ht.") => {
+                ModelFamily::ClimateSensor
+            }
+            m if m.starts_with("lumi.sen_ill.") => ModelFamily::LightSensor,
+            m if m.starts_with("lumi.airrtc.") => ModelFamily::Thermostat,
+            _ => return None,
+        };
+        Some(family)
+    }
+
+    /// 型号族中资源的ID，族内没有该资源时返回 `None`
+    /// The id of a resource within the family; `None` when the family lacks it
+    pub fn resource_id(self, kind: ResourceKind) -> Option<&'static str> {
+        use ModelFamily as F;
+        use ResourceKind as K;
+        let id = match (self, kind) {
+            (F::Light, K::Power) => LIGHT_POWER_RESOURCE_ID,
+            (F::Light, K::Brightness) => LIGHT_BRIGHTNESS_RESOURCE_ID,
+            (F::Light, K::ColorTemperature) => LIGHT_COLOR_TEMPERATURE_RESOURCE_ID,
+            (F::Light, K::Color) => LIGHT_COLOR_RESOURCE_ID,
+            (F::Switch | F::Plug, K::Power) => SWITCH_POWER_RESOURCE_ID,
+            (F::Switch | F::Plug, K::Energy) => ENERGY_RESOURCE_ID,
+            (F::Cover, K::CoverPosition) => COVER_POSITION_RESOURCE_ID,
+            (F::Cover, K::CoverControl) => COVER_CONTROL_RESOURCE_ID,
+            (F::ClimateSensor | F::Thermostat, K::Temperature) => TEMPERATURE_RESOURCE_ID,
+            (F::ClimateSensor, K::Humidity) => HUMIDITY_RESOURCE_ID,
+            (F::ClimateSensor, K::Pressure) => PRESSURE_RESOURCE_ID,
+            (F::LightSensor, K::Illuminance) => ILLUMINANCE_RESOURCE_ID,
+            (F::ClimateSensor | F::LightSensor | F::Thermostat, K::Battery) => BATTERY_RESOURCE_ID,
+            (F::Thermostat, K::TargetTemperature) => THERMOSTAT_TARGET_RESOURCE_ID,
+            _ => return None,
+        };
+        Some(id)
+    }
+}
+
+/// 型号的资源ID (Resource id for a model)
+///
+/// # Parameters 参数
+/// - `model`: 设备型号 / Device model
+/// - `kind`: 资源种类 / Resource kind
+///
+/// # Returns
+/// 型号族未知或不提供该资源时返回 `None` / `None` when the family is unknown or lacks the resource
+pub fn resource_id_for(model: &str, kind: ResourceKind) -> Option<&'static str> {
+    ModelFamily::for_model(model)?.resource_id(kind)
+}
+
+/// 资源信息 (Resource info)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceInfo {
+    /// 资源ID / Resource ID
+    pub resource_id: String,
+    /// 资源名称 / Resource name
+    #[serde(default)]
+    pub name: String,
+    /// 描述 / Description
+    #[serde(default)]
+    pub description: String,
+    /// 访问权限 (位掩码: 1 读, 2 写, 4 上报) / Access (bit mask: 1 read, 2 write, 4 report)
+    #[serde(default)]
+    pub access: i32,
+}
+
+/// 型号的资源目录 (Resource catalog of a model)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceCatalog {
+    /// 设备型号 / Device model
+    pub model: String,
+    /// 型号提供的资源 / Resources the model exposes
+    pub resources: Vec<ResourceInfo>,
+}
+
+impl ResourceCatalog {
+    /// 是否提供资源 / Whether the model exposes a resource
+    pub fn contains(&self, resource_id: &str) -> bool {
+        self.resources
+            .iter()
+            .any(|resource| resource.resource_id == resource_id)
+    }
+
+    /// 资源种类对应的ID，仅在型号确实提供时返回
+    /// The id of a resource kind, only when the model actually exposes it
+    pub fn resolve(&self, kind: ResourceKind) -> Option<&'static str> {
+        resource_id_for(&self.model, kind).filter(|id| self.contains(id))
+    }
+}
+
+impl AqaraClient {
+    /// 查询型号的资源目录 (Query a model's resource catalog)
+    ///
+    /// intent: query.resource.info
+    ///
+    /// # Parameters 参数
+    /// - `model`: 设备型号 / Device model
+    ///
+    /// # Returns
+    /// 成功返回资源目录 / Returns the resource catalog on success
+    pub async fn resource_catalog(&self, model: &str) -> Result<ResourceCatalog, AqaraError> {
+        let resources = self
+            .call_api::<Vec<ResourceInfo>>("query.resource.info", json!({ "model": model }), true)
+            .await?
+            .result
+            .unwrap_or_default();
+        Ok(ResourceCatalog {
+            model: model.to_string(),
+            resources,
+        })
+    }
+}
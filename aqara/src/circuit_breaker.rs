@@ -0,0 +1,314 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+use tracing::warn;
+
+use crate::{AqaraClient, AqaraError, ErrorKind};
+
+/// 熔断器配置 (Circuit breaker config)
+///
+/// 统计窗口内失败率达到 `failure_rate` (且调用数不少于 `min_calls`) 时熔断 `open_for`，
+/// 期间所有请求立即返回 `AqaraError::CircuitOpen`；之后放行一个探测请求，成功则恢复。
+/// When the failure rate within `window` reaches `failure_rate` (with at least
+/// `min_calls` calls), the circuit opens for `open_for` and every request fails
+/// fast with `AqaraError::CircuitOpen`; afterwards one probe is let through and
+/// closes the circuit again if it succeeds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CircuitBreakerConfig {
+    /// 触发熔断的失败率 (0.0-1.0) / Failure rate that opens the circuit (0.0-1.0)
+    pub failure_rate: f64,
+    /// 窗口内计算失败率所需的最少调用数 / Minimum calls in a window before the rate counts
+    pub min_calls: u32,
+    /// 统计窗口 / Counting window
+    pub window: Duration,
+    /// 熔断持续时间 / How long the circuit stays open
+    pub open_for: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        CircuitBreakerConfig {
+            failure_rate: 0.5,
+            min_calls: 20,
+            window: Duration::from_secs(10),
+            open_for: Duration::from_secs(30),
+        }
+    }
+}
+
+/// 熔断器状态 (Circuit state)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// 正常放行 / Requests flow normally
+    Closed,
+    /// 熔断中，请求立即失败 / Open; requests fail fast
+    Open,
+    /// 半开，仅放行一个探测请求 / Half-open; a single probe is allowed
+    HalfOpen,
+}
+
+enum Phase {
+    Closed,
+    Open { until: Instant },
+    HalfOpen { probing: bool },
+}
+
+struct State {
+    phase: Phase,
+    window_started: Instant,
+    calls: u32,
+    failures: u32,
+}
+
+pub(crate) struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: Mutex<State>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
+        CircuitBreaker {
+            config,
+            state: Mutex::new(State {
+                phase: Phase::Closed,
+                window_started: Instant::now(),
+                calls: 0,
+                failures: 0,
+            }),
+        }
+    }
+
+    fn state(&self) -> CircuitState {
+        let state = self.state.lock().unwrap();
+        match state.phase {
+            Phase::Closed => CircuitState::Closed,
+            Phase::Open { until } if Instant::now() < until => CircuitState::Open,
+            Phase::Open { .. } | Phase::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+
+    // 申请放行 / Ask to let a request through
+    fn acquire(&self) -> Result<Permit<'_>, AqaraError> {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let probe = match state.phase {
+            Phase::Closed => false,
+            Phase::Open { until } if now < until => {
+                return Err(AqaraError::CircuitOpen(until - now));
+            }
+            Phase::Open { .. } | Phase::HalfOpen { probing: false } => {
+                state.phase = Phase::HalfOpen { probing: true };
+                true
+            }
+            // 探测请求进行中 / A probe is already in flight
+            Phase::HalfOpen { probing: true } => {
+                return Err(AqaraError::CircuitOpen(Duration::ZERO));
+            }
+        };
+        Ok(Permit {
+            breaker: self,
+            probe,
+            recorded: false,
+        })
+    }
+
+    fn record(&self, failed: bool) {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        match state.phase {
+            Phase::HalfOpen { .. } if failed => {
+                warn!("Aqara circuit re-opened after a failed probe");
+                state.phase = Phase::Open {
+                    until: now + self.config.open_for,
+                };
+            }
+            Phase::HalfOpen { .. } => {
+                state.phase = Phase::Closed;
+                state.window_started = now;
+                state.calls = 0;
+                state.failures = 0;
+            }
+            Phase::Closed => {
+                if now.duration_since(state.window_started) >= self.config.window {
+                    state.window_started = now;
+                    state.calls = 0;
+                    state.failures = 0;
+                }
+                state.calls += 1;
+                if failed {
+                    state.failures += 1;
+                }
+                let rate = f64::from(state.failures) / f64::from(state.calls);
+                if state.calls >= self.config.min_calls.max(1) && rate >= self.config.failure_rate {
+                    warn!(
+                        "Aqara circuit opened: {}/{} calls failed",
+                        state.failures, state.calls
+                    );
+                    state.phase = Phase::Open {
+                        until: now + self.config.open_for,
+                    };
+                }
+            }
+            // 熔断前发出的请求不再计数 / Calls started before the circuit opened no longer count
+            Phase::Open { .. } => {}
+        }
+    }
+}
+
+struct Permit<'a> {
+    breaker: &'a CircuitBreaker,
+    probe: bool,
+    recorded: bool,
+}
+
+impl Permit<'_> {
+    fn record(mut self, result: &Result<String, AqaraError>) {
+        self.recorded = true;
+        self.breaker.record(result.as_ref().is_err_and(is_outage));
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        // 探测请求被取消时允许下一个请求重新探测 / A cancelled probe lets the next request probe again
+        if self.probe && !self.recorded {
+            let mut state = self.breaker.state.lock().unwrap();
+            if let Phase::HalfOpen { probing } = &mut state.phase {
+                *probing = false;
+            }
+        }
+    }
+}
+
+// 仅计入表明 Aqara 不可用的错误 / Only errors that indicate Aqara is unavailable count
+fn is_outage(error: &AqaraError) -> bool {
+    matches!(
+        error.kind(),
+        ErrorKind::Transport | ErrorKind::Timeout | ErrorKind::Server | ErrorKind::RateLimited
+    )
+}
+
+impl AqaraClient {
+    /// 熔断器状态 (Circuit breaker state)
+    ///
+    /// # Returns
+    /// 未启用熔断器时为 `None` / `None` when no circuit breaker is configured
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.inner
+            .circuit_breaker
+            .as_ref()
+            .map(CircuitBreaker::state)
+    }
+
+    // 经熔断器发送请求 (含重试) / Send a request (with retries) through the circuit breaker
+    pub(crate) async fn send_api_request_guarded(
+        &self,
+        intent: &str,
+        data: &Value,
+        include_access_token: bool,
+    ) -> Result<String, AqaraError> {
+        let Some(breaker) = &self.inner.circuit_breaker else {
+            return self
+                .send_api_request_with_retry(intent, data, include_access_token)
+                .await;
+        };
+        let permit = breaker.acquire()?;
+        let result = self
+            .send_api_request_with_retry(intent, data, include_access_token)
+            .await;
+        permit.record(&result);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(open_for: Duration) -> CircuitBreaker {
+        CircuitBreaker::new(CircuitBreakerConfig {
+            failure_rate: 0.5,
+            min_calls: 4,
+            window: Duration::from_secs(60),
+            open_for,
+        })
+    }
+
+    fn trip(breaker: &CircuitBreaker) {
+        for failed in [false, true, false, true] {
+            breaker.record(failed);
+        }
+    }
+
+    #[test]
+    fn stays_closed_below_min_calls_or_rate() {
+        let breaker = breaker(Duration::from_secs(60));
+        for _ in 0..3 {
+            breaker.record(true);
+        }
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        let breaker = self::breaker(Duration::from_secs(60));
+        for failed in [false, false, true, false, false, true] {
+            breaker.record(failed);
+        }
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.acquire().is_ok());
+    }
+
+    #[test]
+    fn opens_at_the_failure_rate_and_fails_fast() {
+        let breaker = breaker(Duration::from_secs(60));
+        trip(&breaker);
+        assert_eq!(breaker.state(), CircuitState::Open);
+        match breaker.acquire() {
+            Err(AqaraError::CircuitOpen(wait)) => assert!(wait > Duration::from_secs(50)),
+            _ => panic!("expected CircuitOpen"),
+        };
+    }
+
+    #[test]
+    fn lets_a_single_probe_through_once_open_for_elapses() {
+        let breaker = breaker(Duration::ZERO);
+        trip(&breaker);
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        let probe = breaker.acquire().unwrap();
+        assert!(probe.probe);
+        assert!(matches!(
+            breaker.acquire(),
+            Err(AqaraError::CircuitOpen(wait)) if wait.is_zero()
+        ));
+        probe.record(&Ok(String::new()));
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn failed_probe_reopens_the_circuit() {
+        let breaker = breaker(Duration::ZERO);
+        trip(&breaker);
+        let _probe = breaker.acquire().unwrap();
+        breaker.record(true);
+        assert!(matches!(
+            breaker.state.lock().unwrap().phase,
+            Phase::Open { .. }
+        ));
+    }
+
+    #[test]
+    fn cancelled_probe_lets_the_next_request_probe() {
+        let breaker = breaker(Duration::ZERO);
+        trip(&breaker);
+        drop(breaker.acquire().unwrap());
+        assert!(breaker.acquire().unwrap().probe);
+    }
+
+    #[test]
+    fn only_outages_count_as_failures() {
+        assert!(!is_outage(&AqaraError::InvalidParams(Vec::new())));
+        assert!(!is_outage(&AqaraError::PolicyDenied(
+            "write.device.unbind".to_string()
+        )));
+    }
+}
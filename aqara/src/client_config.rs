@@ -0,0 +1,129 @@
+use std::env;
+
+use crate::{AqaraClientBuilder, AqaraConfig, AqaraError, ConfigProblem, Region};
+
+/// 应用ID环境变量 / Environment variable holding the app ID
+pub const ENV_APP_ID: &str = "AQARA_APP_ID";
+/// 密钥ID环境变量 / Environment variable holding the key ID
+pub const ENV_KEY_ID: &str = "AQARA_KEY_ID";
+/// 应用密钥环境变量 / Environment variable holding the app key
+pub const ENV_APP_KEY: &str = "AQARA_APP_KEY";
+/// 访问令牌环境变量 (可选) / Environment variable holding the access token (optional)
+pub const ENV_ACCESS_TOKEN: &str = "AQARA_ACCESS_TOKEN";
+/// 服务区域环境变量 (可选) / Environment variable holding the region (optional)
+pub const ENV_REGION: &str = "AQARA_REGION";
+
+// 配置文件内容 / Config file contents
+#[cfg(feature = "config-file")]
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    app_id: String,
+    key_id: String,
+    app_key: String,
+    #[serde(default)]
+    access_token: String,
+    region: Option<String>,
+    base_url: Option<String>,
+    app_mode: Option<crate::AppMode>,
+    defaults: Option<crate::ClientDefaults>,
+}
+
+impl AqaraClientBuilder {
+    /// 从环境变量创建构建器 (Create a builder from environment variables)
+    ///
+    /// 读取 `AQARA_APP_ID`、`AQARA_KEY_ID`、`AQARA_APP_KEY`，以及可选的
+    /// `AQARA_ACCESS_TOKEN`、`AQARA_REGION` (如 `china`、`usa`、`europe`)。
+    /// 缺少的变量一并列在 `AqaraError::InvalidConfig` 中。
+    /// Reads `AQARA_APP_ID`, `AQARA_KEY_ID`, `AQARA_APP_KEY` and the optional
+    /// `AQARA_ACCESS_TOKEN` and `AQARA_REGION` (e.g. `china`, `usa`, `europe`).
+    /// Every missing variable is listed in `AqaraError::InvalidConfig`.
+    pub fn from_env() -> Result<Self, AqaraError> {
+        let mut problems = Vec::new();
+        let mut required = |name: &str| {
+            env::var(name).unwrap_or_else(|_| {
+                problems.push(ConfigProblem {
+                    field: name.to_string(),
+                    message: "environment variable is not set".to_string(),
+                });
+                String::new()
+            })
+        };
+        let config = AqaraConfig {
+            app_id: required(ENV_APP_ID),
+            key_id: required(ENV_KEY_ID),
+            app_key: required(ENV_APP_KEY),
+            access_token: env::var(ENV_ACCESS_TOKEN).unwrap_or_default(),
+        };
+        let region = match env::var(ENV_REGION) {
+            Ok(region) => match region.parse::<Region>() {
+                Ok(region) => Some(region),
+                Err(e) => {
+                    problems.push(ConfigProblem {
+                        field: ENV_REGION.to_string(),
+                        message: e.to_string(),
+                    });
+                    None
+                }
+            },
+            Err(_) => None,
+        };
+        if !problems.is_empty() {
+            return Err(AqaraError::InvalidConfig(problems));
+        }
+
+        let builder = AqaraClientBuilder::new(config);
+        Ok(match region {
+            Some(region) => builder.region(region),
+            None => builder,
+        })
+    }
+
+    /// 从 TOML 配置文件创建构建器 (Create a builder from a TOML config file)
+    ///
+    /// 需启用 `config-file` 特性。必填 `app_id`、`key_id`、`app_key`；可选 `access_token`、
+    /// `region`、`base_url`、`app_mode` 与 `[defaults]` 表。
+    /// Requires the `config-file` feature. `app_id`, `key_id` and `app_key` are
+    /// required; `access_token`, `region`, `base_url`, `app_mode` and a
+    /// `[defaults]` table are optional.
+    ///
+    /// # Parameters 参数
+    /// - `path`: 配置文件路径 / Config file path
+    #[cfg(feature = "config-file")]
+    pub fn from_config_file(path: impl AsRef<std::path::Path>) -> Result<Self, AqaraError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+        let file: ConfigFile = toml::from_str(&contents).map_err(|e| {
+            AqaraError::InvalidConfig(vec![ConfigProblem {
+                field: path.display().to_string(),
+                message: e.message().to_string(),
+            }])
+        })?;
+
+        let mut builder = AqaraClientBuilder::new(AqaraConfig {
+            access_token: file.access_token,
+            app_id: file.app_id,
+            key_id: file.key_id,
+            app_key: file.app_key,
+        });
+        if let Some(region) = &file.region {
+            let region = region.parse::<Region>().map_err(|e| {
+                AqaraError::InvalidConfig(vec![ConfigProblem {
+                    field: "region".to_string(),
+                    message: e.to_string(),
+                }])
+            })?;
+            builder = builder.region(region);
+        }
+        if let Some(base_url) = &file.base_url {
+            builder = builder.base_url(base_url);
+        }
+        if let Some(app_mode) = file.app_mode {
+            builder = builder.app_mode(app_mode);
+        }
+        if let Some(defaults) = file.defaults {
+            builder = builder.defaults(defaults);
+        }
+        Ok(builder)
+    }
+}
@@ -0,0 +1,58 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::{AqaraClient, ErrorCode};
+
+// 只解析响应中的业务码 / Decode only the business code of a response
+#[derive(Deserialize)]
+struct CodeOnly {
+    code: i32,
+}
+
+// 本地时钟相对服务器的偏移 / Offset of the local clock against the server
+#[derive(Default)]
+pub(crate) struct ClockSkew {
+    offset_millis: AtomicI64,
+}
+
+impl ClockSkew {
+    // 校正本地毫秒时间戳 / Correct a local timestamp in milliseconds
+    pub(crate) fn adjust(&self, millis: i64) -> i64 {
+        millis + self.offset_millis.load(Ordering::Relaxed)
+    }
+
+    // 时间戳被拒时按服务器 `Date` 响应头重新计算偏移
+    // Recompute the offset from the server's `Date` header when the timestamp was rejected
+    pub(crate) fn observe(&self, body: &str, date: Option<&str>) {
+        let expired = serde_json::from_str::<CodeOnly>(body)
+            .is_ok_and(|body| ErrorCode::from_code(body.code) == ErrorCode::DataPackageExpired);
+        if !expired {
+            return;
+        }
+        let Some(server) = date.and_then(|date| DateTime::parse_from_rfc2822(date).ok()) else {
+            return;
+        };
+        let offset = server.timestamp_millis() - Utc::now().timestamp_millis();
+        debug!("Correcting clock skew: server is {} ms ahead", offset);
+        self.offset_millis.store(offset, Ordering::Relaxed);
+    }
+}
+
+impl AqaraClient {
+    /// 当前的时钟偏移 (Current clock skew)
+    ///
+    /// 服务器时间减去本地时间；启用时钟校正后才会更新。
+    /// Server time minus local time; only updated when clock-skew correction is enabled.
+    ///
+    /// # Returns
+    /// 未启用时钟校正时为 `None` / `None` when clock-skew correction is disabled
+    pub fn clock_skew(&self) -> Option<chrono::Duration> {
+        self.inner
+            .clock_skew
+            .as_ref()
+            .map(|skew| chrono::Duration::milliseconds(skew.offset_millis.load(Ordering::Relaxed)))
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{AqaraError, Device};
+
+/// 窗帘控制资源ID (0 关, 1 开, 2 停) / Curtain control resource ID (0 close, 1 open, 2 stop)
+pub const COVER_CONTROL_RESOURCE_ID: &str = "14.2.85";
+/// 窗帘位置资源ID (0-100%) / Curtain position resource ID (0-100%)
+pub const COVER_POSITION_RESOURCE_ID: &str = "1.1.85";
+
+/// 窗帘电机参数 (Cover motor profile)
+///
+/// 描述某型号使用的控制与位置资源ID。
+/// Describes the control and position resource ids a model uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoverProfile {
+    /// 控制资源ID / Control resource ID
+    pub control_resource_id: &'static str,
+    /// 位置资源ID / Position resource ID
+    pub position_resource_id: &'static str,
+}
+
+impl Default for CoverProfile {
+    fn default() -> Self {
+        CoverProfile {
+            control_resource_id: COVER_CONTROL_RESOURCE_ID,
+            position_resource_id: COVER_POSITION_RESOURCE_ID,
+        }
+    }
+}
+
+impl CoverProfile {
+    /// 常见窗帘型号的参数 (Profile for common curtain models)
+    ///
+    /// 覆盖窗帘伴侣、B1、C2、C3 与卷帘 E1；未知型号返回 `None`。
+    /// Covers the curtain controller, B1, C2, C3 and roller shade E1; unknown
+    /// models return `None`.
+    pub fn for_model(model: &str) -> Option<Self> {
+        match model {
+            "lumi.curtain.v1"
+            | "lumi.curtain.hagl04"
+            | "lumi.curtain.hagl07"
+            | "lumi.curtain.acn003"
+            | "lumi.curtain.acn002" => Some(CoverProfile::default()),
+            _ => None,
+        }
+    }
+}
+
+/// 窗帘控制 (Cover control)
+///
+/// 通过 `Device::cover` 获取，适用于窗帘、卷帘、百叶电机。位置以百分比表示，100 为全开。
+/// Obtained through `Device::cover`; works for curtain, roller and blind motors.
+/// Positions are percentages with 100 meaning fully open.
+pub struct CoverControl<'a> {
+    device: &'a Device,
+    profile: CoverProfile,
+}
+
+impl Device {
+    /// 窗帘控制 (默认参数) / Cover control (default profile)
+    pub fn cover(&self) -> CoverControl<'_> {
+        self.cover_with(CoverProfile::default())
+    }
+
+    /// 窗帘控制 (指定参数) / Cover control with a specific profile
+    ///
+    /// # Parameters 参数
+    /// - `profile`: 电机参数，通常为 `CoverProfile::for_model(model)` / Motor profile, usually `CoverProfile::for_model(model)`
+    pub fn cover_with(&self, profile: CoverProfile) -> CoverControl<'_> {
+        CoverControl {
+            device: self,
+            profile,
+        }
+    }
+}
+
+impl CoverControl<'_> {
+    /// 打开 / Open
+    ///
+    /// intent: write.resource.device
+    pub async fn open(&self) -> Result<(), AqaraError> {
+        self.control("1").await
+    }
+
+    /// 关闭 / Close
+    ///
+    /// intent: write.resource.device
+    pub async fn close(&self) -> Result<(), AqaraError> {
+        self.control("0").await
+    }
+
+    /// 停止 / Stop
+    ///
+    /// intent: write.resource.device
+    pub async fn stop(&self) -> Result<(), AqaraError> {
+        self.control("2").await
+    }
+
+    /// 移动到指定位置 (Move to a position)
+    ///
+    /// intent: write.resource.device
+    ///
+    /// # Parameters 参数
+    /// - `percent`: 目标位置 (0-100，超出按 100 处理) / Target position (0-100, larger values clamp to 100)
+    pub async fn set_position(&self, percent: u8) -> Result<(), AqaraError> {
+        let value = percent.min(100).to_string();
+        self.device
+            .write(&[(self.profile.position_resource_id, &value)])
+            .await
+    }
+
+    /// 当前位置 (Current position)
+    ///
+    /// intent: query.resource.value
+    ///
+    /// # Returns
+    /// 成功返回百分比，未上报时为 `None` / Returns the percentage, or `None` when not reported
+    pub async fn current_position(&self) -> Result<Option<u8>, AqaraError> {
+        let resource_id = self.profile.position_resource_id;
+        let values = self.device.read(&[resource_id]).await?;
+        Ok(values
+            .get(resource_id)
+            .and_then(|raw| raw.parse::<u8>().ok())
+            .map(|raw| raw.min(100)))
+    }
+
+    async fn control(&self, value: &str) -> Result<(), AqaraError> {
+        self.device
+            .write(&[(self.profile.control_resource_id, value)])
+            .await
+    }
+}
@@ -0,0 +1,223 @@
+//! 本地定时任务 (Local scheduled jobs)
+//!
+//! 按本地时间定时执行场景、自动化启停与资源写入，适用于云端定时无法表达的规则。
+//! Runs scenes, linkage toggles and resource writes on local-time schedules,
+//! for rules that Aqara's cloud scheduling cannot express.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Days, Local, NaiveTime, TimeZone, Weekday};
+use serde_json::{json, Value};
+use tracing::{debug, warn};
+
+use crate::{AqaraClient, AqaraError};
+
+/// 错过多久以上视为停机期间错过 / How late an occurrence must be to count as missed during downtime
+const MISSED_GRACE: chrono::Duration = chrono::Duration::minutes(1);
+
+/// 两次检查之间的最长间隔 / Longest wait between two checks
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+/// 定时规则 (Schedule)
+///
+/// 在指定星期的本地时间 `at` 触发；`days` 为空表示每天。
+/// 夏令时跳过的时间当天不触发。
+/// Fires at local time `at` on the given weekdays; empty `days` means every day.
+/// Times skipped by a DST change do not fire that day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobSchedule {
+    /// 触发时间 (本地) / Fire time (local)
+    pub at: NaiveTime,
+    /// 生效的星期 / Weekdays it applies to
+    pub days: Vec<Weekday>,
+}
+
+impl JobSchedule {
+    /// 每天固定时间 / Every day at a fixed time
+    pub fn daily(at: NaiveTime) -> Self {
+        JobSchedule {
+            at,
+            days: Vec::new(),
+        }
+    }
+
+    /// 指定星期的固定时间 / Fixed time on given weekdays
+    pub fn weekly(at: NaiveTime, days: &[Weekday]) -> Self {
+        JobSchedule {
+            at,
+            days: days.to_vec(),
+        }
+    }
+
+    fn fires_on(&self, day: Weekday) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+
+    // `from` 之后 (不含) 到 `to` (含) 之间的触发时间
+    // Fire times after `from` (exclusive) up to `to` (inclusive)
+    fn occurrences(&self, from: DateTime<Local>, to: DateTime<Local>) -> Vec<DateTime<Local>> {
+        let mut times = Vec::new();
+        let mut date = from.date_naive();
+        while date <= to.date_naive() {
+            if self.fires_on(date.weekday()) {
+                if let Some(time) = Local
+                    .from_local_datetime(&date.and_time(self.at))
+                    .earliest()
+                {
+                    if time > from && time <= to {
+                        times.push(time);
+                    }
+                }
+            }
+            match date.checked_add_days(Days::new(1)) {
+                Some(next) => date = next,
+                None => break,
+            }
+        }
+        times
+    }
+
+    fn next_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        let until = after.checked_add_days(Days::new(8))?;
+        self.occurrences(after, until).into_iter().next()
+    }
+}
+
+/// 停机后错过的触发如何处理 (Catch-up policy after downtime)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatchUpPolicy {
+    /// 跳过错过的触发 / Skip missed occurrences
+    Skip,
+    /// 恢复后补执行一次 (无论错过几次) / Run once on resume, however many were missed
+    RunOnce,
+}
+
+/// 定时动作 (Scheduled action)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobAction {
+    /// 执行场景 / Run a scene
+    RunScene { scene_id: String },
+    /// 启用或停用自动化 / Enable or disable a linkage
+    SetLinkageEnabled { linkage_id: String, enable: bool },
+    /// 写入设备资源 / Write a device resource
+    WriteResource {
+        subject_id: String,
+        resource_id: String,
+        value: String,
+    },
+}
+
+/// 定时任务 (Scheduled job)
+#[derive(Debug, Clone)]
+pub struct ScheduledJob {
+    /// 任务名称 (用于日志) / Job name (used in logs)
+    pub name: String,
+    /// 定时规则 / Schedule
+    pub schedule: JobSchedule,
+    /// 动作 / Action
+    pub action: JobAction,
+    /// 补执行策略 / Catch-up policy
+    pub catch_up: CatchUpPolicy,
+}
+
+/// 本地定时器 (Local scheduler)
+pub struct LocalScheduler {
+    client: AqaraClient,
+    jobs: Vec<ScheduledJob>,
+    resume_from: Option<DateTime<Local>>,
+}
+
+impl LocalScheduler {
+    /// 创建定时器 / Create a scheduler
+    pub fn new(client: AqaraClient) -> Self {
+        LocalScheduler {
+            client,
+            jobs: Vec::new(),
+            resume_from: None,
+        }
+    }
+
+    /// 添加任务 / Add a job
+    pub fn job(mut self, job: ScheduledJob) -> Self {
+        self.jobs.push(job);
+        self
+    }
+
+    /// 从上次停止的时间恢复 (Resume from when the scheduler last stopped)
+    ///
+    /// 该时间之后错过的触发按各任务的 `CatchUpPolicy` 处理；未设置时从启动时刻开始。
+    /// Occurrences missed since then are handled by each job's `CatchUpPolicy`;
+    /// without it the scheduler starts from the moment it runs.
+    pub fn resume_from(mut self, last_stopped: DateTime<Local>) -> Self {
+        self.resume_from = Some(last_stopped);
+        self
+    }
+
+    /// 运行定时器 (Run the scheduler)
+    ///
+    /// 持续运行直到客户端关闭；单个任务失败只记录告警。
+    /// Runs until the client shuts down; a failing job is only logged.
+    pub async fn run(self) {
+        let mut last_check = self.resume_from.unwrap_or_else(Local::now);
+        while !self.client.is_shutdown() {
+            let now = Local::now();
+            for job in &self.jobs {
+                let Some(latest) = job.schedule.occurrences(last_check, now).pop() else {
+                    continue;
+                };
+                let missed = now - latest > MISSED_GRACE;
+                if missed && job.catch_up == CatchUpPolicy::Skip {
+                    debug!("Skipping missed run of {} due at {}", job.name, latest);
+                    continue;
+                }
+                if let Err(e) = self.execute(&job.action).await {
+                    warn!("Scheduled job {} failed: {}", job.name, e);
+                }
+            }
+            last_check = now;
+
+            let next = self
+                .jobs
+                .iter()
+                .filter_map(|job| job.schedule.next_after(now))
+                .min();
+            let wait = match next {
+                Some(next) => (next - Local::now()).to_std().unwrap_or_default(),
+                None => MAX_SLEEP,
+            };
+            tokio::select! {
+                _ = tokio::time::sleep(wait.min(MAX_SLEEP)) => {}
+                _ = self.client.inner.lifecycle.closed() => break,
+            }
+        }
+    }
+
+    async fn execute(&self, action: &JobAction) -> Result<(), AqaraError> {
+        match action {
+            JobAction::RunScene { scene_id } => {
+                self.client
+                    .call::<Value>("config.scene.run", json!({ "sceneId": scene_id }))
+                    .await?;
+            }
+            JobAction::SetLinkageEnabled { linkage_id, enable } => {
+                self.client
+                    .call::<Value>(
+                        "config.linkage.enable",
+                        json!({ "linkageId": linkage_id, "enable": if *enable { 1 } else { 0 } }),
+                    )
+                    .await?;
+            }
+            JobAction::WriteResource {
+                subject_id,
+                resource_id,
+                value,
+            } => {
+                self.client
+                    .device(subject_id)
+                    .write(&[(resource_id, value)])
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,747 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::util::value_to_string;
+use crate::{
+    AqaraClient, AqaraError, AqaraResponse, ResourceQuery, DEVICE_INFO_MAX_DIDS,
+    POSITION_DETAIL_MAX_IDS, RESOURCE_VALUE_MAX_SUBJECTS,
+};
+
+/// 导出设备清单时的分页大小 / Page size used when exporting the inventory
+const INVENTORY_PAGE_SIZE: i32 = 100;
+
+/// 电量资源ID / Battery level resource ID
+pub const BATTERY_RESOURCE_ID: &str = "8.0.2001";
+
+/// 移动设备后回读校验前的建议等待时间 / Suggested wait before reading back moved devices
+pub const DEFAULT_MOVE_VERIFY_DELAY: Duration = Duration::from_secs(2);
+
+/// 设备清单格式 (Inventory format)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InventoryFormat {
+    Csv,
+    Json,
+}
+
+/// 设备清单条目 (Inventory record)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryRecord {
+    /// 设备ID / Device ID
+    pub did: String,
+    /// 设备型号 / Device model
+    pub model: String,
+    /// 设备名称 / Device name
+    pub name: String,
+    /// 固件版本 / Firmware version
+    pub firmware: String,
+    /// 位置路径 (例如 "家 / 客厅") / Position path (e.g. "Home / Living Room")
+    pub position_path: String,
+    /// 是否在线 / Whether the device is online
+    pub online: bool,
+}
+
+/// 设备电量 (Device battery level)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatteryLevel {
+    /// 设备ID / Device ID
+    pub did: String,
+    /// 设备型号 / Device model
+    pub model: String,
+    /// 设备名称 / Device name
+    pub name: String,
+    /// 位置ID / Position ID
+    pub position_id: String,
+    /// 剩余电量百分比 (未上报时为 `None`) / Remaining charge in percent (`None` when not reported)
+    pub level: Option<u8>,
+}
+
+/// 设备信息 (Device info)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceInfo {
+    /// 设备ID / Device ID
+    pub did: String,
+    /// 网关ID (子设备) / Gateway ID (sub-devices)
+    #[serde(default)]
+    pub parent_did: String,
+    /// 位置ID / Position ID
+    #[serde(default)]
+    pub position_id: String,
+    /// 设备型号 / Device model
+    #[serde(default)]
+    pub model: String,
+    /// 型号类型 (1 可挂子设备的网关, 2 不可挂子设备的网关, 3 子设备)
+    /// Model type (1 gateway with sub-devices, 2 gateway without, 3 sub-device)
+    #[serde(default)]
+    pub model_type: i32,
+    /// 设备名称 / Device name
+    #[serde(default)]
+    pub device_name: String,
+    /// 在线状态 (0 离线, 1 在线) / Online state (0 offline, 1 online)
+    #[serde(default)]
+    pub state: i32,
+    /// 固件版本 / Firmware version
+    #[serde(default)]
+    pub firmware_version: String,
+    /// 时区 / Time zone
+    #[serde(default)]
+    pub time_zone: String,
+    /// 入网时间 (毫秒) / Join time (milliseconds)
+    #[serde(default)]
+    pub create_time: i64,
+    /// 更新时间 (毫秒) / Update time (milliseconds)
+    #[serde(default)]
+    pub update_time: i64,
+}
+
+impl DeviceInfo {
+    /// 是否在线 / Whether the device is online
+    pub fn is_online(&self) -> bool {
+        self.state == 1
+    }
+}
+
+/// 设备信息分页 (Device info page)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceInfoPage {
+    /// 当前页设备 / Devices on this page
+    #[serde(default)]
+    pub data: Vec<DeviceInfo>,
+    /// 设备总数 / Total number of devices
+    #[serde(default)]
+    pub total_count: u64,
+}
+
+/// 子设备信息 (Sub-device info)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubDeviceInfo {
+    /// 设备ID / Device ID
+    pub did: String,
+    /// 网关ID / Gateway ID
+    #[serde(default)]
+    pub parent_did: String,
+    /// 设备型号 / Device model
+    #[serde(default)]
+    pub model: String,
+    /// 型号类型 / Model type
+    #[serde(default)]
+    pub model_type: i32,
+    /// 设备名称 / Device name
+    #[serde(default)]
+    pub device_name: String,
+    /// 时区 / Time zone
+    #[serde(default)]
+    pub time_zone: String,
+    /// 入网时间 (毫秒) / Join time (milliseconds)
+    #[serde(default)]
+    pub create_time: i64,
+    /// 更新时间 (毫秒) / Update time (milliseconds)
+    #[serde(default)]
+    pub update_time: i64,
+}
+
+/// 设备移动结果 (Device move outcome)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceMove {
+    /// 设备ID / Device ID
+    pub did: String,
+    /// 是否已在目标位置 / Whether the device now reports the target position
+    pub moved: bool,
+    /// 最后回读到的位置ID (未查到设备时为空) / Last position ID read back (empty when the device was not found)
+    pub position_id: String,
+}
+
+impl AqaraClient {
+    /// 查询设备信息 (Query device info)
+    ///
+    /// intent: query.device.info
+    ///
+    /// # Parameters 参数
+    /// - `dids`: 设备ID列表 (可选) / Device IDs (optional)
+    /// - `position_id`: 位置ID (可选) / Position ID (optional)
+    /// - `page_num`: 页码 (可选) / Page number (optional)
+    /// - `page_size`: 每页数量 (可选) / Page size (optional)
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn query_device_info(
+        &self,
+        dids: Option<&[&str]>,
+        position_id: Option<&str>,
+        page_num: Option<i32>,
+        page_size: Option<i32>,
+    ) -> Result<String, AqaraError> {
+        let data = self.device_info_data(dids, position_id, page_num, page_size);
+        self.send_api_request("query.device.info", data, true).await
+    }
+
+    /// 查询设备信息 (类型化) (Query device info, typed)
+    ///
+    /// intent: query.device.info
+    ///
+    /// 参数同 `query_device_info`。
+    /// Takes the same parameters as `query_device_info`.
+    ///
+    /// # Returns
+    /// 成功返回设备信息分页 / Returns a device info page on success
+    pub async fn query_device_info_typed(
+        &self,
+        dids: Option<&[&str]>,
+        position_id: Option<&str>,
+        page_num: Option<i32>,
+        page_size: Option<i32>,
+    ) -> Result<AqaraResponse<DeviceInfoPage>, AqaraError> {
+        let data = self.device_info_data(dids, position_id, page_num, page_size);
+        self.call_api("query.device.info", data, true).await
+    }
+
+    /// 查询子设备信息 (类型化) (Query sub device info, typed)
+    ///
+    /// intent: query.device.subInfo
+    ///
+    /// # Parameters 参数
+    /// - `gateway_did`: 网关ID / Gateway device ID
+    ///
+    /// # Returns
+    /// 成功返回子设备列表 / Returns the sub-devices on success
+    pub async fn query_device_sub_info_typed(
+        &self,
+        gateway_did: &str,
+    ) -> Result<AqaraResponse<Vec<SubDeviceInfo>>, AqaraError> {
+        self.call_api("query.device.subInfo", json!({ "did": gateway_did }), true)
+            .await
+    }
+
+    /// 查询设备在线状态 (Query device online states)
+    ///
+    /// intent: query.device.info
+    ///
+    /// 读取设备信息中的 `state` 字段；未查到的设备不在结果中。
+    /// Reads the `state` field of the device info; devices that are not found are
+    /// missing from the result.
+    ///
+    /// # Parameters 参数
+    /// - `dids`: 设备ID列表 / Device IDs
+    ///
+    /// # Returns
+    /// 成功返回 设备ID -> 是否在线 / Returns device id -> online on success
+    pub async fn query_device_online_states(
+        &self,
+        dids: &[&str],
+    ) -> Result<HashMap<String, bool>, AqaraError> {
+        let mut states = HashMap::new();
+        for chunk in dids.chunks(DEVICE_INFO_MAX_DIDS) {
+            let page = self
+                .query_device_info_typed(
+                    Some(chunk),
+                    None,
+                    Some(1),
+                    Some(DEVICE_INFO_MAX_DIDS as i32),
+                )
+                .await?
+                .result
+                .unwrap_or(DeviceInfoPage {
+                    data: Vec::new(),
+                    total_count: 0,
+                });
+            for device in page.data {
+                states.insert(device.did.clone(), device.is_online());
+            }
+        }
+        Ok(states)
+    }
+
+    /// 查询设备基础信息 (Query device basic info)
+    ///
+    /// intent: query.device.basicInfo
+    ///
+    /// # Parameters 参数
+    /// - `did`: 设备ID / Device ID
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn query_device_basic_info(&self, did: &str) -> Result<String, AqaraError> {
+        let data = json!({
+            "did": did
+        });
+        self.send_api_request("query.device.basicInfo", data, true)
+            .await
+    }
+
+    /// 查询设备基础信息 (类型化) (Query device basic info, typed)
+    ///
+    /// intent: query.device.basicInfo
+    ///
+    /// # Parameters 参数
+    /// - `did`: 设备ID / Device ID
+    ///
+    /// # Returns
+    /// 成功返回设备信息 / Returns the device info on success
+    pub async fn query_device_basic_info_typed(
+        &self,
+        did: &str,
+    ) -> Result<AqaraResponse<DeviceInfo>, AqaraError> {
+        self.call_api("query.device.basicInfo", json!({ "did": did }), true)
+            .await
+    }
+
+    /// 设置设备时区 (Set a device's time zone)
+    ///
+    /// intent: config.device.timezone
+    ///
+    /// # Parameters 参数
+    /// - `did`: 设备ID / Device ID
+    /// - `time_zone`: 时区，格式同 `DeviceInfo::time_zone` (例如 "GMT+08:00") / Time zone, formatted like `DeviceInfo::time_zone` (e.g. "GMT+08:00")
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn config_device_timezone(
+        &self,
+        did: &str,
+        time_zone: &str,
+    ) -> Result<String, AqaraError> {
+        let data = json!({
+            "did": did,
+            "timeZone": time_zone
+        });
+        self.send_api_request("config.device.timezone", data, true)
+            .await
+    }
+
+    /// 修改设备位置 (Move devices to a position)
+    ///
+    /// intent: config.device.position
+    ///
+    /// # Parameters 参数
+    /// - `dids`: 设备ID列表 / Device IDs
+    /// - `position_id`: 目标位置ID / Target position ID
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn config_device_position(
+        &self,
+        dids: &[&str],
+        position_id: &str,
+    ) -> Result<String, AqaraError> {
+        let data = json!({
+            "dids": dids,
+            "positionId": position_id
+        });
+        self.send_api_request("config.device.position", data, true)
+            .await
+    }
+
+    /// 设备清单 (Device inventory)
+    ///
+    /// intent: query.device.info, query.position.detail
+    ///
+    /// 分页拉取全部设备，并通过位置详情拼接位置路径。
+    /// Pages through every device and joins position names into a path.
+    ///
+    /// # Returns
+    /// 成功返回清单条目 / Returns the inventory records on success
+    pub async fn device_inventory(&self) -> Result<Vec<InventoryRecord>, AqaraError> {
+        let devices = self.list_all_devices(None).await?;
+
+        let position_ids: Vec<String> = devices
+            .iter()
+            .map(|device| value_to_string(device.get("positionId")))
+            .filter(|id| !id.is_empty())
+            .collect();
+        let positions = self.load_position_names(position_ids).await?;
+
+        Ok(devices
+            .iter()
+            .map(|device| InventoryRecord {
+                did: value_to_string(device.get("did")),
+                model: value_to_string(device.get("model")),
+                name: value_to_string(device.get("deviceName")),
+                firmware: value_to_string(device.get("firmwareVersion")),
+                position_path: position_path(
+                    &positions,
+                    &value_to_string(device.get("positionId")),
+                ),
+                online: device.get("state").and_then(Value::as_i64) == Some(1),
+            })
+            .collect())
+    }
+
+    /// 导出设备清单 (Export the device inventory)
+    ///
+    /// intent: query.device.info, query.position.detail
+    ///
+    /// 拉取设备清单 (参见 `device_inventory`) 并按格式渲染，由调用方决定写入位置。
+    /// Fetches the inventory (see `device_inventory`) and renders it in the given
+    /// format; the caller decides where to write it.
+    ///
+    /// # Parameters 参数
+    /// - `format`: 输出格式 / Output format
+    ///
+    /// # Returns
+    /// 成功返回渲染后的清单 / Returns the rendered inventory on success
+    pub async fn export_device_inventory(
+        &self,
+        format: InventoryFormat,
+    ) -> Result<String, AqaraError> {
+        let records = self.device_inventory().await?;
+        render_inventory(&records, format)
+    }
+
+    /// 移动设备并校验 (Move devices and verify)
+    ///
+    /// intent: config.device.position, query.device.info
+    ///
+    /// 移动后回读设备信息，确认每个设备已上报目标位置；未生效的设备 (常见于离线设备)
+    /// 重新移动，最多尝试 `attempts` 次，结果按 `dids` 顺序返回。
+    /// Reads device info back after moving to confirm each device reports the
+    /// target position; devices whose move did not take effect (common for offline
+    /// devices) are moved again, up to `attempts` times. Results follow `dids` order.
+    ///
+    /// # Parameters 参数
+    /// - `dids`: 设备ID列表 / Device IDs
+    /// - `position_id`: 目标位置ID / Target position ID
+    /// - `attempts`: 最多尝试次数 (0 视为 1) / Max attempts (0 is treated as 1)
+    /// - `verify_delay`: 每次移动后回读前的等待时间 (参见 `DEFAULT_MOVE_VERIFY_DELAY`) /
+    ///   Wait after each move before reading back (see `DEFAULT_MOVE_VERIFY_DELAY`)
+    ///
+    /// # Returns
+    /// 成功返回每个设备的移动结果 / Returns a move outcome per device on success
+    pub async fn move_devices_and_verify(
+        &self,
+        dids: &[&str],
+        position_id: &str,
+        attempts: usize,
+        verify_delay: Duration,
+    ) -> Result<Vec<DeviceMove>, AqaraError> {
+        let mut positions: HashMap<String, String> = HashMap::new();
+        let mut pending: Vec<&str> = dids.to_vec();
+        for _ in 0..attempts.max(1) {
+            if pending.is_empty() {
+                break;
+            }
+            self.call_api::<Value>(
+                "config.device.position",
+                json!({ "dids": pending, "positionId": position_id }),
+                true,
+            )
+            .await?;
+            tokio::time::sleep(verify_delay).await;
+            for chunk in pending.chunks(INVENTORY_PAGE_SIZE as usize) {
+                let data =
+                    self.device_info_data(Some(chunk), None, Some(1), Some(INVENTORY_PAGE_SIZE));
+                let result = self
+                    .call_api::<Value>("query.device.info", data, true)
+                    .await?
+                    .result
+                    .unwrap_or(Value::Null);
+                for device in result
+                    .get("data")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                {
+                    positions.insert(
+                        value_to_string(device.get("did")),
+                        value_to_string(device.get("positionId")),
+                    );
+                }
+            }
+            pending.retain(|did| positions.get(*did).map(String::as_str) != Some(position_id));
+        }
+        Ok(dids
+            .iter()
+            .map(|did| {
+                let reported = positions.get(*did).cloned().unwrap_or_default();
+                DeviceMove {
+                    did: did.to_string(),
+                    moved: reported == position_id,
+                    position_id: reported,
+                }
+            })
+            .collect())
+    }
+
+    /// 电量报告 (Battery report)
+    ///
+    /// intent: query.device.info, query.resource.info, query.resource.value
+    ///
+    /// 通过型号的资源目录找出带电量资源的设备，分批读取电量，按剩余电量升序返回；
+    /// 未上报电量的设备排在最后。
+    /// Finds devices whose model exposes the battery resource, reads their levels
+    /// in chunks and returns them sorted by remaining charge, lowest first; devices
+    /// that report no level come last.
+    ///
+    /// # Parameters 参数
+    /// - `position_id`: 限定位置ID (可选) / Position ID to scope the report to (optional)
+    ///
+    /// # Returns
+    /// 成功返回电量列表 / Returns the battery levels on success
+    pub async fn battery_report(
+        &self,
+        position_id: Option<&str>,
+    ) -> Result<Vec<BatteryLevel>, AqaraError> {
+        let devices = self
+            .devices_with_resource(position_id, BATTERY_RESOURCE_ID)
+            .await?;
+        let mut report: Vec<BatteryLevel> = devices
+            .iter()
+            .map(|device| BatteryLevel {
+                did: value_to_string(device.get("did")),
+                model: value_to_string(device.get("model")),
+                name: value_to_string(device.get("deviceName")),
+                position_id: value_to_string(device.get("positionId")),
+                level: None,
+            })
+            .collect();
+
+        let mut levels = HashMap::new();
+        for chunk in report.chunks(RESOURCE_VALUE_MAX_SUBJECTS) {
+            let resources: Vec<ResourceQuery> = chunk
+                .iter()
+                .map(|device| ResourceQuery {
+                    subject_id: device.did.clone(),
+                    resource_ids: vec![BATTERY_RESOURCE_ID.to_string()],
+                })
+                .collect();
+            let result = self
+                .call_api::<Value>(
+                    "query.resource.value",
+                    json!({ "resources": resources }),
+                    true,
+                )
+                .await?
+                .result
+                .unwrap_or(Value::Null);
+            for value in result.as_array().into_iter().flatten() {
+                if let Ok(level) = value_to_string(value.get("value")).parse::<u8>() {
+                    levels.insert(value_to_string(value.get("subjectId")), level);
+                }
+            }
+        }
+        for device in &mut report {
+            device.level = levels.get(&device.did).copied();
+        }
+        report.sort_by_key(|device| (device.level.is_none(), device.level));
+        Ok(report)
+    }
+
+    // 通过型号的资源目录找出带有指定资源的设备
+    // Find the devices whose model exposes `resource_id` in the resource catalog
+    pub(crate) async fn devices_with_resource(
+        &self,
+        position_id: Option<&str>,
+        resource_id: &str,
+    ) -> Result<Vec<Value>, AqaraError> {
+        let mut devices = self.list_all_devices(position_id).await?;
+        let mut models: Vec<String> = devices
+            .iter()
+            .map(|device| value_to_string(device.get("model")))
+            .filter(|model| !model.is_empty())
+            .collect();
+        models.sort();
+        models.dedup();
+        let mut matching = HashSet::new();
+        for model in models {
+            let info = self
+                .call_api::<Value>(
+                    "query.resource.info",
+                    json!({ "model": model, "resourceId": resource_id }),
+                    true,
+                )
+                .await?
+                .result
+                .unwrap_or(Value::Null);
+            if info
+                .as_array()
+                .is_some_and(|resources| !resources.is_empty())
+            {
+                matching.insert(model);
+            }
+        }
+        devices.retain(|device| matching.contains(&value_to_string(device.get("model"))));
+        Ok(devices)
+    }
+
+    // 分页拉取全部设备 / Page through every device
+    async fn list_all_devices(&self, position_id: Option<&str>) -> Result<Vec<Value>, AqaraError> {
+        let data = self.device_info_data(None, position_id, None, None);
+        self.fetch_all_pages("query.device.info", data).await
+    }
+
+    // 加载位置及其所有祖先的 (名称, 父位置ID)
+    // Load (name, parent id) for the positions and all their ancestors
+    async fn load_position_names(
+        &self,
+        mut pending: Vec<String>,
+    ) -> Result<HashMap<String, (String, String)>, AqaraError> {
+        let mut positions: HashMap<String, (String, String)> = HashMap::new();
+        while !pending.is_empty() {
+            pending.sort();
+            pending.dedup();
+            pending.retain(|id| !positions.contains_key(id));
+            let mut parents = Vec::new();
+            for chunk in pending.chunks(POSITION_DETAIL_MAX_IDS) {
+                let result = self
+                    .call_api::<Value>(
+                        "query.position.detail",
+                        json!({ "positionIds": chunk }),
+                        true,
+                    )
+                    .await?
+                    .result
+                    .unwrap_or(Value::Null);
+                for detail in result.as_array().into_iter().flatten() {
+                    let id = value_to_string(detail.get("positionId"));
+                    let parent = value_to_string(detail.get("parentPositionId"));
+                    if !parent.is_empty() && !positions.contains_key(&parent) {
+                        parents.push(parent.clone());
+                    }
+                    positions.insert(id, (value_to_string(detail.get("positionName")), parent));
+                }
+                // 查询不到的位置记为空名称，避免重复查询
+                // Unknown positions get an empty name so they are not queried again
+                for id in chunk {
+                    positions
+                        .entry(id.clone())
+                        .or_insert_with(|| (String::new(), String::new()));
+                }
+            }
+            pending = parents;
+        }
+        Ok(positions)
+    }
+
+    // 未指定设备ID时使用客户端默认位置
+    // Fall back to the client's default position when no dids are given
+    pub(crate) fn device_info_data(
+        &self,
+        dids: Option<&[&str]>,
+        position_id: Option<&str>,
+        page_num: Option<i32>,
+        page_size: Option<i32>,
+    ) -> Value {
+        let mut data = json!({
+            "pageNum": page_num.unwrap_or(1),
+            "pageSize": page_size.or(self.inner.defaults.page_size).unwrap_or(50)
+        });
+        let position_id = match dids {
+            Some(_) => position_id,
+            None => position_id.or(self.inner.defaults.position_id.as_deref()),
+        };
+        if let Some(dids) = dids {
+            data["dids"] = json!(dids);
+        }
+        if let Some(position_id) = position_id {
+            data["positionId"] = json!(position_id);
+        }
+        data
+    }
+}
+
+fn position_path(positions: &HashMap<String, (String, String)>, position_id: &str) -> String {
+    let mut names = Vec::new();
+    let mut current = position_id;
+    // 限制深度以防止环路 / Bound the depth to guard against cycles
+    while let Some((name, parent)) = positions.get(current) {
+        names.push(name.as_str());
+        if parent.is_empty() || names.len() > 32 {
+            break;
+        }
+        current = parent;
+    }
+    names.reverse();
+    names.join(" / ")
+}
+
+/// 渲染设备清单 / Render inventory records
+fn render_inventory(
+    records: &[InventoryRecord],
+    format: InventoryFormat,
+) -> Result<String, AqaraError> {
+    match format {
+        InventoryFormat::Json => Ok(serde_json::to_string_pretty(records)?),
+        InventoryFormat::Csv => {
+            let mut out = String::from("did,model,name,firmware,position_path,online\n");
+            for record in records {
+                // 写入 String 不会失败 / Writing into a String cannot fail
+                let _ = writeln!(
+                    out,
+                    "{},{},{},{},{},{}",
+                    csv_field(&record.did),
+                    csv_field(&record.model),
+                    csv_field(&record.name),
+                    csv_field(&record.firmware),
+                    csv_field(&record.position_path),
+                    record.online
+                );
+            }
+            Ok(out)
+        }
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_field_passes_plain_text_through() {
+        assert_eq!(csv_field("lumi.sensor_ht.v1"), "lumi.sensor_ht.v1");
+        assert_eq!(csv_field(""), "");
+    }
+
+    #[test]
+    fn csv_field_quotes_special_characters() {
+        assert_eq!(csv_field("Home / Living, Room"), "\"Home / Living, Room\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv_field("cr\r"), "\"cr\r\"");
+    }
+
+    #[test]
+    fn render_inventory_writes_csv_rows() {
+        let records = [InventoryRecord {
+            did: "lumi.1".to_string(),
+            model: "lumi.plug.v1".to_string(),
+            name: "Plug, desk".to_string(),
+            firmware: "1.0.0".to_string(),
+            position_path: "Home / Office".to_string(),
+            online: true,
+        }];
+        assert_eq!(
+            render_inventory(&records, InventoryFormat::Csv).unwrap(),
+            "did,model,name,firmware,position_path,online\n\
+             lumi.1,lumi.plug.v1,\"Plug, desk\",1.0.0,Home / Office,true\n"
+        );
+    }
+
+    #[test]
+    fn position_path_joins_ancestors() {
+        let positions: HashMap<String, (String, String)> =
+            [("home", ("Home", "")), ("office", ("Office", "home"))]
+                .into_iter()
+                .map(|(id, (name, parent))| {
+                    (id.to_string(), (name.to_string(), parent.to_string()))
+                })
+                .collect();
+        assert_eq!(position_path(&positions, "office"), "Home / Office");
+        assert_eq!(position_path(&positions, "unknown"), "");
+    }
+}
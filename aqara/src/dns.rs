@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+
+/// DNS 缓存配置 (DNS cache config)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DnsCacheConfig {
+    /// 解析成功的缓存时长 / How long successful lookups are cached
+    pub ttl: Duration,
+    /// 解析失败的缓存时长，`None` 表示不缓存失败
+    /// How long failed lookups are cached; `None` disables negative caching
+    pub negative_ttl: Option<Duration>,
+}
+
+impl Default for DnsCacheConfig {
+    fn default() -> Self {
+        DnsCacheConfig {
+            ttl: Duration::from_secs(300),
+            negative_ttl: Some(Duration::from_secs(5)),
+        }
+    }
+}
+
+type CacheEntry = (Instant, Result<Vec<SocketAddr>, String>);
+
+/// 带 TTL 的缓存解析器 (Caching resolver with TTL)
+///
+/// 部分嵌入式平台上系统解析很慢，而默认每建立一个新连接都会重新解析。
+/// System lookups are slow on some embedded targets and by default every new
+/// connection resolves again.
+pub(crate) struct CachingResolver {
+    config: DnsCacheConfig,
+    cache: Arc<Mutex<HashMap<String, CacheEntry>>>,
+}
+
+impl CachingResolver {
+    pub(crate) fn new(config: DnsCacheConfig) -> Self {
+        CachingResolver {
+            config,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl Resolve for CachingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        let config = self.config;
+        let cache = self.cache.clone();
+        Box::pin(async move {
+            let cached = cache
+                .lock()
+                .unwrap()
+                .get(&host)
+                .filter(|(expires_at, _)| *expires_at > Instant::now())
+                .map(|(_, result)| result.clone());
+            let result = match cached {
+                Some(result) => result,
+                None => {
+                    let result = tokio::net::lookup_host((host.as_str(), 0))
+                        .await
+                        .map(|addrs| addrs.collect::<Vec<_>>())
+                        .map_err(|e| e.to_string());
+                    let ttl = match &result {
+                        Ok(_) => Some(config.ttl),
+                        Err(_) => config.negative_ttl,
+                    };
+                    if let Some(ttl) = ttl {
+                        cache
+                            .lock()
+                            .unwrap()
+                            .insert(host.clone(), (Instant::now() + ttl, result.clone()));
+                    }
+                    result
+                }
+            };
+            let addrs: Addrs = Box::new(result?.into_iter());
+            Ok(addrs)
+        })
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::history::split_windows;
+use crate::util::value_to_string;
+use crate::{
+    AggrType, AqaraClient, AqaraError, ResourceHistoryPoint, StatisticsDimension, StatisticsParams,
+    HISTORY_MAX_PAGE_SIZE,
+};
+
+/// 累计用电量资源ID (单位 Wh) / Cumulative energy resource ID (in Wh)
+pub const ENERGY_RESOURCE_ID: &str = "0.13.85";
+
+/// 单日用电量 (Daily consumption)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyEnergy {
+    /// 设备ID / Device ID
+    pub did: String,
+    /// 位置ID / Position ID
+    pub position_id: String,
+    /// 日期 (UTC) / Date (UTC)
+    pub date: NaiveDate,
+    /// 用电量 (kWh) / Consumption (kWh)
+    pub kwh: f64,
+}
+
+/// 用电量报告 (Energy consumption report)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnergyReport {
+    /// 按设备、日期排列的单日用电量 / Daily consumption ordered by device and date
+    pub daily: Vec<DailyEnergy>,
+    /// 按设备汇总 (kWh) / Totals per device (kWh)
+    pub by_device: BTreeMap<String, f64>,
+    /// 按位置汇总 (kWh) / Totals per position (kWh)
+    pub by_position: BTreeMap<String, f64>,
+    /// 按日期汇总 (kWh) / Totals per day (kWh)
+    pub by_day: BTreeMap<NaiveDate, f64>,
+    /// 总用电量 (kWh) / Total consumption (kWh)
+    pub total_kwh: f64,
+}
+
+impl EnergyReport {
+    fn push(&mut self, day: DailyEnergy) {
+        *self.by_device.entry(day.did.clone()).or_default() += day.kwh;
+        *self.by_position.entry(day.position_id.clone()).or_default() += day.kwh;
+        *self.by_day.entry(day.date).or_default() += day.kwh;
+        self.total_kwh += day.kwh;
+        self.daily.push(day);
+    }
+}
+
+impl AqaraClient {
+    /// 用电量报告 (Energy consumption report)
+    ///
+    /// intent: query.device.info, query.resource.info, fetch.resource.statistics
+    ///
+    /// 找出带累计用电量资源的插座/开关，按天统计区间用电量并换算为 kWh，
+    /// 再按设备、位置、日期汇总。日期按 UTC 划分。
+    /// Finds the plugs and relays exposing the cumulative energy resource, pulls
+    /// per-day consumption through the statistics API, converts it to kWh and
+    /// totals it per device, position and day. Days are split in UTC.
+    ///
+    /// # Parameters 参数
+    /// - `position_id`: 限定位置ID (可选) / Position ID to scope the report to (optional)
+    /// - `start`: 开始时间 / Start time
+    /// - `end`: 结束时间 / End time
+    ///
+    /// # Returns
+    /// 成功返回用电量报告 / Returns the consumption report on success
+    pub async fn energy_report(
+        &self,
+        position_id: Option<&str>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<EnergyReport, AqaraError> {
+        let devices = self
+            .devices_with_resource(position_id, ENERGY_RESOURCE_ID)
+            .await?;
+        let mut report = EnergyReport::default();
+        for device in &devices {
+            let did = value_to_string(device.get("did"));
+            let position_id = value_to_string(device.get("positionId"));
+            let mut days: BTreeMap<NaiveDate, f64> = BTreeMap::new();
+            for (start, end) in split_windows(start, end) {
+                let params = StatisticsParams {
+                    subject_id: did.clone(),
+                    resource_ids: vec![ENERGY_RESOURCE_ID.to_string()],
+                    aggr_types: vec![AggrType::Difference],
+                    start_time: start,
+                    end_time: end,
+                    dimension: StatisticsDimension::OneDay,
+                    size: Some(HISTORY_MAX_PAGE_SIZE),
+                };
+                for point in self.fetch_statistics_points(&params).await? {
+                    let date = point.timestamp.date_naive();
+                    if let Ok(wh) = point.value.parse::<f64>() {
+                        *days.entry(date).or_default() += wh / 1000.0;
+                    }
+                }
+            }
+            for (date, kwh) in days {
+                report.push(DailyEnergy {
+                    did: did.clone(),
+                    position_id: position_id.clone(),
+                    date,
+                    kwh,
+                });
+            }
+        }
+        Ok(report)
+    }
+
+    async fn fetch_statistics_points(
+        &self,
+        params: &StatisticsParams,
+    ) -> Result<Vec<ResourceHistoryPoint>, AqaraError> {
+        let result = self
+            .call_api::<Value>("fetch.resource.statistics", params.to_request_data(), true)
+            .await?
+            .result
+            .unwrap_or(Value::Null);
+        Ok(result
+            .get("data")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(ResourceHistoryPoint::from_value)
+            .collect())
+    }
+}
@@ -0,0 +1,418 @@
+use std::fmt;
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::audit::{redact, REDACTED_KEYS};
+use crate::{AppMode, AttemptHistory};
+
+/// 配置问题 (Configuration problem)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    /// 出错的配置项 / The offending setting
+    pub field: String,
+    /// 问题描述 / What is wrong with it
+    pub message: String,
+}
+
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// 解析失败的响应片段 (Snippet of an undecodable response)
+///
+/// `text` 为脱敏后的响应文本 (令牌等字段替换为 `***`)，`offset` 为出错位置的字节偏移。
+/// `text` is the redacted response text (tokens and similar fields replaced
+/// with `***`) and `offset` is the byte offset of the failure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeSnippet {
+    /// 脱敏后的响应文本 / Redacted response text
+    pub text: String,
+    /// 出错位置的字节偏移 / Byte offset of the failure
+    pub offset: usize,
+}
+
+impl DecodeSnippet {
+    /// 定位原始响应体中的解析错误 / Locate a decode failure in a raw response body
+    ///
+    /// 可解析为 JSON 时先脱敏再重新定位；无法解析且可能含敏感字段时不保留。
+    /// JSON bodies are redacted and the failure re-located in the redacted text;
+    /// unparseable bodies that may hold secrets are dropped.
+    pub(crate) fn from_body<T: DeserializeOwned>(
+        body: &str,
+        source: &serde_json::Error,
+    ) -> Option<Box<Self>> {
+        match serde_json::from_str::<Value>(body) {
+            Ok(value) => Self::from_value::<T>(&value),
+            Err(_) if REDACTED_KEYS.iter().any(|key| body.contains(key)) => None,
+            Err(_) => Some(Box::new(Self::at(body.to_string(), source))),
+        }
+    }
+
+    /// 定位 `result` 中的解析错误 / Locate a decode failure in a `result` value
+    pub(crate) fn from_value<T: DeserializeOwned>(value: &Value) -> Option<Box<Self>> {
+        let text = serde_json::to_string_pretty(&redact(value)).ok()?;
+        let snippet = match serde_json::from_str::<T>(&text) {
+            Err(e) => Self::at(text, &e),
+            Ok(_) => DecodeSnippet { text, offset: 0 },
+        };
+        Some(Box::new(snippet))
+    }
+
+    // serde_json 的行列号均从 1 开始 / serde_json lines and columns are 1-based
+    fn at(text: String, e: &serde_json::Error) -> Self {
+        let line_start: usize = text
+            .split_inclusive('\n')
+            .take(e.line().saturating_sub(1))
+            .map(str::len)
+            .sum();
+        let offset = (line_start + e.column().saturating_sub(1)).min(text.len());
+        DecodeSnippet { text, offset }
+    }
+}
+
+/// 错误分类 (Error kind)
+///
+/// 便于区分 "Aqara 故障"、"本地网络故障" 与 "配置错误" 并分别告警。
+/// Lets callers tell "Aqara is down" from "our network is broken" from
+/// "we misconfigured something" and alert differently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ErrorKind {
+    /// 连接或网络失败 / Connection or network failure
+    Transport,
+    /// 请求超时 / Request timed out
+    Timeout,
+    /// 服务端故障 (HTTP 5xx 或后端业务码) / Server failure (HTTP 5xx or backend business codes)
+    Server,
+    /// 被限流 (HTTP 429) / Rate limited (HTTP 429)
+    RateLimited,
+    /// 其他非成功 HTTP 状态 / Other unsuccessful HTTP status
+    Http,
+    /// Aqara 业务错误 / Aqara business error
+    Api,
+    /// 响应解析失败 / Response decoding failure
+    Decode,
+    /// 读写失败 / I/O failure
+    Io,
+    /// 客户端配置或使用方式错误 / Client misconfiguration or misuse
+    Config,
+    /// 参数校验失败 / Parameter validation failure
+    InvalidParams,
+    /// 推送签名校验失败 / Push signature verification failure
+    Unauthorized,
+    /// 客户端已关闭 / Client shut down
+    Shutdown,
+    /// 被 intent 策略拒绝 / Denied by the intent policy
+    PolicyDenied,
+    /// 熔断器已打开 / The circuit breaker is open
+    CircuitOpen,
+}
+
+/// 表示 Aqara 后端故障的业务码 / Business codes that indicate an Aqara backend failure
+///
+/// 100 为服务端超时，5xx 段为服务端内部错误。
+/// 100 is a server-side timeout and the 5xx range covers internal server errors.
+fn is_backend_code(code: i32) -> bool {
+    code == 100 || (500..600).contains(&code)
+}
+
+/// SDK 错误类型 (SDK error type)
+///
+/// 封装 HTTP 传输错误、Aqara 业务错误、响应解析错误以及客户端参数校验错误。
+/// Wraps HTTP transport errors, Aqara business errors, response decoding errors
+/// and client-side parameter validation errors.
+#[derive(Debug)]
+pub enum AqaraError {
+    /// HTTP 请求失败 / HTTP request failed
+    Http(reqwest::Error),
+    /// Aqara 返回非 0 业务码 / Aqara returned a non-zero business code
+    Api {
+        code: i32,
+        message: String,
+        request_id: String,
+    },
+    /// 响应解析失败 / Failed to decode the response
+    ///
+    /// `raw` 为解析失败的原始 `result` (若有)；`snippet` 为脱敏后的响应片段及出错位置
+    /// `raw` is the undecodable `result`, if any; `snippet` is the redacted
+    /// response text with the failing offset
+    Decode {
+        source: serde_json::Error,
+        raw: Option<Value>,
+        snippet: Option<Box<DecodeSnippet>>,
+    },
+    /// 响应缺少必需的 `result` / The response lacks the required `result`
+    MissingResult { intent: String, request_id: String },
+    /// 读写失败 / I/O failure
+    Io(std::io::Error),
+    /// 客户端已关闭 / The client has been shut down
+    Shutdown,
+    /// 当前应用模式不支持该 intent / The intent is not available in the configured app mode
+    WrongAppMode { intent: String, mode: AppMode },
+    /// 令牌表中没有该用户 (或缺少刷新令牌) / The user is missing from the token map (or has no refresh token)
+    UnknownUser(String),
+    /// 客户端配置无效 (列出所有问题) / Invalid client configuration (lists every problem)
+    InvalidConfig(Vec<ConfigProblem>),
+    /// 参数校验失败 (列出所有问题) / Parameter validation failed (lists every problem)
+    InvalidParams(Vec<String>),
+    /// 推送签名校验失败 / Push signature verification failed
+    InvalidSignature(String),
+    /// intent 被客户端策略拒绝 / The intent was denied by the client's policy
+    PolicyDenied(String),
+    /// 熔断器已打开，请求未发出 (附建议等待时间；为 0 表示探测请求进行中)
+    /// The circuit breaker is open and the request was not sent (with the
+    /// suggested wait; zero means a probe is in flight)
+    CircuitOpen(std::time::Duration),
+    /// 合并请求失败，错误与其他调用方共享 / A coalesced request failed; the error is shared with other callers
+    Shared(Arc<AqaraError>),
+    /// 重试后仍失败 (附尝试历史) / Still failing after retries (with the attempt history)
+    RetriesExhausted {
+        last: Box<AqaraError>,
+        history: AttemptHistory,
+    },
+}
+
+impl fmt::Display for AqaraError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AqaraError::Http(e) => write!(f, "http error: {}", e),
+            AqaraError::Api {
+                code,
+                message,
+                request_id,
+            } => write!(
+                f,
+                "aqara api error {}: {} (request id: {})",
+                code, message, request_id
+            ),
+            AqaraError::Decode { source, .. } => write!(f, "decode error: {}", source),
+            AqaraError::MissingResult { intent, request_id } => write!(
+                f,
+                "{} returned no result (request id: {})",
+                intent, request_id
+            ),
+            AqaraError::Io(e) => write!(f, "io error: {}", e),
+            AqaraError::Shutdown => write!(f, "client has been shut down"),
+            AqaraError::WrongAppMode { intent, mode } => match mode {
+                AppMode::User => write!(
+                    f,
+                    "{} is only available to project apps; user-authorized apps should use config.auth.getAuthCode and config.auth.getToken",
+                    intent
+                ),
+                AppMode::Project => write!(
+                    f,
+                    "{} is only available to user-authorized apps; project apps should use config.auth.createAccount",
+                    intent
+                ),
+            },
+            AqaraError::UnknownUser(key) if key.is_empty() => {
+                write!(f, "no user selected; call as_user first")
+            }
+            AqaraError::UnknownUser(key) => write!(f, "no tokens stored for user {}", key),
+            AqaraError::InvalidConfig(problems) => {
+                let problems: Vec<String> = problems.iter().map(ToString::to_string).collect();
+                write!(f, "invalid config: {}", problems.join("; "))
+            }
+            AqaraError::InvalidParams(problems) => {
+                write!(f, "invalid params: {}", problems.join("; "))
+            }
+            AqaraError::InvalidSignature(reason) => write!(f, "invalid push signature: {}", reason),
+            AqaraError::PolicyDenied(intent) => write!(f, "{} is denied by the intent policy", intent),
+            AqaraError::CircuitOpen(retry_after) => {
+                write!(f, "circuit breaker is open; retry in {:?}", retry_after)
+            }
+            AqaraError::Shared(e) => write!(f, "{}", e),
+            AqaraError::RetriesExhausted { last, history } => write!(
+                f,
+                "{} (after {} attempts in {:?})",
+                last,
+                history.attempts.len(),
+                history.elapsed
+            ),
+        }
+    }
+}
+
+impl AqaraError {
+    /// 错误分类 / Error kind
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            AqaraError::Http(e) if e.is_timeout() => ErrorKind::Timeout,
+            AqaraError::Http(e) => match e.status() {
+                Some(status) if status.as_u16() == 429 => ErrorKind::RateLimited,
+                Some(status) if status.is_server_error() => ErrorKind::Server,
+                Some(_) => ErrorKind::Http,
+                None => ErrorKind::Transport,
+            },
+            AqaraError::Api { code, .. } if is_backend_code(*code) => ErrorKind::Server,
+            AqaraError::Api { .. } => ErrorKind::Api,
+            AqaraError::Decode { .. } | AqaraError::MissingResult { .. } => ErrorKind::Decode,
+            AqaraError::Io(_) => ErrorKind::Io,
+            AqaraError::Shutdown => ErrorKind::Shutdown,
+            AqaraError::WrongAppMode { .. }
+            | AqaraError::UnknownUser(_)
+            | AqaraError::InvalidConfig(_) => ErrorKind::Config,
+            AqaraError::InvalidParams(_) => ErrorKind::InvalidParams,
+            AqaraError::InvalidSignature(_) => ErrorKind::Unauthorized,
+            AqaraError::PolicyDenied(_) => ErrorKind::PolicyDenied,
+            AqaraError::CircuitOpen(_) => ErrorKind::CircuitOpen,
+            AqaraError::Shared(e) => e.kind(),
+            AqaraError::RetriesExhausted { last, .. } => last.kind(),
+        }
+    }
+
+    /// 重试历史 (仅在重试后仍失败时存在) / Attempt history (only present when retries did not help)
+    pub fn attempt_history(&self) -> Option<&AttemptHistory> {
+        match self {
+            AqaraError::RetriesExhausted { history, .. } => Some(history),
+            AqaraError::Shared(e) => e.attempt_history(),
+            _ => None,
+        }
+    }
+
+    /// 解析失败时保留的原始 `result` / The raw `result` retained when decoding failed
+    pub fn raw_result(&self) -> Option<&Value> {
+        match self {
+            AqaraError::Decode { raw, .. } => raw.as_ref(),
+            AqaraError::RetriesExhausted { last, .. } => last.raw_result(),
+            AqaraError::Shared(e) => e.raw_result(),
+            _ => None,
+        }
+    }
+}
+
+impl std::error::Error for AqaraError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AqaraError::Http(e) => Some(e),
+            AqaraError::Decode { source, .. } => Some(source),
+            AqaraError::Io(e) => Some(e),
+            AqaraError::RetriesExhausted { last, .. } => Some(last.as_ref()),
+            AqaraError::Shared(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for AqaraError {
+    fn from(e: reqwest::Error) -> Self {
+        AqaraError::Http(e)
+    }
+}
+
+impl From<serde_json::Error> for AqaraError {
+    fn from(e: serde_json::Error) -> Self {
+        AqaraError::Decode {
+            source: e,
+            raw: None,
+            snippet: None,
+        }
+    }
+}
+
+impl From<std::io::Error> for AqaraError {
+    fn from(e: std::io::Error) -> Self {
+        AqaraError::Io(e)
+    }
+}
+
+impl ErrorKind {
+    /// 稳定的诊断代码 / Stable diagnostic code
+    pub fn code(self) -> &'static str {
+        match self {
+            ErrorKind::Transport => "aqara::transport",
+            ErrorKind::Timeout => "aqara::timeout",
+            ErrorKind::Server => "aqara::server",
+            ErrorKind::RateLimited => "aqara::rate_limited",
+            ErrorKind::Http => "aqara::http",
+            ErrorKind::Api => "aqara::api",
+            ErrorKind::Decode => "aqara::decode",
+            ErrorKind::Io => "aqara::io",
+            ErrorKind::Config => "aqara::config",
+            ErrorKind::InvalidParams => "aqara::invalid_params",
+            ErrorKind::Unauthorized => "aqara::unauthorized",
+            ErrorKind::Shutdown => "aqara::shutdown",
+            ErrorKind::PolicyDenied => "aqara::policy_denied",
+            ErrorKind::CircuitOpen => "aqara::circuit_open",
+        }
+    }
+
+    /// 处理建议 / Suggested remedy
+    pub fn help(self) -> &'static str {
+        match self {
+            ErrorKind::Transport => {
+                "check network connectivity and proxy settings to the Aqara endpoint"
+            }
+            ErrorKind::Timeout => {
+                "the request timed out; retry later or raise the timeout for this intent"
+            }
+            ErrorKind::Server => {
+                "Aqara cloud reported a failure; retry later and check the Aqara status page"
+            }
+            ErrorKind::RateLimited => "too many requests; slow down or enable retries with backoff",
+            ErrorKind::Http => "the endpoint rejected the request; check the region and base URL",
+            ErrorKind::Api => "see the Aqara open API error code documentation for this code",
+            ErrorKind::Decode => {
+                "the response did not match the expected shape; inspect raw_result()"
+            }
+            ErrorKind::Io => "check the file or stream being read or written",
+            ErrorKind::Config => "fix the client configuration listed in the message",
+            ErrorKind::InvalidParams => "fix the request parameters listed in the message",
+            ErrorKind::Unauthorized => {
+                "check the app id, key id and app key the push verifier holds"
+            }
+            ErrorKind::Shutdown => "the client was shut down; create a new client",
+            ErrorKind::PolicyDenied => {
+                "the client's allow/deny rules block this intent; adjust them on the builder"
+            }
+            ErrorKind::CircuitOpen => {
+                "recent requests to Aqara kept failing; wait for the circuit to close and retry"
+            }
+        }
+    }
+}
+
+#[cfg(feature = "miette")]
+impl AqaraError {
+    fn decode_snippet(&self) -> Option<(&DecodeSnippet, &serde_json::Error)> {
+        match self {
+            AqaraError::Decode {
+                source,
+                snippet: Some(snippet),
+                ..
+            } => Some((snippet, source)),
+            AqaraError::RetriesExhausted { last, .. } => last.decode_snippet(),
+            AqaraError::Shared(e) => e.decode_snippet(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for AqaraError {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(self.kind().code()))
+    }
+
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        self.decode_snippet()
+            .map(|(snippet, _)| &snippet.text as &dyn miette::SourceCode)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        let (snippet, source) = self.decode_snippet()?;
+        let label = miette::LabeledSpan::at_offset(snippet.offset, source.to_string());
+        Some(Box::new(std::iter::once(label)))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        match self.describe_localized("en") {
+            Some(description) => Some(Box::new(description)),
+            None => Some(Box::new(self.kind().help())),
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::AqaraError;
+
+/// Aqara 业务码 (Aqara business code)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "i32", into = "i32")]
+pub enum ErrorCode {
+    /// 0 成功 / 0 Success
+    Success,
+    /// 100 超时 / 100 Timeout
+    Timeout,
+    /// 101 无效的数据包 / 101 Invalid data package
+    InvalidDataPackage,
+    /// 102 数据已被修改 / 102 Data has been modified
+    DataModified,
+    /// 103 数据可能丢失 / 103 Data may be lost
+    DataMayBeLost,
+    /// 104 服务器繁忙 / 104 Server busy
+    ServerBusy,
+    /// 105 数据包已过期 / 105 Data package has expired
+    DataPackageExpired,
+    /// 106 签名无效 / 106 Invalid signature
+    InvalidSignature,
+    /// 107 非法的 appKey / 107 Illegal appKey
+    IllegalAppKey,
+    /// 108 Token 已过期 / 108 Token has expired
+    TokenExpired,
+    /// 109 缺少 Token / 109 Token is absent
+    TokenAbsent,
+    /// 302 缺少参数 / 302 Missing parameter
+    MissingParameter,
+    /// 303 参数错误 / 303 Parameter error
+    ParameterError,
+    /// 500 服务器错误 / 500 Server error
+    ServerError,
+    /// 701 设备离线 / 701 Device is offline
+    DeviceOffline,
+    /// 未收录的业务码 / Code not in the catalog
+    Other(i32),
+}
+
+impl ErrorCode {
+    /// 从业务码转换 / Convert from a business code
+    pub fn from_code(code: i32) -> Self {
+        match code {
+            0 => ErrorCode::Success,
+            100 => ErrorCode::Timeout,
+            101 => ErrorCode::InvalidDataPackage,
+            102 => ErrorCode::DataModified,
+            103 => ErrorCode::DataMayBeLost,
+            104 => ErrorCode::ServerBusy,
+            105 => ErrorCode::DataPackageExpired,
+            106 => ErrorCode::InvalidSignature,
+            107 => ErrorCode::IllegalAppKey,
+            108 => ErrorCode::TokenExpired,
+            109 => ErrorCode::TokenAbsent,
+            302 => ErrorCode::MissingParameter,
+            303 => ErrorCode::ParameterError,
+            500 => ErrorCode::ServerError,
+            701 => ErrorCode::DeviceOffline,
+            other => ErrorCode::Other(other),
+        }
+    }
+
+    /// 业务码 / Business code
+    pub fn code(self) -> i32 {
+        match self {
+            ErrorCode::Success => 0,
+            ErrorCode::Timeout => 100,
+            ErrorCode::InvalidDataPackage => 101,
+            ErrorCode::DataModified => 102,
+            ErrorCode::DataMayBeLost => 103,
+            ErrorCode::ServerBusy => 104,
+            ErrorCode::DataPackageExpired => 105,
+            ErrorCode::InvalidSignature => 106,
+            ErrorCode::IllegalAppKey => 107,
+            ErrorCode::TokenExpired => 108,
+            ErrorCode::TokenAbsent => 109,
+            ErrorCode::MissingParameter => 302,
+            ErrorCode::ParameterError => 303,
+            ErrorCode::ServerError => 500,
+            ErrorCode::DeviceOffline => 701,
+            ErrorCode::Other(code) => code,
+        }
+    }
+
+    /// Token 已过期 / The token has expired
+    pub fn is_token_expired(self) -> bool {
+        self == ErrorCode::TokenExpired
+    }
+
+    /// Token 缺失或过期 / The token is absent or expired
+    pub fn is_token_problem(self) -> bool {
+        matches!(self, ErrorCode::TokenExpired | ErrorCode::TokenAbsent)
+    }
+
+    /// 参数缺失或错误 / A parameter is missing or invalid
+    pub fn is_param_invalid(self) -> bool {
+        matches!(
+            self,
+            ErrorCode::MissingParameter | ErrorCode::ParameterError
+        )
+    }
+
+    /// 凭据 (签名或 appKey) 无效 / The credentials (signature or appKey) are invalid
+    pub fn is_credentials_invalid(self) -> bool {
+        matches!(self, ErrorCode::InvalidSignature | ErrorCode::IllegalAppKey)
+    }
+
+    /// 设备离线 / The device is offline
+    pub fn is_device_offline(self) -> bool {
+        self == ErrorCode::DeviceOffline
+    }
+
+    /// 可重试的临时故障 (超时、服务器繁忙或 5xx 服务器错误)
+    /// A transient failure worth retrying (timeout, server busy or a 5xx server error)
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            ErrorCode::Timeout | ErrorCode::ServerBusy | ErrorCode::ServerError
+        ) || matches!(self, ErrorCode::Other(code) if (500..600).contains(&code))
+    }
+
+    /// 本地化说明 (Localized description)
+    ///
+    /// `lang` 以 `zh` 开头时返回中文，否则返回英文；未收录的业务码返回 `None`。
+    /// Returns Chinese when `lang` starts with `zh` and English otherwise; codes
+    /// not in the catalog return `None`.
+    pub fn describe(self, lang: &str) -> Option<&'static str> {
+        let (en, zh) = match self {
+            ErrorCode::Success => ("Success", "成功"),
+            ErrorCode::Timeout => ("Timeout", "超时"),
+            ErrorCode::InvalidDataPackage => ("Invalid data package", "无效的数据包"),
+            ErrorCode::DataModified => ("Data has been modified", "数据已被修改"),
+            ErrorCode::DataMayBeLost => ("Data may be lost", "数据可能丢失"),
+            ErrorCode::ServerBusy => ("Server busy", "服务器繁忙"),
+            ErrorCode::DataPackageExpired => ("Data package has expired", "数据包已过期"),
+            ErrorCode::InvalidSignature => ("Invalid signature", "签名无效"),
+            ErrorCode::IllegalAppKey => ("Illegal appKey", "非法的 appKey"),
+            ErrorCode::TokenExpired => ("Token has expired", "Token 已过期"),
+            ErrorCode::TokenAbsent => ("Token is absent", "缺少 Token"),
+            ErrorCode::MissingParameter => ("Missing parameter", "缺少参数"),
+            ErrorCode::ParameterError => ("Parameter error", "参数错误"),
+            ErrorCode::ServerError => ("Server error", "服务器错误"),
+            ErrorCode::DeviceOffline => ("Device is offline", "设备离线"),
+            ErrorCode::Other(_) => return None,
+        };
+        Some(if lang.starts_with("zh") { zh } else { en })
+    }
+}
+
+impl From<i32> for ErrorCode {
+    fn from(code: i32) -> Self {
+        ErrorCode::from_code(code)
+    }
+}
+
+impl From<ErrorCode> for i32 {
+    fn from(code: ErrorCode) -> Self {
+        code.code()
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.describe("en") {
+            Some(description) => write!(f, "{} ({})", self.code(), description),
+            None => write!(f, "{}", self.code()),
+        }
+    }
+}
+
+/// 查询业务码说明 (Describe a business code)
+///
+/// `lang` 以 `zh` 开头时返回中文，否则返回英文；未知业务码返回 `None`。
+/// Returns Chinese when `lang` starts with `zh` and English otherwise; unknown
+/// codes return `None`.
+///
+/// # Parameters 参数
+/// - `code`: 业务码 / Business code
+/// - `lang`: 语言 (同请求头 `Lang`) / Language (as in the `Lang` header)
+pub fn describe_code(code: i32, lang: &str) -> Option<&'static str> {
+    ErrorCode::from_code(code).describe(lang)
+}
+
+impl AqaraError {
+    /// 业务码 (仅 `AqaraError::Api`，含重试后仍失败的)
+    /// Business code (only `AqaraError::Api`, including one that persisted after retries)
+    pub fn code_enum(&self) -> Option<ErrorCode> {
+        match self {
+            AqaraError::Api { code, .. } => Some(ErrorCode::from_code(*code)),
+            AqaraError::RetriesExhausted { last, .. } => last.code_enum(),
+            AqaraError::Shared(e) => e.code_enum(),
+            _ => None,
+        }
+    }
+
+    /// 本地化的业务码说明 (Localized business code description)
+    ///
+    /// 仅对 `AqaraError::Api` (含重试后仍失败的) 返回说明。
+    /// Only `AqaraError::Api` (including one that persisted after retries) has a description.
+    ///
+    /// # Parameters 参数
+    /// - `lang`: 语言 (同请求头 `Lang`) / Language (as in the `Lang` header)
+    pub fn describe_localized(&self, lang: &str) -> Option<&'static str> {
+        self.code_enum()?.describe(lang)
+    }
+}
+
+impl crate::AqaraClient {
+    /// 按客户端语言说明错误 (Describe an error in the client's language)
+    ///
+    /// 使用 `ClientDefaults::lang` (默认 "en")。
+    /// Uses `ClientDefaults::lang` ("en" by default).
+    pub fn describe_error(&self, error: &AqaraError) -> Option<&'static str> {
+        error.describe_localized(self.inner.defaults.lang.as_deref().unwrap_or("en"))
+    }
+}
@@ -0,0 +1,263 @@
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::util::require_id;
+use crate::{AqaraClient, AqaraError};
+
+/// 条件参数 (Condition param)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventParam {
+    /// 参数ID / Param ID
+    pub param_id: String,
+    /// 参数值 / Param value
+    pub value: String,
+    /// 参数类型 (可选) / Param type (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub param_type: Option<String>,
+    /// 参数单位 (可选) / Param unit (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub param_unit: Option<String>,
+}
+
+/// 事件条件 (Event condition)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventCondition {
+    /// 设备ID (与 `model` 至少一个) / Subject (device) ID (this or `model` is required)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject_id: Option<String>,
+    /// 设备型号 (与 `subject_id` 至少一个) / Device model (this or `subject_id` is required)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// 资源ID (可选) / Resource ID (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_id: Option<String>,
+    /// 触发器定义ID (可选) / Trigger definition ID (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trigger_definition_id: Option<String>,
+    /// 生效开始时间 `HH:MM:SS` (可选) / Window start `HH:MM:SS` (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub begin_time: Option<String>,
+    /// 生效结束时间 `HH:MM:SS` (可选) / Window end `HH:MM:SS` (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<String>,
+    /// 条件参数 / Condition params
+    #[serde(default)]
+    pub params: Vec<EventParam>,
+}
+
+/// 事件条件集 (Event condition set)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventConditionSet {
+    /// 名称 / Name
+    pub name: String,
+    /// 位置ID (可选) / Position ID (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position_id: Option<String>,
+    /// 条件关系 (0 且, 1 或) / Relation between conditions (0 and, 1 or)
+    pub relation: i32,
+    /// 条件 / Conditions
+    #[serde(rename = "condition")]
+    pub conditions: Vec<EventCondition>,
+}
+
+impl EventConditionSet {
+    /// 校验条件集 (Validate the condition set)
+    ///
+    /// 一次性收集所有问题，返回 `AqaraError::InvalidParams`。
+    /// Collects every problem and returns them as `AqaraError::InvalidParams`.
+    pub fn validate(&self) -> Result<(), AqaraError> {
+        let mut problems = Vec::new();
+        if self.relation != 0 && self.relation != 1 {
+            problems.push(format!("relation must be 0 or 1, got {}", self.relation));
+        }
+        if self.conditions.is_empty() {
+            problems.push("at least one condition is required".to_string());
+        }
+        for (i, condition) in self.conditions.iter().enumerate() {
+            let blank = |value: &Option<String>| value.as_deref().is_none_or(str::is_empty);
+            if blank(&condition.subject_id) && blank(&condition.model) {
+                problems.push(format!("condition {}: subject_id or model is required", i));
+            }
+            let parse = |field: &str, value: &Option<String>, problems: &mut Vec<String>| {
+                let value = value.as_deref()?;
+                let parsed = NaiveTime::parse_from_str(value, "%H:%M:%S").ok();
+                if parsed.is_none() {
+                    problems.push(format!(
+                        "condition {}: {} {:?} is not HH:MM:SS",
+                        i, field, value
+                    ));
+                }
+                parsed
+            };
+            let begin = parse("begin_time", &condition.begin_time, &mut problems);
+            let end = parse("end_time", &condition.end_time, &mut problems);
+            if condition.begin_time.is_some() != condition.end_time.is_some() {
+                problems.push(format!(
+                    "condition {}: begin_time and end_time must be set together",
+                    i
+                ));
+            } else if begin.is_some() && begin == end {
+                problems.push(format!("condition {}: time window is empty", i));
+            }
+            for (j, param) in condition.params.iter().enumerate() {
+                if param.param_id.is_empty() {
+                    problems.push(format!("condition {} param {}: param_id is empty", i, j));
+                }
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(AqaraError::InvalidParams(problems))
+        }
+    }
+}
+
+impl AqaraClient {
+    /// 创建条件集 (Create an event condition set)
+    ///
+    /// intent: config.event.create
+    ///
+    /// 发送前在客户端校验，参见 `EventConditionSet::validate`。
+    /// Validated client-side before sending; see `EventConditionSet::validate`.
+    ///
+    /// # Parameters 参数
+    /// - `event`: 条件集 / Condition set
+    ///
+    /// # Returns
+    /// 成功返回条件集ID / Returns the event ID on success
+    pub async fn config_event_create(
+        &self,
+        event: &EventConditionSet,
+    ) -> Result<String, AqaraError> {
+        event.validate()?;
+        let data = serde_json::to_value(event)?;
+        let response = self
+            .call_api::<Value>("config.event.create", data, true)
+            .await?;
+        require_id(response, "config.event.create", "eventId")
+    }
+
+    /// 更新条件集 (Update an event condition set)
+    ///
+    /// intent: config.event.update
+    ///
+    /// 发送前在客户端校验，参见 `EventConditionSet::validate`。
+    /// Validated client-side before sending; see `EventConditionSet::validate`.
+    ///
+    /// # Parameters 参数
+    /// - `event_id`: 条件集ID / Event ID
+    /// - `event`: 条件集 / Condition set
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn config_event_update(
+        &self,
+        event_id: &str,
+        event: &EventConditionSet,
+    ) -> Result<String, AqaraError> {
+        event.validate()?;
+        let mut data = serde_json::to_value(event)?;
+        data["eventId"] = json!(event_id);
+        self.send_api_request("config.event.update", data, true)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition(subject_id: &str, window: Option<(&str, &str)>) -> EventCondition {
+        EventCondition {
+            subject_id: Some(subject_id.to_string()),
+            begin_time: window.map(|(begin, _)| begin.to_string()),
+            end_time: window.map(|(_, end)| end.to_string()),
+            ..EventCondition::default()
+        }
+    }
+
+    fn event(relation: i32, conditions: Vec<EventCondition>) -> EventConditionSet {
+        EventConditionSet {
+            name: "night motion".to_string(),
+            position_id: None,
+            relation,
+            conditions,
+        }
+    }
+
+    fn problems(event: &EventConditionSet) -> Vec<String> {
+        match event.validate() {
+            Err(AqaraError::InvalidParams(problems)) => problems,
+            other => panic!("expected InvalidParams, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn accepts_a_valid_set() {
+        let mut by_model = EventCondition {
+            model: Some("lumi.motion.agl04".to_string()),
+            ..EventCondition::default()
+        };
+        by_model.params.push(EventParam {
+            param_id: "PD1".to_string(),
+            value: "1".to_string(),
+            param_type: None,
+            param_unit: None,
+        });
+        let set = event(
+            1,
+            vec![
+                condition("lumi.1", Some(("22:00:00", "06:00:00"))),
+                by_model,
+            ],
+        );
+        assert!(set.validate().is_ok());
+    }
+
+    #[test]
+    fn requires_a_condition() {
+        assert_eq!(
+            problems(&event(0, Vec::new())),
+            ["at least one condition is required"]
+        );
+    }
+
+    #[test]
+    fn reports_every_problem_at_once() {
+        let mut empty_window = condition("lumi.4", Some(("08:00:00", "08:00:00")));
+        empty_window.params.push(EventParam {
+            param_id: String::new(),
+            value: "1".to_string(),
+            param_type: None,
+            param_unit: None,
+        });
+        let set = event(
+            2,
+            vec![
+                EventCondition::default(),
+                condition("lumi.2", Some(("8am", "20:00:00"))),
+                EventCondition {
+                    begin_time: Some("08:00:00".to_string()),
+                    ..condition("lumi.3", None)
+                },
+                empty_window,
+            ],
+        );
+        assert_eq!(
+            problems(&set),
+            [
+                "relation must be 0 or 1, got 2",
+                "condition 0: subject_id or model is required",
+                "condition 1: begin_time \"8am\" is not HH:MM:SS",
+                "condition 2: begin_time and end_time must be set together",
+                "condition 3: time window is empty",
+                "condition 3 param 0: param_id is empty",
+            ]
+        );
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use futures_util::stream::{BoxStream, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::util::value_to_string;
+use crate::{
+    AqaraClient, AqaraError, DeviceInfo, ResourceHistoryPoint, ResourceQuery, ResourceWrite,
+    TimeRange,
+};
+
+/// 温度资源ID (0.01 °C) / Temperature resource ID (0.01 °C)
+pub const TEMPERATURE_RESOURCE_ID: &str = "0.1.85";
+/// 湿度资源ID (0.01 %RH) / Humidity resource ID (0.01 %RH)
+pub const HUMIDITY_RESOURCE_ID: &str = "0.2.85";
+/// 气压资源ID (0.01 hPa) / Pressure resource ID (0.01 hPa)
+pub const PRESSURE_RESOURCE_ID: &str = "0.3.85";
+
+/// 带单位的读数 (Reading with unit)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Reading {
+    /// 换算后的数值 / Scaled value
+    pub value: f64,
+    /// 单位 / Unit
+    pub unit: &'static str,
+}
+
+impl fmt::Display for Reading {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.value, self.unit)
+    }
+}
+
+/// 设备句柄 (Device handle)
+///
+/// 绑定到单个设备ID的轻量视图，克隆开销很小。
+/// A lightweight view bound to one device id; cheap to clone.
+///
+/// 由 `query.device.info` 结果创建的句柄同时携带型号、名称等设备信息。
+/// Handles created from `query.device.info` results also carry the device
+/// info such as model and name.
+#[derive(Clone)]
+pub struct Device {
+    client: AqaraClient,
+    did: String,
+    info: Option<Arc<DeviceInfo>>,
+}
+
+impl AqaraClient {
+    /// 获取设备句柄 / Get a device handle
+    ///
+    /// # Parameters 参数
+    /// - `did`: 设备ID / Device ID
+    pub fn device(&self, did: &str) -> Device {
+        Device {
+            client: self.clone(),
+            did: did.to_string(),
+            info: None,
+        }
+    }
+
+    /// 由设备信息创建句柄 / Create a handle from device info
+    ///
+    /// # Parameters 参数
+    /// - `info`: 设备信息 / Device info
+    pub fn device_from_info(&self, info: DeviceInfo) -> Device {
+        Device {
+            client: self.clone(),
+            did: info.did.clone(),
+            info: Some(Arc::new(info)),
+        }
+    }
+
+    /// 位置下全部设备的句柄 (Handles for every device under a position)
+    ///
+    /// intent: query.device.info
+    ///
+    /// # Parameters 参数
+    /// - `position_id`: 位置ID (可选) / Position ID (optional)
+    ///
+    /// # Returns
+    /// 成功返回携带设备信息的句柄 / Returns handles carrying device info on success
+    pub async fn devices(&self, position_id: Option<&str>) -> Result<Vec<Device>, AqaraError> {
+        self.query_device_info_all(position_id)
+            .map_ok(|info| self.device_from_info(info))
+            .try_collect()
+            .await
+    }
+}
+
+impl Device {
+    /// 设备ID / Device ID
+    pub fn did(&self) -> &str {
+        &self.did
+    }
+
+    /// 设备信息 (通过 `device` 创建的句柄为空) / Device info (absent for handles from `device`)
+    pub fn info(&self) -> Option<&DeviceInfo> {
+        self.info.as_deref()
+    }
+
+    /// 设备型号 / Device model
+    pub fn model(&self) -> Option<&str> {
+        self.info().map(|info| info.model.as_str())
+    }
+
+    /// 加载设备信息 (Load the device info)
+    ///
+    /// intent: query.device.info
+    ///
+    /// # Returns
+    /// 成功返回携带最新设备信息的句柄；设备不存在时返回 `None`
+    /// Returns a handle carrying fresh device info on success; `None` when the device does not exist
+    pub async fn with_info(&self) -> Result<Option<Device>, AqaraError> {
+        let page = self
+            .client
+            .query_device_info_typed(Some(&[self.did.as_str()]), None, None, None)
+            .await?
+            .result
+            .unwrap_or_default();
+        Ok(page
+            .data
+            .into_iter()
+            .find(|info| info.did == self.did)
+            .map(|info| self.client.device_from_info(info)))
+    }
+
+    /// 读取资源值 (Read resource values)
+    ///
+    /// intent: query.resource.value
+    ///
+    /// # Parameters 参数
+    /// - `resource_ids`: 资源ID列表 / Resource IDs
+    ///
+    /// # Returns
+    /// 成功返回 资源ID -> 值 / Returns resource id -> value on success
+    pub async fn read(&self, resource_ids: &[&str]) -> Result<HashMap<String, String>, AqaraError> {
+        let resources = [ResourceQuery {
+            subject_id: self.did.clone(),
+            resource_ids: resource_ids.iter().map(|id| id.to_string()).collect(),
+        }];
+        let result = self
+            .client
+            .call_api::<Value>(
+                "query.resource.value",
+                json!({ "resources": resources }),
+                true,
+            )
+            .await?
+            .result
+            .unwrap_or(Value::Null);
+        Ok(result
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|value| {
+                (
+                    value_to_string(value.get("resourceId")),
+                    value_to_string(value.get("value")),
+                )
+            })
+            .collect())
+    }
+
+    /// 写入资源值 (Write resource values)
+    ///
+    /// intent: write.resource.device
+    ///
+    /// # Parameters 参数
+    /// - `resources`: (资源ID, 值) 列表 / (resource id, value) pairs
+    pub async fn write(&self, resources: &[(&str, &str)]) -> Result<(), AqaraError> {
+        let resources: Vec<ResourceWrite> = resources
+            .iter()
+            .map(|(resource_id, value)| ResourceWrite {
+                resource_id: resource_id.to_string(),
+                value: value.to_string(),
+            })
+            .collect();
+        let data = json!([{
+            "subjectId": self.did,
+            "resources": resources
+        }]);
+        self.client
+            .call_api::<Value>("write.resource.device", data, true)
+            .await?;
+        Ok(())
+    }
+
+    /// 写入单个资源值 (Write one resource value)
+    ///
+    /// intent: write.resource.device
+    ///
+    /// # Parameters 参数
+    /// - `resource_id`: 资源ID / Resource ID
+    /// - `value`: 值 / Value
+    pub async fn write_resource(&self, resource_id: &str, value: &str) -> Result<(), AqaraError> {
+        self.write(&[(resource_id, value)]).await
+    }
+
+    /// 资源历史 (Resource history)
+    ///
+    /// intent: fetch.resource.history
+    ///
+    /// # Parameters 参数
+    /// - `resource_ids`: 资源ID列表 / Resource IDs
+    /// - `range`: 时间范围，如 `TimeRange::last_hours(24)` / Time range, e.g. `TimeRange::last_hours(24)`
+    ///
+    /// # Returns
+    /// 逐条返回记录的流 / A stream yielding each record
+    pub fn history(
+        &self,
+        resource_ids: &[&str],
+        range: impl Into<TimeRange>,
+    ) -> BoxStream<'static, Result<ResourceHistoryPoint, AqaraError>> {
+        let range = range.into();
+        self.client
+            .history_all(&self.did, resource_ids, range.start, range.end)
+    }
+
+    /// 读取温度 (°C) / Read the temperature (°C)
+    ///
+    /// intent: query.resource.value
+    pub async fn temperature(&self) -> Result<Option<Reading>, AqaraError> {
+        self.read_scaled(TEMPERATURE_RESOURCE_ID, "°C").await
+    }
+
+    /// 读取相对湿度 (%RH) / Read the relative humidity (%RH)
+    ///
+    /// intent: query.resource.value
+    pub async fn humidity(&self) -> Result<Option<Reading>, AqaraError> {
+        self.read_scaled(HUMIDITY_RESOURCE_ID, "%RH").await
+    }
+
+    /// 读取气压 (hPa) / Read the pressure (hPa)
+    ///
+    /// intent: query.resource.value
+    pub async fn pressure(&self) -> Result<Option<Reading>, AqaraError> {
+        self.read_scaled(PRESSURE_RESOURCE_ID, "hPa").await
+    }
+
+    // Aqara 以百分之一为单位上报 / Aqara reports hundredths
+    pub(crate) async fn read_scaled(
+        &self,
+        resource_id: &str,
+        unit: &'static str,
+    ) -> Result<Option<Reading>, AqaraError> {
+        let values = self.read(&[resource_id]).await?;
+        Ok(values
+            .get(resource_id)
+            .and_then(|raw| raw.parse::<f64>().ok())
+            .map(|raw| Reading {
+                value: raw / 100.0,
+                unit,
+            }))
+    }
+}
@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::{AqaraClient, AqaraError};
+
+/// 对冲请求配置 (Hedged request config)
+///
+/// 仅对幂等 intent 生效：首次请求超过 `threshold` 仍未返回时再发出一次相同请求，
+/// 采用先成功返回的结果。
+/// Only applies to idempotent intents: when the first request has not answered
+/// within `threshold`, an identical second request is sent and whichever
+/// succeeds first wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HedgingConfig {
+    /// 发出对冲请求前的等待时间 / Wait before sending the hedge
+    pub threshold: Duration,
+}
+
+impl HedgingConfig {
+    /// 创建 (Create)
+    ///
+    /// # Parameters 参数
+    /// - `threshold`: 发出对冲请求前的等待时间，通常取 p95 延迟 / Wait before the hedge, typically the p95 latency
+    pub fn new(threshold: Duration) -> Self {
+        HedgingConfig { threshold }
+    }
+}
+
+impl AqaraClient {
+    // 发送请求，超过阈值后再发出一次对冲请求 / Send a request and hedge it after the threshold
+    pub(crate) async fn send_hedged(
+        &self,
+        intent: &str,
+        data: &Value,
+        include_access_token: bool,
+        hedging: HedgingConfig,
+    ) -> Result<String, AqaraError> {
+        let first = self.send_api_request_once(intent, data, include_access_token);
+        tokio::pin!(first);
+        tokio::select! {
+            result = &mut first => return result,
+            _ = tokio::time::sleep(hedging.threshold) => {}
+        }
+
+        let second = self.send_api_request_once(intent, data, include_access_token);
+        tokio::pin!(second);
+        // 先返回的失败时继续等待另一个 / If the first to answer failed, wait for the other
+        tokio::select! {
+            result = &mut first => match result {
+                Ok(body) => Ok(body),
+                Err(_) => second.await,
+            },
+            result = &mut second => match result {
+                Ok(body) => Ok(body),
+                Err(_) => first.await,
+            },
+        }
+    }
+}
@@ -0,0 +1,741 @@
+use chrono::{DateTime, Duration, Utc};
+use futures_util::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::warn;
+
+use crate::util::value_to_string;
+use crate::{AqaraClient, AqaraError};
+
+/// 单次历史查询的最大时间跨度 / Max time span of a single history query
+pub const HISTORY_MAX_WINDOW: Duration = Duration::days(7);
+
+/// 单页历史记录的最大条数 / Max records per history page
+pub const HISTORY_MAX_PAGE_SIZE: i32 = 300;
+
+/// 游标分页的最大页数 (防止游标异常时无限循环)
+/// Max pages followed by cursor (guards against endless loops on a misbehaving cursor)
+pub const SCAN_MAX_PAGES: usize = 10_000;
+
+/// 资源历史记录点 (Resource history point)
+///
+/// 反序列化时接受 Aqara 返回的 `timeStamp` (毫秒，数字或字符串)。
+/// Deserializes from the `timeStamp` (milliseconds, number or string) Aqara returns.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceHistoryPoint {
+    /// 设备ID / Subject (device) ID
+    pub subject_id: String,
+    /// 资源ID / Resource ID
+    pub resource_id: String,
+    /// 资源值 / Resource value
+    pub value: String,
+    /// 时间 (UTC) / Time (UTC)
+    #[serde(rename = "timeStamp", with = "timestamp_millis")]
+    pub timestamp: DateTime<Utc>,
+}
+
+impl ResourceHistoryPoint {
+    pub(crate) fn from_value(value: &Value) -> Self {
+        let millis = match value.get("timeStamp") {
+            Some(Value::Number(n)) => n.as_i64().unwrap_or_default(),
+            other => value_to_string(other).parse().unwrap_or_default(),
+        };
+        ResourceHistoryPoint {
+            subject_id: value_to_string(value.get("subjectId")),
+            resource_id: value_to_string(value.get("resourceId")),
+            value: value_to_string(value.get("value")),
+            timestamp: DateTime::from_timestamp_millis(millis).unwrap_or_default(),
+        }
+    }
+}
+
+// 毫秒时间戳 (数字或字符串) 与 `DateTime<Utc>` 互转
+// Convert between millisecond timestamps (number or string) and `DateTime<Utc>`
+mod timestamp_millis {
+    use chrono::{DateTime, Utc};
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde_json::Value;
+
+    pub(super) fn serialize<S: Serializer>(
+        time: &DateTime<Utc>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(time.timestamp_millis())
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DateTime<Utc>, D::Error> {
+        let millis = match Value::deserialize(deserializer)? {
+            Value::Number(n) => n.as_i64(),
+            Value::String(s) => s.parse().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| D::Error::custom("timeStamp is not a millisecond timestamp"))?;
+        DateTime::from_timestamp_millis(millis)
+            .ok_or_else(|| D::Error::custom("timeStamp is out of range"))
+    }
+}
+
+/// 资源历史记录分页 (Resource history page)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceHistoryPage {
+    /// 当前页记录 / Records on this page
+    pub points: Vec<ResourceHistoryPoint>,
+    /// 下一页游标 (没有更多时为空) / Cursor for the next page (empty when there are no more)
+    pub scan_id: String,
+}
+
+impl ResourceHistoryPage {
+    pub(crate) fn from_value(value: &Value) -> Self {
+        ResourceHistoryPage {
+            points: value
+                .get("data")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .map(ResourceHistoryPoint::from_value)
+                .collect(),
+            scan_id: value_to_string(value.get("scanId")),
+        }
+    }
+}
+
+impl AqaraClient {
+    /// 查询资源历史 (Fetch resource history)
+    ///
+    /// intent: fetch.resource.history
+    ///
+    /// # Parameters 参数
+    /// - `subject_id`: 设备ID / Subject (device) ID
+    /// - `resource_ids`: 资源ID列表 / Resource IDs
+    /// - `start_time`: 开始时间 (毫秒) / Start time (milliseconds)
+    /// - `end_time`: 结束时间 (毫秒，可选) / End time (milliseconds, optional)
+    /// - `size`: 每页数量 (可选，最大 300) / Page size (optional, max 300)
+    /// - `scan_id`: 上一页返回的游标 (可选) / Cursor returned by the previous page (optional)
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn fetch_resource_history(
+        &self,
+        subject_id: &str,
+        resource_ids: &[&str],
+        start_time: i64,
+        end_time: Option<i64>,
+        size: Option<i32>,
+        scan_id: Option<&str>,
+    ) -> Result<String, AqaraError> {
+        let data = history_data(
+            subject_id,
+            resource_ids,
+            start_time,
+            end_time,
+            size,
+            scan_id,
+        );
+        self.send_api_request("fetch.resource.history", data, true)
+            .await
+    }
+
+    /// 查询资源历史 (类型化) (Fetch resource history, typed)
+    ///
+    /// intent: fetch.resource.history
+    ///
+    /// # Parameters 参数
+    /// - `subject_id`: 设备ID / Subject (device) ID
+    /// - `resource_ids`: 资源ID列表 / Resource IDs
+    /// - `start`: 开始时间 / Start time
+    /// - `end`: 结束时间 (可选) / End time (optional)
+    /// - `size`: 每页数量 (可选，最大 300) / Page size (optional, max 300)
+    /// - `scan_id`: 上一页返回的游标 (可选) / Cursor returned by the previous page (optional)
+    ///
+    /// # Returns
+    /// 成功返回一页历史记录 / Returns one page of history on success
+    pub async fn history_typed(
+        &self,
+        subject_id: &str,
+        resource_ids: &[&str],
+        start: DateTime<Utc>,
+        end: Option<DateTime<Utc>>,
+        size: Option<i32>,
+        scan_id: Option<&str>,
+    ) -> Result<ResourceHistoryPage, AqaraError> {
+        let data = history_data(
+            subject_id,
+            resource_ids,
+            start.timestamp_millis(),
+            end.map(|end| end.timestamp_millis()),
+            size,
+            scan_id,
+        );
+        let result = self
+            .call_api::<Value>("fetch.resource.history", data, true)
+            .await?
+            .result
+            .unwrap_or(Value::Null);
+        Ok(ResourceHistoryPage::from_value(&result))
+    }
+
+    /// 查询任意时间范围的资源历史 (Fetch resource history over an arbitrary range)
+    ///
+    /// intent: fetch.resource.history
+    ///
+    /// 将时间范围拆分为不超过 `HISTORY_MAX_WINDOW` 的子窗口，每个窗口按游标分页拉取，
+    /// 最多 `concurrency` 个窗口同时进行，结果按时间升序合并。
+    /// Splits the range into sub-windows no longer than `HISTORY_MAX_WINDOW`, pages
+    /// through each one by cursor with at most `concurrency` windows in flight, and
+    /// merges the results in ascending time order.
+    ///
+    /// # Parameters 参数
+    /// - `subject_id`: 设备ID / Subject (device) ID
+    /// - `resource_ids`: 资源ID列表 / Resource IDs
+    /// - `start`: 开始时间 / Start time
+    /// - `end`: 结束时间 / End time
+    /// - `concurrency`: 并发窗口数 (0 视为 1) / Windows fetched concurrently (0 is treated as 1)
+    ///
+    /// # Returns
+    /// 成功返回按时间升序排列的记录 / Returns the records in ascending time order
+    pub async fn fetch_history_range(
+        &self,
+        subject_id: &str,
+        resource_ids: &[&str],
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        concurrency: usize,
+    ) -> Result<Vec<ResourceHistoryPoint>, AqaraError> {
+        let windows: Vec<Vec<ResourceHistoryPoint>> = stream::iter(split_windows(start, end))
+            .map(|(start, end)| self.fetch_history_window(subject_id, resource_ids, start, end))
+            .buffered(concurrency.max(1))
+            .try_collect()
+            .await?;
+        let mut points: Vec<ResourceHistoryPoint> = windows.into_iter().flatten().collect();
+        // 相邻窗口共享边界，去掉边界上的重复记录
+        // Adjacent windows share a boundary, so drop records duplicated on it
+        points.sort_by(|a, b| {
+            (a.timestamp, &a.resource_id, &a.value).cmp(&(b.timestamp, &b.resource_id, &b.value))
+        });
+        points.dedup();
+        Ok(points)
+    }
+
+    /// 按游标拉取资源历史 (Stream resource history by cursor)
+    ///
+    /// intent: fetch.resource.history
+    ///
+    /// 按 `HISTORY_MAX_WINDOW` 拆分时间范围，依次自动跟随 `scanId` 拉取每个窗口，
+    /// 逐条返回记录；单个窗口最多跟随 `SCAN_MAX_PAGES` 页。
+    /// Splits the range by `HISTORY_MAX_WINDOW` and follows `scanId` through each
+    /// window in turn, yielding records one by one; at most `SCAN_MAX_PAGES` pages
+    /// are followed per window.
+    ///
+    /// # Parameters 参数
+    /// - `subject_id`: 设备ID / Subject (device) ID
+    /// - `resource_ids`: 资源ID列表 / Resource IDs
+    /// - `start`: 开始时间 / Start time
+    /// - `end`: 结束时间 / End time
+    ///
+    /// # Returns
+    /// 逐条返回记录的流 / A stream yielding each record
+    pub fn history_all(
+        &self,
+        subject_id: &str,
+        resource_ids: &[&str],
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> BoxStream<'static, Result<ResourceHistoryPoint, AqaraError>> {
+        let client = self.clone();
+        let subject_id = subject_id.to_string();
+        let resource_ids: Vec<String> = resource_ids.iter().map(|id| id.to_string()).collect();
+        stream::iter(split_windows(start, end))
+            .flat_map(move |(start, end)| {
+                let resource_ids: Vec<&str> = resource_ids.iter().map(String::as_str).collect();
+                let data = history_data(
+                    &subject_id,
+                    &resource_ids,
+                    start.timestamp_millis(),
+                    Some(end.timestamp_millis()),
+                    Some(HISTORY_MAX_PAGE_SIZE),
+                    None,
+                );
+                client.scan(
+                    "fetch.resource.history",
+                    data,
+                    ResourceHistoryPoint::from_value,
+                )
+            })
+            .boxed()
+    }
+
+    async fn fetch_history_window(
+        &self,
+        subject_id: &str,
+        resource_ids: &[&str],
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<ResourceHistoryPoint>, AqaraError> {
+        let data = history_data(
+            subject_id,
+            resource_ids,
+            start.timestamp_millis(),
+            Some(end.timestamp_millis()),
+            Some(HISTORY_MAX_PAGE_SIZE),
+            None,
+        );
+        self.scan(
+            "fetch.resource.history",
+            data,
+            ResourceHistoryPoint::from_value,
+        )
+        .try_collect()
+        .await
+    }
+
+    // 自动跟随 `scanId` 拉取分页并逐项返回 / Follow `scanId` across pages and yield each item
+    fn scan<T: Send + 'static>(
+        &self,
+        intent: &str,
+        data: Value,
+        parse: fn(&Value) -> T,
+    ) -> BoxStream<'static, Result<T, AqaraError>> {
+        let client = self.clone();
+        let intent = intent.to_string();
+        // (游标, 已拉取页数, 是否结束) / (cursor, pages fetched, finished)
+        stream::try_unfold((None::<String>, 0, false), move |(scan_id, pages, done)| {
+            let client = client.clone();
+            let intent = intent.clone();
+            let mut data = data.clone();
+            async move {
+                if done {
+                    return Ok(None);
+                }
+                if pages >= SCAN_MAX_PAGES {
+                    warn!("{} stopped after {} pages", intent, SCAN_MAX_PAGES);
+                    return Ok(None);
+                }
+                if let Some(scan_id) = &scan_id {
+                    data["scanId"] = json!(scan_id);
+                }
+                let result = client
+                    .call_api::<Value>(&intent, data, true)
+                    .await?
+                    .result
+                    .unwrap_or(Value::Null);
+                let page = result.get("data").and_then(Value::as_array);
+                let items: Vec<T> = page.into_iter().flatten().map(parse).collect();
+                let next = value_to_string(result.get("scanId"));
+                // 没有游标或游标未变化时结束 / Stop when there is no cursor or it did not advance
+                let done = next.is_empty()
+                    || page.is_none_or(|page| page.is_empty())
+                    || Some(&next) == scan_id.as_ref();
+                Ok::<_, AqaraError>(Some((items, (Some(next), pages + 1, done))))
+            }
+        })
+        .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+    }
+}
+
+/// 将时间范围拆分为不超过 `HISTORY_MAX_WINDOW` 的子窗口
+/// Split a time range into sub-windows no longer than `HISTORY_MAX_WINDOW`
+pub(crate) fn split_windows(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    let mut windows = Vec::new();
+    let mut window_start = start;
+    while window_start < end {
+        let window_end = (window_start + HISTORY_MAX_WINDOW).min(end);
+        windows.push((window_start, window_end));
+        window_start = window_end;
+    }
+    windows
+}
+
+fn history_data(
+    subject_id: &str,
+    resource_ids: &[&str],
+    start_time: i64,
+    end_time: Option<i64>,
+    size: Option<i32>,
+    scan_id: Option<&str>,
+) -> Value {
+    let mut data = json!({
+        "subjectId": subject_id,
+        "resourceIds": resource_ids,
+        "startTime": start_time.to_string()
+    });
+    if let Some(end_time) = end_time {
+        data["endTime"] = json!(end_time.to_string());
+    }
+    if let Some(size) = size {
+        data["size"] = json!(size);
+    }
+    if let Some(scan_id) = scan_id {
+        data["scanId"] = json!(scan_id);
+    }
+    data
+}
+
+/// 统计维度 (Statistics dimension)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatisticsDimension {
+    #[serde(rename = "30m")]
+    ThirtyMinutes,
+    #[serde(rename = "1h")]
+    OneHour,
+    #[serde(rename = "2h")]
+    TwoHours,
+    #[serde(rename = "3h")]
+    ThreeHours,
+    #[serde(rename = "4h")]
+    FourHours,
+    #[serde(rename = "1d")]
+    OneDay,
+    #[serde(rename = "7d")]
+    SevenDays,
+    #[serde(rename = "30d")]
+    ThirtyDays,
+}
+
+impl StatisticsDimension {
+    /// 从细到粗的全部维度 / Every dimension, finest first
+    pub const ALL: [StatisticsDimension; 8] = [
+        StatisticsDimension::ThirtyMinutes,
+        StatisticsDimension::OneHour,
+        StatisticsDimension::TwoHours,
+        StatisticsDimension::ThreeHours,
+        StatisticsDimension::FourHours,
+        StatisticsDimension::OneDay,
+        StatisticsDimension::SevenDays,
+        StatisticsDimension::ThirtyDays,
+    ];
+
+    /// 接口中的取值 / Value used by the API
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StatisticsDimension::ThirtyMinutes => "30m",
+            StatisticsDimension::OneHour => "1h",
+            StatisticsDimension::TwoHours => "2h",
+            StatisticsDimension::ThreeHours => "3h",
+            StatisticsDimension::FourHours => "4h",
+            StatisticsDimension::OneDay => "1d",
+            StatisticsDimension::SevenDays => "7d",
+            StatisticsDimension::ThirtyDays => "30d",
+        }
+    }
+
+    /// 每个统计点覆盖的时长 / Time span covered by one point
+    pub fn span(self) -> Duration {
+        match self {
+            StatisticsDimension::ThirtyMinutes => Duration::minutes(30),
+            StatisticsDimension::OneHour => Duration::hours(1),
+            StatisticsDimension::TwoHours => Duration::hours(2),
+            StatisticsDimension::ThreeHours => Duration::hours(3),
+            StatisticsDimension::FourHours => Duration::hours(4),
+            StatisticsDimension::OneDay => Duration::days(1),
+            StatisticsDimension::SevenDays => Duration::days(7),
+            StatisticsDimension::ThirtyDays => Duration::days(30),
+        }
+    }
+
+    /// 选择维度 (Pick a dimension)
+    ///
+    /// 返回点数不超过 `max_points` 的最细维度；范围过大时返回最粗维度。
+    /// Returns the finest dimension yielding at most `max_points` points, or the
+    /// coarsest one when the range is too long for any of them.
+    pub fn for_range(start: DateTime<Utc>, end: DateTime<Utc>, max_points: usize) -> Self {
+        let range = (end - start).max(Duration::zero());
+        let max_points = max_points.max(1) as i64;
+        Self::ALL
+            .into_iter()
+            .find(|dimension| {
+                let span = dimension.span().num_milliseconds();
+                (range.num_milliseconds() + span - 1) / span <= max_points
+            })
+            .unwrap_or(StatisticsDimension::ThirtyDays)
+    }
+}
+
+impl std::str::FromStr for StatisticsDimension {
+    type Err = AqaraError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|dimension| dimension.as_str() == s)
+            .ok_or_else(|| AqaraError::InvalidParams(vec![format!("unknown dimension {:?}", s)]))
+    }
+}
+
+/// 聚合类型 (Aggregation type)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "i32", into = "i32")]
+pub enum AggrType {
+    /// 区间差值 (0)，如累计用电量 / Difference over the interval (0), e.g. cumulative energy
+    Difference,
+    /// 最小值 (1) / Minimum (1)
+    Min,
+    /// 最大值 (2) / Maximum (2)
+    Max,
+    /// 平均值 (3) / Average (3)
+    Average,
+    /// 未收录的聚合类型 / Aggregation type not in the catalog
+    Other(i32),
+}
+
+impl AggrType {
+    /// 接口中的取值 / Value used by the API
+    pub fn code(self) -> i32 {
+        match self {
+            AggrType::Difference => 0,
+            AggrType::Min => 1,
+            AggrType::Max => 2,
+            AggrType::Average => 3,
+            AggrType::Other(code) => code,
+        }
+    }
+}
+
+impl From<i32> for AggrType {
+    fn from(code: i32) -> Self {
+        match code {
+            0 => AggrType::Difference,
+            1 => AggrType::Min,
+            2 => AggrType::Max,
+            3 => AggrType::Average,
+            other => AggrType::Other(other),
+        }
+    }
+}
+
+impl From<AggrType> for i32 {
+    fn from(aggr_type: AggrType) -> Self {
+        aggr_type.code()
+    }
+}
+
+/// 统计点 (Statistics point)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatisticsPoint {
+    /// 设备ID / Subject (device) ID
+    pub subject_id: String,
+    /// 资源ID / Resource ID
+    pub resource_id: String,
+    /// 聚合类型 / Aggregation type
+    pub aggr_type: AggrType,
+    /// 统计值 / Aggregated value
+    pub value: String,
+    /// 时间戳 (毫秒) / Timestamp (milliseconds)
+    pub time_stamp: i64,
+}
+
+impl StatisticsPoint {
+    pub(crate) fn from_value(value: &Value) -> Self {
+        let time_stamp = match value.get("timeStamp") {
+            Some(Value::Number(n)) => n.as_i64().unwrap_or_default(),
+            other => value_to_string(other).parse().unwrap_or_default(),
+        };
+        StatisticsPoint {
+            subject_id: value_to_string(value.get("subjectId")),
+            resource_id: value_to_string(value.get("resourceId")),
+            aggr_type: value_to_string(value.get("aggrType"))
+                .parse::<i32>()
+                .unwrap_or_default()
+                .into(),
+            value: value_to_string(value.get("value")),
+            time_stamp,
+        }
+    }
+}
+
+/// 统计查询参数 (Statistics query params)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatisticsParams {
+    /// 设备ID / Subject (device) ID
+    pub subject_id: String,
+    /// 资源ID列表 / Resource IDs
+    pub resource_ids: Vec<String>,
+    /// 聚合类型 / Aggregation types
+    pub aggr_types: Vec<AggrType>,
+    /// 开始时间 / Start time
+    pub start_time: DateTime<Utc>,
+    /// 结束时间 / End time
+    pub end_time: DateTime<Utc>,
+    /// 统计维度 (`statistics_auto` 会自动选择) / Dimension (picked automatically by `statistics_auto`)
+    pub dimension: StatisticsDimension,
+    /// 每页数量 (可选) / Page size (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<i32>,
+}
+
+impl StatisticsParams {
+    pub(crate) fn to_request_data(&self) -> Value {
+        let mut data = json!({
+            "resources": {
+                "subjectId": self.subject_id,
+                "resourceIds": self.resource_ids,
+                "aggrTypes": self.aggr_types
+            },
+            "startTime": self.start_time.timestamp_millis().to_string(),
+            "endTime": self.end_time.timestamp_millis().to_string(),
+            "dimension": self.dimension.as_str()
+        });
+        if let Some(size) = self.size {
+            data["size"] = json!(size);
+        }
+        data
+    }
+}
+
+impl AqaraClient {
+    /// 查询资源统计 (Fetch resource statistics)
+    ///
+    /// intent: fetch.resource.statistics
+    ///
+    /// # Parameters 参数
+    /// - `params`: 统计查询参数 / Statistics query params
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn fetch_resource_statistics(
+        &self,
+        params: &StatisticsParams,
+    ) -> Result<String, AqaraError> {
+        let data = params.to_request_data();
+        self.send_api_request("fetch.resource.statistics", data, true)
+            .await
+    }
+
+    /// 自动选择维度查询资源统计 (Fetch resource statistics with an automatic dimension)
+    ///
+    /// intent: fetch.resource.statistics
+    ///
+    /// 忽略 `params.dimension`，按时间范围选择点数不超过 `max_points` 的最细维度，
+    /// 例如一天 48 点对应 30m，一年 400 点对应 1d。
+    /// Ignores `params.dimension` and picks the finest dimension yielding at most
+    /// `max_points` points for the range, e.g. 30m for a day at 48 points or 1d for
+    /// a year at 400 points.
+    ///
+    /// # Parameters 参数
+    /// - `params`: 统计查询参数 / Statistics query params
+    /// - `max_points`: 期望的最大点数 / Desired maximum point count
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn statistics_auto(
+        &self,
+        params: &StatisticsParams,
+        max_points: usize,
+    ) -> Result<String, AqaraError> {
+        let params = StatisticsParams {
+            dimension: StatisticsDimension::for_range(
+                params.start_time,
+                params.end_time,
+                max_points,
+            ),
+            ..params.clone()
+        };
+        self.fetch_resource_statistics(&params).await
+    }
+
+    /// 按游标拉取资源统计 (Stream resource statistics by cursor)
+    ///
+    /// intent: fetch.resource.statistics
+    ///
+    /// 自动跟随 `scanId` 拉取全部分页，逐条返回统计点；最多跟随 `SCAN_MAX_PAGES` 页。
+    /// Follows `scanId` through every page and yields each point; at most
+    /// `SCAN_MAX_PAGES` pages are followed.
+    ///
+    /// # Parameters 参数
+    /// - `params`: 统计查询参数 / Statistics query params
+    ///
+    /// # Returns
+    /// 逐条返回统计点的流 / A stream yielding each point
+    pub fn statistics_all(
+        &self,
+        params: &StatisticsParams,
+    ) -> BoxStream<'static, Result<StatisticsPoint, AqaraError>> {
+        self.scan(
+            "fetch.resource.statistics",
+            params.to_request_data(),
+            StatisticsPoint::from_value,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(days: i64, hours: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(0, 0).unwrap() + Duration::days(days) + Duration::hours(hours)
+    }
+
+    #[test]
+    fn split_windows_covers_the_range_contiguously() {
+        let windows = split_windows(at(0, 0), at(15, 12));
+        assert_eq!(
+            windows,
+            [
+                (at(0, 0), at(7, 0)),
+                (at(7, 0), at(14, 0)),
+                (at(14, 0), at(15, 12)),
+            ]
+        );
+    }
+
+    #[test]
+    fn split_windows_keeps_short_ranges_whole() {
+        assert_eq!(split_windows(at(0, 0), at(0, 3)), [(at(0, 0), at(0, 3))]);
+        assert_eq!(split_windows(at(0, 0), at(7, 0)), [(at(0, 0), at(7, 0))]);
+    }
+
+    #[test]
+    fn split_windows_is_empty_for_empty_or_reversed_ranges() {
+        assert!(split_windows(at(1, 0), at(1, 0)).is_empty());
+        assert!(split_windows(at(2, 0), at(1, 0)).is_empty());
+    }
+
+    #[test]
+    fn for_range_picks_the_finest_dimension_within_budget() {
+        // 一天 48 个半小时点 / One day is 48 half-hour points
+        assert_eq!(
+            StatisticsDimension::for_range(at(0, 0), at(1, 0), 48),
+            StatisticsDimension::ThirtyMinutes
+        );
+        assert_eq!(
+            StatisticsDimension::for_range(at(0, 0), at(1, 0), 47),
+            StatisticsDimension::OneHour
+        );
+        assert_eq!(
+            StatisticsDimension::for_range(at(0, 0), at(365, 0), 400),
+            StatisticsDimension::OneDay
+        );
+        assert_eq!(
+            StatisticsDimension::for_range(at(0, 0), at(365, 0), 100),
+            StatisticsDimension::SevenDays
+        );
+    }
+
+    #[test]
+    fn for_range_falls_back_to_the_coarsest_dimension() {
+        assert_eq!(
+            StatisticsDimension::for_range(at(0, 0), at(3650, 0), 10),
+            StatisticsDimension::ThirtyDays
+        );
+    }
+
+    #[test]
+    fn for_range_handles_degenerate_input() {
+        assert_eq!(
+            StatisticsDimension::for_range(at(1, 0), at(0, 0), 0),
+            StatisticsDimension::ThirtyMinutes
+        );
+    }
+}
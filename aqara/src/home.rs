@@ -0,0 +1,246 @@
+//! 家庭视图 (Home facade)
+//!
+//! 按需加载并缓存位置、设备、场景和自动化，提供按名称导航的高层接口。
+//! Lazily loads and caches positions, devices, scenes and linkages, and offers
+//! name-based navigation on top of them.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+
+use crate::util::value_to_string;
+use crate::{AqaraClient, AqaraError, Device};
+
+/// 房间 (位置) (Room / position)
+#[derive(Clone)]
+pub struct Room {
+    /// 位置ID / Position ID
+    pub position_id: String,
+    /// 名称 / Name
+    pub name: String,
+    /// 父位置ID (顶层为空) / Parent position ID (empty at the top level)
+    pub parent_id: String,
+    devices: Vec<Device>,
+}
+
+impl Room {
+    /// 房间内的设备 / Devices in the room
+    pub fn devices(&self) -> &[Device] {
+        &self.devices
+    }
+}
+
+/// 场景 (Scene)
+#[derive(Clone)]
+pub struct Scene {
+    client: AqaraClient,
+    /// 场景ID / Scene ID
+    pub scene_id: String,
+    /// 名称 / Name
+    pub name: String,
+}
+
+impl Scene {
+    /// 执行场景 (Run the scene)
+    ///
+    /// intent: config.scene.run
+    pub async fn run(&self) -> Result<(), AqaraError> {
+        self.client
+            .call_api::<Value>(
+                "config.scene.run",
+                json!({ "sceneId": self.scene_id }),
+                true,
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+/// 自动化 (Linkage)
+#[derive(Clone)]
+pub struct Linkage {
+    client: AqaraClient,
+    /// 自动化ID / Linkage ID
+    pub linkage_id: String,
+    /// 名称 / Name
+    pub name: String,
+}
+
+impl Linkage {
+    /// 启用或停用 (Enable or disable)
+    ///
+    /// intent: config.linkage.enable
+    pub async fn set_enabled(&self, enable: bool) -> Result<(), AqaraError> {
+        self.client
+            .config_linkage_enable(&self.linkage_id, enable)
+            .await?;
+        Ok(())
+    }
+}
+
+struct HomeIndex {
+    rooms: Vec<Room>,
+    scenes: Vec<Scene>,
+    linkages: Vec<Linkage>,
+}
+
+/// 家庭视图 (Home facade)
+///
+/// 首次访问时加载整棵位置树及其设备、场景和自动化，之后复用缓存，直到调用 `refresh`。
+/// Loads the whole position tree with its devices, scenes and linkages on first
+/// access and reuses that cache until `refresh` is called.
+pub struct Home {
+    client: AqaraClient,
+    root: Option<String>,
+    index: Mutex<Option<Arc<HomeIndex>>>,
+}
+
+impl AqaraClient {
+    /// 家庭视图 (Home facade)
+    ///
+    /// 房间为根位置下的全部子孙位置 (不含根位置本身)。
+    /// Rooms are every position below the root (not the root itself).
+    ///
+    /// # Parameters 参数
+    /// - `root`: 根位置ID (可选，默认顶层) / Root position ID (optional, top level by default)
+    pub fn home(&self, root: Option<&str>) -> Home {
+        Home {
+            client: self.clone(),
+            root: root.map(str::to_string),
+            index: Mutex::new(None),
+        }
+    }
+}
+
+impl Home {
+    /// 全部房间 (Every room)
+    ///
+    /// intent: query.position.info, query.device.info, query.scene.listByPositionId,
+    /// query.linkage.listByPositionId
+    pub async fn rooms(&self) -> Result<Vec<Room>, AqaraError> {
+        Ok(self.index().await?.rooms.clone())
+    }
+
+    /// 按名称查找房间 / Find a room by name
+    pub async fn room(&self, name: &str) -> Result<Option<Room>, AqaraError> {
+        let index = self.index().await?;
+        Ok(index.rooms.iter().find(|room| room.name == name).cloned())
+    }
+
+    /// 全部场景 / Every scene
+    pub async fn scenes(&self) -> Result<Vec<Scene>, AqaraError> {
+        Ok(self.index().await?.scenes.clone())
+    }
+
+    /// 按名称查找场景 / Find a scene by name
+    pub async fn scene_by_name(&self, name: &str) -> Result<Option<Scene>, AqaraError> {
+        let index = self.index().await?;
+        Ok(index
+            .scenes
+            .iter()
+            .find(|scene| scene.name == name)
+            .cloned())
+    }
+
+    /// 全部自动化 / Every linkage
+    pub async fn linkages(&self) -> Result<Vec<Linkage>, AqaraError> {
+        Ok(self.index().await?.linkages.clone())
+    }
+
+    /// 按名称查找自动化 / Find a linkage by name
+    pub async fn linkage_by_name(&self, name: &str) -> Result<Option<Linkage>, AqaraError> {
+        let index = self.index().await?;
+        Ok(index
+            .linkages
+            .iter()
+            .find(|linkage| linkage.name == name)
+            .cloned())
+    }
+
+    /// 清空缓存，下次访问时重新加载 / Drop the cache so the next access reloads
+    pub async fn refresh(&self) {
+        *self.index.lock().await = None;
+    }
+
+    async fn index(&self) -> Result<Arc<HomeIndex>, AqaraError> {
+        let mut guard = self.index.lock().await;
+        if let Some(index) = guard.as_ref() {
+            return Ok(index.clone());
+        }
+        let index = Arc::new(self.load().await?);
+        *guard = Some(index.clone());
+        Ok(index)
+    }
+
+    async fn load(&self) -> Result<HomeIndex, AqaraError> {
+        let client = &self.client;
+        let mut positions = Vec::new();
+        let mut pending = vec![self.root.clone().unwrap_or_default()];
+        while let Some(parent) = pending.pop() {
+            let children = client
+                .fetch_all_pages("query.position.info", json!({ "parentPositionId": parent }))
+                .await?;
+            for child in children {
+                pending.push(value_to_string(child.get("positionId")));
+                positions.push(child);
+            }
+        }
+
+        let mut devices: HashMap<String, Vec<Device>> = HashMap::new();
+        let mut scenes = Vec::new();
+        let mut linkages = Vec::new();
+        for position in &positions {
+            let position_id = value_to_string(position.get("positionId"));
+            let data = json!({ "positionId": position_id });
+            for device in client
+                .fetch_all_pages("query.device.info", data.clone())
+                .await?
+            {
+                devices
+                    .entry(position_id.clone())
+                    .or_default()
+                    .push(client.device(&value_to_string(device.get("did"))));
+            }
+            for scene in client
+                .fetch_all_pages("query.scene.listByPositionId", data.clone())
+                .await?
+            {
+                scenes.push(Scene {
+                    client: client.clone(),
+                    scene_id: value_to_string(scene.get("sceneId")),
+                    name: value_to_string(scene.get("name")),
+                });
+            }
+            for linkage in client
+                .fetch_all_pages("query.linkage.listByPositionId", data)
+                .await?
+            {
+                linkages.push(Linkage {
+                    client: client.clone(),
+                    linkage_id: value_to_string(linkage.get("linkageId")),
+                    name: value_to_string(linkage.get("name")),
+                });
+            }
+        }
+
+        let rooms = positions
+            .iter()
+            .map(|position| {
+                let position_id = value_to_string(position.get("positionId"));
+                Room {
+                    devices: devices.remove(&position_id).unwrap_or_default(),
+                    name: value_to_string(position.get("positionName")),
+                    parent_id: value_to_string(position.get("parentPositionId")),
+                    position_id,
+                }
+            })
+            .collect();
+        Ok(HomeIndex {
+            rooms,
+            scenes,
+            linkages,
+        })
+    }
+}
@@ -0,0 +1,348 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::util::{require_id, value_to_string};
+use crate::{AqaraClient, AqaraError};
+
+/// 自定义遥控器按键信息 (Custom IR controller key info)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IrCodeInfo {
+    /// 按键ID / Key ID
+    pub key_id: String,
+    /// 按键名称 / Key name
+    pub name: String,
+    /// 红外码 (base64) / IR code (base64)
+    pub ir_code: String,
+    /// 载波频率 (可选) / Carrier frequency (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub freq: Option<i32>,
+}
+
+/// 自定义遥控器参数 (Custom IR controller params)
+#[derive(Debug, Clone)]
+pub struct IrCustomParams {
+    /// 红外网关ID / IR gateway DID
+    pub parent_did: String,
+    /// 位置ID (可选) / Position ID (optional)
+    pub position_id: Option<String>,
+    /// 品类ID / Category ID
+    pub category_id: i32,
+    /// 品牌ID / Brand ID
+    pub brand_id: i32,
+    /// 遥控器名称 / Controller name
+    pub name: String,
+}
+
+/// 红外按键导出文档 (IR key set export document)
+///
+/// 用于备份已学习的遥控器并导入到其他网关。
+/// Used to back up a learned remote and import it onto another gateway.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IrKeySetDocument {
+    /// 文档版本 / Document version
+    pub version: u32,
+    /// 遥控器名称 / Controller name
+    pub name: String,
+    /// 品类ID / Category ID
+    pub category_id: i32,
+    /// 品牌ID / Brand ID
+    pub brand_id: i32,
+    /// 按键列表 / Key list
+    pub keys: Vec<IrCodeInfo>,
+}
+
+impl IrKeySetDocument {
+    /// 当前文档版本 / Current document version
+    pub const VERSION: u32 = 1;
+
+    /// 序列化为 JSON / Serialize into JSON
+    pub fn to_json_string(&self) -> Result<String, AqaraError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// 从 JSON 解析 / Parse from JSON
+    pub fn from_json_str(s: &str) -> Result<Self, AqaraError> {
+        Ok(serde_json::from_str(s)?)
+    }
+}
+
+/// 校验自定义按键 (Validate custom IR keys)
+///
+/// 检查按键ID唯一、名称非空、红外码为 base64，一次返回所有问题。
+/// Checks key id uniqueness, non-empty names and base64 IR codes, reporting
+/// every problem at once.
+pub fn validate_ir_code_infos(infos: &[IrCodeInfo]) -> Result<(), AqaraError> {
+    let mut problems = Vec::new();
+    let mut seen = HashSet::new();
+
+    if infos.is_empty() {
+        problems.push("infos must contain at least one key".to_string());
+    }
+    for (i, info) in infos.iter().enumerate() {
+        if info.key_id.is_empty() {
+            problems.push(format!("infos[{}]: key_id is empty", i));
+        } else if !seen.insert(info.key_id.as_str()) {
+            problems.push(format!("infos[{}]: duplicate key_id {}", i, info.key_id));
+        }
+        if info.name.is_empty() {
+            problems.push(format!("infos[{}]: name is empty", i));
+        }
+        if info.ir_code.is_empty() {
+            problems.push(format!("infos[{}]: ir_code is empty", i));
+        } else if !info
+            .ir_code
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'='))
+        {
+            problems.push(format!("infos[{}]: ir_code is not valid base64", i));
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(AqaraError::InvalidParams(problems))
+    }
+}
+
+impl AqaraClient {
+    /// 创建或更新自定义遥控器 (Create or update a custom IR controller)
+    ///
+    /// intent: config.ir.custom
+    ///
+    /// 发送前在本地校验按键 (参见 `validate_ir_code_infos`)。
+    /// Keys are validated locally before sending (see `validate_ir_code_infos`).
+    ///
+    /// # Parameters 参数
+    /// - `params`: 遥控器参数 / Controller params
+    /// - `did`: 已有遥控器ID (更新时传入) / Existing controller DID (set when updating)
+    /// - `infos`: 按键列表 / Key list
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn config_ir_custom(
+        &self,
+        params: &IrCustomParams,
+        did: Option<&str>,
+        infos: &[IrCodeInfo],
+    ) -> Result<String, AqaraError> {
+        validate_ir_code_infos(infos)?;
+        let data = ir_custom_data(params, did, infos);
+        self.send_api_request("config.ir.custom", data, true).await
+    }
+
+    /// 查询遥控器信息 (Query IR controller info)
+    ///
+    /// intent: query.ir.info
+    ///
+    /// # Parameters 参数
+    /// - `did`: 遥控器ID / Controller DID
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn query_ir_info(&self, did: &str) -> Result<String, AqaraError> {
+        let data = json!({
+            "did": did
+        });
+        self.send_api_request("query.ir.info", data, true).await
+    }
+
+    /// 查询遥控器按键 (Query IR controller keys)
+    ///
+    /// intent: query.ir.keys
+    ///
+    /// # Parameters 参数
+    /// - `did`: 遥控器ID / Controller DID
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn query_ir_keys(&self, did: &str) -> Result<String, AqaraError> {
+        let data = json!({
+            "did": did
+        });
+        self.send_api_request("query.ir.keys", data, true).await
+    }
+
+    /// 导出自定义遥控器按键 (Export a custom IR controller's keys)
+    ///
+    /// intent: query.ir.info, query.ir.keys
+    ///
+    /// Aqara 没有单独查询按键红外码的接口，只能使用 `query.ir.keys` 返回的红外码；
+    /// 任一按键缺少红外码、或遥控器缺少品牌ID时导出失败，绝不生成无法导入的备份。
+    /// Aqara has no intent to fetch a single key's IR code, so only the codes
+    /// `query.ir.keys` returns can be used; the export fails when any key lacks
+    /// one or the controller has no brand ID, so it never writes a backup that
+    /// cannot be restored.
+    ///
+    /// # Parameters 参数
+    /// - `did`: 遥控器ID / Controller DID
+    ///
+    /// # Returns
+    /// 成功返回导出文档 / Returns the export document on success
+    pub async fn export_ir_keys(&self, did: &str) -> Result<IrKeySetDocument, AqaraError> {
+        let info = self
+            .call_api::<Value>("query.ir.info", json!({ "did": did }), true)
+            .await?
+            .result
+            .unwrap_or(Value::Null);
+        let keys = self
+            .call_api::<Value>("query.ir.keys", json!({ "did": did }), true)
+            .await?
+            .result
+            .unwrap_or(Value::Null);
+
+        // 结果可能是数组或包含 keys 的对象 / Result may be an array or an object holding `keys`
+        let keys = match keys {
+            Value::Array(items) => items,
+            Value::Object(mut map) => match map.remove("keys") {
+                Some(Value::Array(items)) => items,
+                _ => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+        let keys = keys
+            .iter()
+            .map(|key| IrCodeInfo {
+                key_id: value_to_string(key.get("keyId")),
+                name: value_to_string(key.get("name")),
+                ir_code: value_to_string(key.get("irCode").or_else(|| key.get("code"))),
+                freq: key.get("freq").and_then(Value::as_i64).map(|f| f as i32),
+            })
+            .collect::<Vec<_>>();
+
+        let mut problems = Vec::new();
+        let category_id = info.get("categoryId").and_then(Value::as_i64);
+        let brand_id = info.get("brandId").and_then(Value::as_i64);
+        if category_id.is_none() {
+            problems.push(format!("{}: query.ir.info returned no categoryId", did));
+        }
+        if brand_id.is_none() {
+            problems.push(format!("{}: query.ir.info returned no brandId", did));
+        }
+        for key in keys.iter().filter(|key| key.ir_code.is_empty()) {
+            problems.push(format!(
+                "key {} ({}): query.ir.keys returned no IR code",
+                key.key_id, key.name
+            ));
+        }
+        if !problems.is_empty() {
+            return Err(AqaraError::InvalidParams(problems));
+        }
+
+        let doc = IrKeySetDocument {
+            version: IrKeySetDocument::VERSION,
+            name: value_to_string(info.get("name")),
+            category_id: category_id.unwrap_or_default() as i32,
+            brand_id: brand_id.unwrap_or_default() as i32,
+            keys,
+        };
+        // 与导入相同的校验 / The same checks the import applies
+        validate_ir_code_infos(&doc.keys)?;
+        Ok(doc)
+    }
+
+    /// 导入自定义遥控器按键 (Import IR keys onto a gateway)
+    ///
+    /// intent: config.ir.custom
+    ///
+    /// # Parameters 参数
+    /// - `doc`: 导出文档 / Export document
+    /// - `parent_did`: 目标红外网关ID / Target IR gateway DID
+    /// - `position_id`: 位置ID (可选) / Position ID (optional)
+    ///
+    /// # Returns
+    /// 成功返回新遥控器ID / Returns the new controller DID on success
+    pub async fn import_ir_keys(
+        &self,
+        doc: &IrKeySetDocument,
+        parent_did: &str,
+        position_id: Option<&str>,
+    ) -> Result<String, AqaraError> {
+        let params = IrCustomParams {
+            parent_did: parent_did.to_string(),
+            position_id: position_id.map(str::to_string),
+            category_id: doc.category_id,
+            brand_id: doc.brand_id,
+            name: doc.name.clone(),
+        };
+        validate_ir_code_infos(&doc.keys)?;
+        let data = ir_custom_data(&params, None, &doc.keys);
+        let response = self
+            .call_api::<Value>("config.ir.custom", data, true)
+            .await?;
+        require_id(response, "config.ir.custom", "did")
+    }
+}
+
+fn ir_custom_data(params: &IrCustomParams, did: Option<&str>, infos: &[IrCodeInfo]) -> Value {
+    let mut data = json!({
+        "parentDid": params.parent_did,
+        "categoryId": params.category_id,
+        "brandId": params.brand_id,
+        "name": params.name,
+        "infos": infos
+    });
+    if let Some(position_id) = &params.position_id {
+        data["positionId"] = json!(position_id);
+    }
+    if let Some(did) = did {
+        data["did"] = json!(did);
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(key_id: &str, name: &str, ir_code: &str) -> IrCodeInfo {
+        IrCodeInfo {
+            key_id: key_id.to_string(),
+            name: name.to_string(),
+            ir_code: ir_code.to_string(),
+            freq: None,
+        }
+    }
+
+    fn problems(infos: &[IrCodeInfo]) -> Vec<String> {
+        match validate_ir_code_infos(infos) {
+            Err(AqaraError::InvalidParams(problems)) => problems,
+            other => panic!("expected InvalidParams, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn accepts_valid_keys() {
+        let infos = [key("1", "power", "AAEC+/8="), key("2", "mode", "QUJD")];
+        assert!(validate_ir_code_infos(&infos).is_ok());
+    }
+
+    #[test]
+    fn rejects_empty_list() {
+        assert_eq!(problems(&[]), ["infos must contain at least one key"]);
+    }
+
+    #[test]
+    fn reports_every_problem_at_once() {
+        let infos = [
+            key("1", "power", "QUJD"),
+            key("1", "", "QUJD"),
+            key("", "mode", ""),
+            key("3", "fan", "not base64!"),
+        ];
+        assert_eq!(
+            problems(&infos),
+            [
+                "infos[1]: duplicate key_id 1",
+                "infos[1]: name is empty",
+                "infos[2]: key_id is empty",
+                "infos[2]: ir_code is empty",
+                "infos[3]: ir_code is not valid base64",
+            ]
+        );
+    }
+}
@@ -0,0 +1,311 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::util::value_to_string;
+use crate::{AqaraClient, AqaraError};
+
+/// 空调最低温度 (°C) / Lowest AC temperature (°C)
+pub const AC_MIN_TEMPERATURE: u8 = 16;
+/// 空调最高温度 (°C) / Highest AC temperature (°C)
+pub const AC_MAX_TEMPERATURE: u8 = 30;
+
+/// 空调模式 (AC mode)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AcMode {
+    /// 制热 (M0) / Heat (M0)
+    Heat,
+    /// 制冷 (M1) / Cool (M1)
+    Cool,
+    /// 自动 (M2) / Auto (M2)
+    Auto,
+    /// 除湿 (M3) / Dry (M3)
+    Dry,
+    /// 送风 (M4) / Fan only (M4)
+    Fan,
+}
+
+/// 空调风速 (AC fan speed)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AcFan {
+    /// 自动 (S0) / Auto (S0)
+    Auto,
+    /// 低速 (S1) / Low (S1)
+    Low,
+    /// 中速 (S2) / Medium (S2)
+    Medium,
+    /// 高速 (S3) / High (S3)
+    High,
+}
+
+/// 空调状态 (AC state)
+///
+/// 对应 `acKey` 字符串 `P{电源}_M{模式}_T{温度}_S{风速}_D{风向}`，
+/// 例如 `P0_M1_T26_S0_D0` 表示开机、制冷、26°C、自动风速、扫风。
+/// Maps to the `acKey` string `P{power}_M{mode}_T{temp}_S{fan}_D{swing}`, e.g.
+/// `P0_M1_T26_S0_D0` is on, cooling, 26°C, auto fan, swinging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AcState {
+    /// 开机 (P0 开, P1 关) / Powered on (P0 on, P1 off)
+    pub power: bool,
+    /// 模式 / Mode
+    pub mode: AcMode,
+    /// 温度 (°C) / Temperature (°C)
+    pub temp: u8,
+    /// 风速 / Fan speed
+    pub fan: AcFan,
+    /// 扫风 (D0 扫风, D1 固定) / Swinging (D0 swing, D1 fixed)
+    pub swing: bool,
+}
+
+impl AcState {
+    /// 生成 `acKey` (Build the `acKey`)
+    ///
+    /// 温度超出 16-30°C 时返回 `AqaraError::InvalidParams`。
+    /// Returns `AqaraError::InvalidParams` when the temperature is outside 16-30°C.
+    pub fn to_ac_key(&self) -> Result<String, AqaraError> {
+        if !(AC_MIN_TEMPERATURE..=AC_MAX_TEMPERATURE).contains(&self.temp) {
+            return Err(AqaraError::InvalidParams(vec![format!(
+                "temperature {} is outside {}-{}",
+                self.temp, AC_MIN_TEMPERATURE, AC_MAX_TEMPERATURE
+            )]));
+        }
+        Ok(self.to_string())
+    }
+
+    /// 解析 `query.ir.acState` 的结果 (Parse a `query.ir.acState` result)
+    ///
+    /// 接受带 `acState` 字段的对象或 `acKey` 字符串本身。
+    /// Accepts an object with an `acState` field or the `acKey` string itself.
+    pub fn from_response(result: &Value) -> Result<Self, AqaraError> {
+        let key = match result.get("acState") {
+            Some(state) => value_to_string(Some(state)),
+            None => value_to_string(Some(result)),
+        };
+        key.parse()
+    }
+}
+
+impl fmt::Display for AcState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mode = match self.mode {
+            AcMode::Heat => 0,
+            AcMode::Cool => 1,
+            AcMode::Auto => 2,
+            AcMode::Dry => 3,
+            AcMode::Fan => 4,
+        };
+        let fan = match self.fan {
+            AcFan::Auto => 0,
+            AcFan::Low => 1,
+            AcFan::Medium => 2,
+            AcFan::High => 3,
+        };
+        write!(
+            f,
+            "P{}_M{}_T{}_S{}_D{}",
+            u8::from(!self.power),
+            mode,
+            self.temp,
+            fan,
+            u8::from(!self.swing)
+        )
+    }
+}
+
+impl FromStr for AcState {
+    type Err = AqaraError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || AqaraError::InvalidParams(vec![format!("invalid acKey {:?}", s)]);
+        let mut fields = [None; 5];
+        for part in s.trim().split('_') {
+            let slot = match part.get(..1) {
+                Some("P") => 0,
+                Some("M") => 1,
+                Some("T") => 2,
+                Some("S") => 3,
+                Some("D") => 4,
+                _ => return Err(invalid()),
+            };
+            let value: u8 = part[1..].parse().map_err(|_| invalid())?;
+            fields[slot] = Some(value);
+        }
+        let [Some(power), Some(mode), Some(temp), Some(fan), Some(swing)] = fields else {
+            return Err(invalid());
+        };
+        Ok(AcState {
+            power: match power {
+                0 => true,
+                1 => false,
+                _ => return Err(invalid()),
+            },
+            mode: match mode {
+                0 => AcMode::Heat,
+                1 => AcMode::Cool,
+                2 => AcMode::Auto,
+                3 => AcMode::Dry,
+                4 => AcMode::Fan,
+                _ => return Err(invalid()),
+            },
+            temp,
+            fan: match fan {
+                0 => AcFan::Auto,
+                1 => AcFan::Low,
+                2 => AcFan::Medium,
+                3 => AcFan::High,
+                _ => return Err(invalid()),
+            },
+            swing: match swing {
+                0 => true,
+                1 => false,
+                _ => return Err(invalid()),
+            },
+        })
+    }
+}
+
+impl AqaraClient {
+    /// 查询空调状态 (Query the AC state)
+    ///
+    /// intent: query.ir.acState
+    ///
+    /// # Parameters 参数
+    /// - `did`: 遥控器ID / Controller DID
+    ///
+    /// # Returns
+    /// 成功返回空调状态 / Returns the AC state on success
+    pub async fn query_ir_ac_state(&self, did: &str) -> Result<AcState, AqaraError> {
+        let result = self
+            .call_api::<Value>("query.ir.acState", json!({ "did": did }), true)
+            .await?
+            .result
+            .unwrap_or(Value::Null);
+        AcState::from_response(&result)
+    }
+
+    /// 设置空调状态 (Set the AC state)
+    ///
+    /// intent: write.ir.click
+    ///
+    /// # Parameters 参数
+    /// - `did`: 遥控器ID / Controller DID
+    /// - `brand_id`: 品牌ID / Brand ID
+    /// - `controller_id`: 遥控器型号ID / Controller (remote model) ID
+    /// - `state`: 目标状态 / Target state
+    pub async fn write_ir_ac_state(
+        &self,
+        did: &str,
+        brand_id: i32,
+        controller_id: i32,
+        state: &AcState,
+    ) -> Result<(), AqaraError> {
+        let data = json!({
+            "did": did,
+            "brandId": brand_id,
+            "controllerId": controller_id,
+            "acKey": state.to_ac_key()?
+        });
+        self.call_api::<Value>("write.ir.click", data, true).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cooling() -> AcState {
+        AcState {
+            power: true,
+            mode: AcMode::Cool,
+            temp: 26,
+            fan: AcFan::Auto,
+            swing: true,
+        }
+    }
+
+    #[test]
+    fn encodes_the_documented_example() {
+        assert_eq!(cooling().to_ac_key().unwrap(), "P0_M1_T26_S0_D0");
+    }
+
+    #[test]
+    fn encodes_off_and_fixed_as_one() {
+        let state = AcState {
+            power: false,
+            mode: AcMode::Fan,
+            temp: 16,
+            fan: AcFan::High,
+            swing: false,
+        };
+        assert_eq!(state.to_ac_key().unwrap(), "P1_M4_T16_S3_D1");
+    }
+
+    #[test]
+    fn rejects_out_of_range_temperatures() {
+        for temp in [AC_MIN_TEMPERATURE - 1, AC_MAX_TEMPERATURE + 1] {
+            let state = AcState { temp, ..cooling() };
+            assert!(matches!(
+                state.to_ac_key(),
+                Err(AqaraError::InvalidParams(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn parses_keys_in_any_field_order() {
+        assert_eq!("P0_M1_T26_S0_D0".parse::<AcState>().unwrap(), cooling());
+        assert_eq!(" D0_S0_T26_M1_P0 ".parse::<AcState>().unwrap(), cooling());
+    }
+
+    #[test]
+    fn round_trips_every_mode_and_fan() {
+        let modes = [
+            AcMode::Heat,
+            AcMode::Cool,
+            AcMode::Auto,
+            AcMode::Dry,
+            AcMode::Fan,
+        ];
+        let fans = [AcFan::Auto, AcFan::Low, AcFan::Medium, AcFan::High];
+        for mode in modes {
+            for fan in fans {
+                let state = AcState {
+                    mode,
+                    fan,
+                    ..cooling()
+                };
+                assert_eq!(state.to_string().parse::<AcState>().unwrap(), state);
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_keys() {
+        for key in [
+            "",
+            "P0_M1_T26_S0",
+            "P2_M1_T26_S0_D0",
+            "P0_M5_T26_S0_D0",
+            "P0_M1_T26_S4_D0",
+            "P0_M1_T26_S0_D2",
+            "P0_M1_Tx_S0_D0",
+            "P0_M1_T26_S0_X0",
+        ] {
+            assert!(key.parse::<AcState>().is_err(), "accepted {:?}", key);
+        }
+    }
+
+    #[test]
+    fn from_response_accepts_an_object_or_a_string() {
+        let object = json!({ "acState": "P0_M1_T26_S0_D0" });
+        assert_eq!(AcState::from_response(&object).unwrap(), cooling());
+        let string = json!("P0_M1_T26_S0_D0");
+        assert_eq!(AcState::from_response(&string).unwrap(), cooling());
+    }
+}
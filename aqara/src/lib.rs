@@ -1,10 +1,149 @@
-use md5;
-use rand::distr::Alphanumeric;
-use rand::{thread_rng, Rng};
-use reqwest::{Client, Error};
+mod audit;
+mod auth;
+mod builder;
+mod call_options;
+mod catalog;
+mod circuit_breaker;
+mod client_config;
+mod clock_skew;
+mod cover;
+#[cfg(feature = "local-scheduler")]
+pub mod cron;
+mod device;
+mod dns;
+mod energy;
+mod error;
+mod error_code;
+mod event;
+mod handle;
+mod hedging;
+mod history;
+mod home;
+mod ir;
+mod ir_ac;
+mod light;
+mod linkage;
+mod middleware;
+pub mod oauth;
+mod ota;
+mod pagination;
+mod policy;
+mod position;
+pub mod push;
+mod quota;
+mod region;
+mod resource;
+mod response;
+mod retry;
+mod scene;
+mod scene_builder;
+mod scheduler;
+mod session;
+mod signer;
+mod shutdown;
+mod single_flight;
+mod switch;
+mod tags;
+mod thermostat;
+mod time_range;
+mod timeout;
+#[cfg(feature = "token-encryption")]
+mod token_file;
+mod tokens;
+mod util;
+mod validate;
+pub mod webhook;
+
+pub use audit::{AuditOutcome, AuditRecord, AuditSink};
+pub use auth::AppMode;
+pub use builder::{AqaraClientBuilder, ClientDefaults};
+pub use call_options::CallOptions;
+pub use catalog::{
+    resource_id_for, ModelFamily, ResourceCatalog, ResourceInfo, ResourceKind,
+    ILLUMINANCE_RESOURCE_ID,
+};
+pub use circuit_breaker::{CircuitBreakerConfig, CircuitState};
+pub use client_config::{ENV_ACCESS_TOKEN, ENV_APP_ID, ENV_APP_KEY, ENV_KEY_ID, ENV_REGION};
+pub use cover::{
+    CoverControl, CoverProfile, COVER_CONTROL_RESOURCE_ID, COVER_POSITION_RESOURCE_ID,
+};
+pub use device::{
+    BatteryLevel, DeviceInfo, DeviceInfoPage, DeviceMove, InventoryFormat, InventoryRecord,
+    SubDeviceInfo, BATTERY_RESOURCE_ID, DEFAULT_MOVE_VERIFY_DELAY,
+};
+pub use dns::DnsCacheConfig;
+pub use energy::{DailyEnergy, EnergyReport, ENERGY_RESOURCE_ID};
+pub use error::{AqaraError, ConfigProblem, DecodeSnippet, ErrorKind};
+pub use error_code::{describe_code, ErrorCode};
+pub use handle::{
+    Device, Reading, HUMIDITY_RESOURCE_ID, PRESSURE_RESOURCE_ID, TEMPERATURE_RESOURCE_ID,
+};
+pub use event::{EventCondition, EventConditionSet, EventParam};
+pub use home::{Home, Linkage, Room, Scene};
+pub use hedging::HedgingConfig;
+pub use history::{
+    AggrType, ResourceHistoryPage, ResourceHistoryPoint, StatisticsDimension, StatisticsParams,
+    StatisticsPoint, HISTORY_MAX_PAGE_SIZE, HISTORY_MAX_WINDOW, SCAN_MAX_PAGES,
+};
+pub use ir::{validate_ir_code_infos, IrCodeInfo, IrCustomParams, IrKeySetDocument};
+pub use ir_ac::{AcFan, AcMode, AcState, AC_MAX_TEMPERATURE, AC_MIN_TEMPERATURE};
+pub use light::{
+    LightControl, LightState, Rgb, LIGHT_BRIGHTNESS_RESOURCE_ID, LIGHT_COLOR_RESOURCE_ID,
+    LIGHT_COLOR_TEMPERATURE_RESOURCE_ID, LIGHT_POWER_RESOURCE_ID,
+};
+pub use linkage::LinkageToggle;
+pub use middleware::{Middleware, MiddlewareRequest};
+pub use ota::{FleetRollout, RolloutConfig, RolloutProgress, UpgradeOutcome};
+pub use position::{
+    CreatedPosition, PositionDetail, PositionInfo, PositionPage, PositionTemplate,
+    POSITION_DETAIL_MAX_IDS,
+};
+pub use quota::{QuotaLimits, QuotaUsage, SoftLimitAction};
+pub use region::Region;
+pub use resource::{
+    BatchResult, ChunkFailure, DeviceResourceWrite, PartialResult, ResourceName, ResourceQuery,
+    ResourceWrite, RESOURCE_NAME_MAX_SUBJECTS, RESOURCE_VALUE_MAX_SUBJECTS,
+};
+pub use response::AqaraResponse;
+pub use retry::{Attempt, AttemptHistory, RetryBudget, RetryConfig, RetryPolicy};
+pub use scene::{SceneAction, SceneDocument};
+pub use scene_builder::{SceneBuilder, SCENE_MAX_ACTIONS, SCENE_MAX_DELAY};
+pub use scheduler::Priority;
+pub use session::Session;
+pub use signer::{AppKeySigner, SignatureParts, Signer};
+pub use switch::{SwitchControl, SwitchProfile, SWITCH_POWER_RESOURCE_ID};
+pub use tags::{TagMap, TaggedWrite};
+pub use thermostat::{
+    ScheduleSegment, ThermostatSchedule, ThermostatScheduleBuilder, THERMOSTAT_MAX_TEMPERATURE,
+    THERMOSTAT_MIN_TEMPERATURE, THERMOSTAT_SCHEDULE_SEGMENTS, THERMOSTAT_TARGET_RESOURCE_ID,
+};
+pub use time_range::TimeRange;
+pub use timeout::TimeoutConfig;
+#[cfg(feature = "token-encryption")]
+pub use token_file::EncryptedTokenFile;
+pub use tokens::{TokenBundle, TokenMap, TokenRefresher, UserTokens};
+pub use validate::DEVICE_INFO_MAX_DIDS;
+
+use std::io::Write;
+use std::sync::{Arc, RwLock};
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use reqwest::Client;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use circuit_breaker::CircuitBreaker;
+use clock_skew::ClockSkew;
+use policy::IntentPolicy;
+use quota::QuotaTracker;
+use retry::RetryThrottle;
+use scheduler::Scheduler;
+use shutdown::Lifecycle;
+use single_flight::SingleFlight;
 use tracing::debug;
+use util::{check_max_items, IntentMap};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AqaraConfig {
@@ -14,62 +153,163 @@ pub struct AqaraConfig {
     pub app_key: String,
 }
 
+/// Aqara 客户端 (Aqara client)
+///
+/// 克隆开销很小，克隆体共享连接池与配置。
+/// Cheap to clone; clones share the connection pool and configuration.
+#[derive(Clone)]
 pub struct AqaraClient {
+    inner: Arc<ClientInner>,
+    // `as_user` 视图对应的用户键 / User key of an `as_user` view
+    user: Option<String>,
+    // `with_actor` 视图对应的操作者 / Actor of a `with_actor` view
+    actor: Option<String>,
+    // `with_options` 视图的调用选项 / Call options of a `with_options` view
+    options: Option<Arc<CallOptions>>,
+}
+
+pub(crate) struct ClientInner {
     config: AqaraConfig,
     client: Client,
     base_url: String,
+    defaults: ClientDefaults,
+    timeout: TimeoutConfig,
+    intent_timeouts: IntentMap<TimeoutConfig>,
+    retry: RetryConfig,
+    retry_policy: Option<Arc<dyn RetryPolicy>>,
+    retry_throttle: Option<RetryThrottle>,
+    intent_retries: IntentMap<RetryConfig>,
+    intent_idempotency: IntentMap<bool>,
+    scheduler: Option<Scheduler>,
+    intent_priorities: IntentMap<Priority>,
+    lifecycle: Lifecycle,
+    app_mode: Option<AppMode>,
+    intent_policy: IntentPolicy,
+    tokens: TokenMap,
+    primary_tokens: RwLock<UserTokens>,
+    tags: TagMap,
+    quota: QuotaTracker,
+    audit_sink: Option<Arc<dyn AuditSink>>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    compression_threshold: Option<usize>,
+    single_flight: Option<SingleFlight>,
+    hedging: Option<HedgingConfig>,
+    circuit_breaker: Option<CircuitBreaker>,
+    clock_skew: Option<ClockSkew>,
+    signer: Option<Arc<dyn Signer>>,
+    auto_install_tokens: bool,
+}
+
+/// 计算签名 (请求与推送共用) / Compute a signature (shared by requests and pushes)
+pub(crate) fn compute_sign(
+    app_id: &str,
+    key_id: &str,
+    app_key: &str,
+    nonce: &str,
+    time: &str,
+    access_token: Option<&str>,
+) -> String {
+    SignatureParts {
+        access_token,
+        app_id,
+        key_id,
+        nonce,
+        time,
+    }
+    .sign_with(app_key)
+}
+
+/// gzip 压缩请求体 / Gzip a request body
+fn gzip(body: &[u8]) -> Result<Vec<u8>, AqaraError> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(body)?;
+    Ok(encoder.finish()?)
+}
+
+fn default_base_url() -> &'static str {
+    Region::default().base_url()
 }
 
 impl AqaraClient {
     pub fn new(config: AqaraConfig) -> Self {
-        // 根据编译特性选择不同的接口地址
-        // Select different API endpoints based on compilation features
-        let base_url = if cfg!(feature = "china") {
-            "https://open-cn.aqara.com/v3.0/open/api"
-        } else if cfg!(feature = "usa") {
-            "https://open-usa.aqara.com/v3.0/open/api"
-        } else if cfg!(feature = "europe") {
-            "https://open-ger.aqara.com/v3.0/open/api"
-        } else if cfg!(feature = "korea") {
-            "https://open-kr.aqara.com/v3.0/open/api"
-        } else if cfg!(feature = "russia") {
-            "https://open-ru.aqara.com/v3.0/open/api"
-        } else if cfg!(feature = "singapore") {
-            "https://open-sg.aqara.com/v3.0/open/api"
-        } else {
-            "https://open-cn.aqara.com/v3.0/open/api"
-        };
-
-        AqaraClient {
+        AqaraClient::from_inner(ClientInner {
             client: Client::new(),
+            primary_tokens: tokens::primary_slot(&config),
             config,
-            base_url: base_url.to_string(),
+            base_url: default_base_url().to_string(),
+            defaults: ClientDefaults::default(),
+            timeout: TimeoutConfig::default(),
+            intent_timeouts: IntentMap::default(),
+            retry: RetryConfig::disabled(),
+            retry_policy: None,
+            retry_throttle: None,
+            intent_retries: IntentMap::default(),
+            intent_idempotency: IntentMap::default(),
+            scheduler: None,
+            intent_priorities: IntentMap::default(),
+            lifecycle: Lifecycle::default(),
+            app_mode: None,
+            intent_policy: IntentPolicy::default(),
+            tokens: TokenMap::default(),
+            tags: TagMap::default(),
+            quota: QuotaTracker::new(None),
+            audit_sink: None,
+            middleware: Vec::new(),
+            compression_threshold: None,
+            single_flight: None,
+            hedging: None,
+            circuit_breaker: None,
+            clock_skew: None,
+            signer: None,
+            auto_install_tokens: false,
+        })
+    }
+
+    pub(crate) fn from_inner(inner: ClientInner) -> Self {
+        AqaraClient {
+            inner: Arc::new(inner),
+            user: None,
+            actor: None,
+            options: None,
         }
     }
 
+    /// 创建客户端构建器 / Create a client builder
+    pub fn builder(config: AqaraConfig) -> AqaraClientBuilder {
+        AqaraClientBuilder::new(config)
+    }
+
     fn generate_nonce(&self) -> String {
-        thread_rng()
-            .sample_iter(&Alphanumeric)
-            .take(30)
-            .map(char::from)
-            .collect()
+        match &self.inner.signer {
+            Some(signer) => signer.nonce(),
+            None => signer::random_nonce(),
+        }
     }
 
     pub fn generate_signature(&self, nonce: &str, time: &str, include_access_token: bool) -> String {
-        let mut sign_str = String::new();
+        let access_token = self.access_token().unwrap_or_default();
+        self.sign(nonce, time, include_access_token.then_some(access_token.as_str()))
+    }
 
-        // 决定是否加入Accesstoken / Decide whether to include Accesstoken
-        if include_access_token && !self.config.access_token.is_empty() {
-            sign_str.push_str(&format!("Accesstoken={}&", self.config.access_token));
-        }
-        sign_str.push_str(&format!(
-            "Appid={}&Keyid={}&Nonce={}&Time={}",
-            self.config.app_id, self.config.key_id, nonce, time
-        ));
-        sign_str.push_str(&self.config.app_key);
-        let sign_str = sign_str.to_lowercase();
-        let digest = md5::compute(sign_str.as_bytes());
-        format!("{:x}", digest)
+    fn sign(&self, nonce: &str, time: &str, access_token: Option<&str>) -> String {
+        let config = &self.inner.config;
+        let Some(signer) = &self.inner.signer else {
+            return compute_sign(
+                &config.app_id,
+                &config.key_id,
+                &config.app_key,
+                nonce,
+                time,
+                access_token,
+            );
+        };
+        signer.sign(&SignatureParts {
+            access_token,
+            app_id: &config.app_id,
+            key_id: &config.key_id,
+            nonce,
+            time,
+        })
     }
 
     async fn send_api_request(
@@ -77,48 +317,393 @@ impl AqaraClient {
         intent: &str,
         data: Value,
         include_access_token: bool,
-    ) -> Result<String, Error> {
+    ) -> Result<String, AqaraError> {
+        let Some(sink) = &self.inner.audit_sink else {
+            return self
+                .dispatch_api_request(intent, data, include_access_token)
+                .await;
+        };
+        let timestamp = chrono::Utc::now();
+        let params = data.clone();
+        let result = self
+            .dispatch_api_request(intent, data, include_access_token)
+            .await;
+        sink.record(&AuditRecord::new(timestamp, intent, self, &params, &result));
+        result
+    }
+
+    async fn dispatch_api_request(
+        &self,
+        intent: &str,
+        data: Value,
+        include_access_token: bool,
+    ) -> Result<String, AqaraError> {
+        if let Some(mode) = self.inner.app_mode {
+            mode.check_intent(intent)?;
+        }
+        self.inner.intent_policy.check(intent)?;
+        validate::validate_request(intent, &data)?;
+        let _in_flight = self.inner.lifecycle.enter()?;
+        tokio::select! {
+            result = self.send_coalesced(intent, data, include_access_token) => result,
+            _ = self.inner.lifecycle.cancelled() => Err(AqaraError::Shutdown),
+        }
+    }
+
+    fn is_idempotent(&self, intent: &str) -> bool {
+        self.inner
+            .intent_idempotency
+            .get(intent)
+            .copied()
+            .unwrap_or_else(|| retry::is_idempotent_by_default(intent))
+    }
+
+    pub(crate) async fn send_api_request_with_retry(
+        &self,
+        intent: &str,
+        data: &Value,
+        include_access_token: bool,
+    ) -> Result<String, AqaraError> {
+        let idempotent = self.is_idempotent(intent);
+        let retry: &dyn RetryPolicy = if idempotent {
+            match self
+                .options
+                .as_ref()
+                .and_then(|options| options.retry.as_ref())
+                .or_else(|| self.inner.intent_retries.get(intent))
+            {
+                Some(retry) => retry,
+                None => match &self.inner.retry_policy {
+                    Some(policy) => policy.as_ref(),
+                    None => &self.inner.retry,
+                },
+            }
+        } else {
+            &RetryConfig::disabled()
+        };
+
+        let priority = self
+            .options
+            .as_ref()
+            .and_then(|options| options.priority)
+            .or_else(|| self.inner.intent_priorities.get(intent).copied())
+            .unwrap_or_else(|| Priority::default_for(intent));
+
+        let started = std::time::Instant::now();
+        let mut history = Vec::new();
+        let mut attempt = 0;
+        loop {
+            let permit = match &self.inner.scheduler {
+                Some(scheduler) => Some(scheduler.acquire(priority).await),
+                None => None,
+            };
+            let result = match self.inner.hedging {
+                Some(hedging) if idempotent => {
+                    self.send_hedged(intent, data, include_access_token, hedging)
+                        .await
+                }
+                _ => {
+                    self.send_api_request_once(intent, data, include_access_token)
+                        .await
+                }
+            };
+            drop(permit);
+            // 可重试的业务码也进入重试；不再重试时原样返回响应体，由 `call_api` 转为错误
+            // Retryable business codes are retried too; once retrying stops the body is
+            // returned as is and `call_api` turns it into an error
+            let (e, body) = match result {
+                Ok(body) => match retry::retryable_business_error(&body) {
+                    Some(e) => (e, Some(body)),
+                    None => return Ok(body),
+                },
+                Err(e) => (e, None),
+            };
+            let delay = retry
+                .should_retry(&e, attempt)
+                .filter(|_| self.spend_retry_budget(intent));
+            let Some(delay) = delay else {
+                if history.is_empty() {
+                    return match body {
+                        Some(body) => Ok(body),
+                        None => Err(e),
+                    };
+                }
+                history.push(Attempt::new(&e, std::time::Duration::ZERO));
+                return Err(AqaraError::RetriesExhausted {
+                    last: Box::new(e),
+                    history: AttemptHistory {
+                        attempts: history,
+                        elapsed: started.elapsed(),
+                    },
+                });
+            };
+            attempt += 1;
+            debug!("Retrying {} in {:?} (attempt {}): {}", intent, delay, attempt, e);
+            history.push(Attempt::new(&e, delay));
+            #[cfg(feature = "metrics")]
+            metrics::counter!(
+                "aqara_retries_total",
+                "intent" => intent.to_string(),
+                "reason" => retry::retry_reason(&e)
+            )
+            .increment(1);
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    // 从客户端重试预算中扣除一次 / Spend one retry from the client-wide budget
+    fn spend_retry_budget(&self, intent: &str) -> bool {
+        let Some(throttle) = &self.inner.retry_throttle else {
+            return true;
+        };
+        let spent = throttle.try_spend();
+        if !spent {
+            debug!("Retry budget exhausted; not retrying {}", intent);
+            #[cfg(feature = "metrics")]
+            metrics::counter!(
+                "aqara_retry_budget_exhausted_total",
+                "intent" => intent.to_string()
+            )
+            .increment(1);
+        }
+        spent
+    }
+
+    async fn send_api_request_once(
+        &self,
+        intent: &str,
+        data: &Value,
+        include_access_token: bool,
+    ) -> Result<String, AqaraError> {
+        if self.inner.middleware.is_empty() {
+            return self
+                .send_http_request(intent, data, &[], include_access_token)
+                .await;
+        }
+        let mut request = MiddlewareRequest {
+            intent: intent.to_string(),
+            data: data.clone(),
+            headers: Vec::new(),
+        };
+        let short_circuit = self
+            .inner
+            .middleware
+            .iter()
+            .find_map(|middleware| middleware.before_request(&mut request));
+        let result = match short_circuit {
+            Some(body) => Ok(body),
+            None => {
+                self.send_http_request(
+                    intent,
+                    &request.data,
+                    &request.headers,
+                    include_access_token,
+                )
+                .await
+            }
+        };
+        for middleware in self.inner.middleware.iter().rev() {
+            middleware.after_response(&request, &result);
+        }
+        result
+    }
+
+    async fn send_http_request(
+        &self,
+        intent: &str,
+        data: &Value,
+        extra_headers: &[(String, String)],
+        include_access_token: bool,
+    ) -> Result<String, AqaraError> {
+        let access_token = match include_access_token {
+            true => Some(self.access_token()?),
+            false => None,
+        };
         let nonce = self.generate_nonce();
-        let time = format!("{}", chrono::Utc::now().timestamp_millis());
-        let sign = self.generate_signature(&nonce, &time, include_access_token);
+        let time = match &self.inner.signer {
+            Some(signer) => signer.time_millis(),
+            None => chrono::Utc::now().timestamp_millis(),
+        };
+        let time = match &self.inner.clock_skew {
+            Some(skew) => skew.adjust(time),
+            None => time,
+        }
+        .to_string();
+        let sign = self.sign(&nonce, &time, access_token.as_deref());
 
         let request_body = json!({
             "intent": intent,
             "data": data
         });
 
-        debug!("Request URL: {}", self.base_url);
+        debug!("Request URL: {}", self.inner.base_url);
         debug!("Request Headers:");
-        debug!("  Appid: {}", &self.config.app_id);
-        debug!("  Keyid: {}", &self.config.key_id);
+        debug!("  Appid: {}", &self.inner.config.app_id);
+        debug!("  Keyid: {}", &self.inner.config.key_id);
         debug!("  Nonce: {}", &nonce);
         debug!("  Time: {}", &time);
         debug!("  Sign: {}", &sign);
         debug!("Request Body: {}", request_body.to_string());
 
-        let mut request = self.client
-            .post(&self.base_url)
-            .header("Appid", &self.config.app_id)
-            .header("Keyid", &self.config.key_id)
+        let options = self.options.as_deref();
+        let lang = options
+            .and_then(|options| options.lang.as_deref())
+            .or(self.inner.defaults.lang.as_deref())
+            .unwrap_or("en");
+        let mut request = self
+            .inner
+            .client
+            .post(&self.inner.base_url)
+            .header("Appid", &self.inner.config.app_id)
+            .header("Keyid", &self.inner.config.key_id)
             .header("Nonce", &nonce)
             .header("Time", &time)
             .header("Sign", &sign)
-            .header("Lang", "en")
+            .header("Lang", lang)
             .header("Content-Type", "application/json")
             .header("User-Agent", "AqaraSDK/1.0");
 
-        if include_access_token {
-            request = request.header("Accesstoken", &self.config.access_token);
+        if let Some(access_token) = &access_token {
+            request = request.header("Accesstoken", access_token);
+        }
+        for (name, value) in options
+            .map(|options| options.headers.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .chain(extra_headers)
+        {
+            request = request.header(name, value);
+        }
+
+        // 调用选项优先，其次按 intent 的超时，最后全局超时
+        // Call options win, then per-intent timeouts, then the global one
+        let timeout = options
+            .and_then(|options| options.timeout)
+            .or_else(|| {
+                self.inner
+                    .intent_timeouts
+                    .get(intent)
+                    .and_then(|t| t.request)
+            })
+            .or(self.inner.timeout.request);
+        if let Some(timeout) = timeout {
+            request = request.timeout(timeout);
         }
 
-        let response = request.json(&request_body).send().await?;
+        let body = serde_json::to_vec(&request_body)?;
+        let request = match self.inner.compression_threshold {
+            Some(threshold) if body.len() >= threshold => request
+                .header("Content-Encoding", "gzip")
+                .body(gzip(&body)?),
+            _ => request.body(body),
+        };
+
+        self.inner.quota.admit(intent).await;
+        let response = request.send().await?;
 
         if response.status().is_success() {
+            let date = response
+                .headers()
+                .get(reqwest::header::DATE)
+                .and_then(|date| date.to_str().ok())
+                .map(str::to_string);
             let body = response.text().await?;
+            if let Some(skew) = &self.inner.clock_skew {
+                skew.observe(&body, date.as_deref());
+            }
             Ok(body)
         } else {
-            Err(response.error_for_status().unwrap_err())
+            if response.status().as_u16() == 429 {
+                self.inner.quota.record_rate_limited();
+            }
+            Err(response.error_for_status().unwrap_err().into())
+        }
+    }
+
+    /// 预先建立连接 (Preconnect)
+    ///
+    /// 向接口地址发送一个轻量的 HEAD 请求，提前完成 DNS 解析与 TLS 握手并放入连接池，
+    /// 缩短启动后第一条交互命令的延迟。任何 HTTP 状态码都视为成功。
+    /// Sends a lightweight HEAD request to the endpoint so DNS resolution and the
+    /// TLS handshake happen ahead of time and the connection lands in the pool,
+    /// shaving latency off the first interactive command after startup. Any HTTP
+    /// status counts as success.
+    pub async fn preconnect(&self) -> Result<(), AqaraError> {
+        let _in_flight = self.inner.lifecycle.enter()?;
+        let mut request = self.inner.client.head(&self.inner.base_url);
+        if let Some(timeout) = self.inner.timeout.request {
+            request = request.timeout(timeout);
+        }
+        request.send().await?;
+        Ok(())
+    }
+
+    /// 通用调用 (Generic call)
+    ///
+    /// 用于 SDK 尚未封装的 intent，自动携带 AccessToken。`result` 无法解析为 `T` 时，
+    /// 返回的 `AqaraError::Decode` 中保留原始 `result`，可通过 `raw_result()` 取出，无需重新请求。
+    /// For intents the SDK does not wrap yet; the access token is always sent. When
+    /// `result` cannot be decoded into `T`, the returned `AqaraError::Decode` keeps the
+    /// raw `result`, available through `raw_result()` without re-issuing the request.
+    ///
+    /// # Parameters 参数
+    /// - `intent`: 接口 intent / Intent name
+    /// - `data`: 请求数据 / Request data
+    pub async fn call<T: DeserializeOwned>(
+        &self,
+        intent: &str,
+        data: Value,
+    ) -> Result<AqaraResponse<T>, AqaraError> {
+        self.call_api(intent, data, true).await
+    }
+
+    /// 发送请求并解析通用响应结构 (Send a request and decode the common envelope)
+    ///
+    /// 业务码非 0 时返回 `AqaraError::Api`。
+    /// Returns `AqaraError::Api` when the business code is non-zero.
+    async fn call_api<T: DeserializeOwned>(
+        &self,
+        intent: &str,
+        data: Value,
+        include_access_token: bool,
+    ) -> Result<AqaraResponse<T>, AqaraError> {
+        let body = self
+            .send_api_request(intent, data, include_access_token)
+            .await?;
+        let response: AqaraResponse<Value> =
+            serde_json::from_str(&body).map_err(|source| AqaraError::Decode {
+                snippet: DecodeSnippet::from_body::<AqaraResponse<Value>>(&body, &source),
+                source,
+                raw: None,
+            })?;
+        if response.code != 0 {
+            return Err(AqaraError::Api {
+                code: response.code,
+                message: response.message,
+                request_id: response.request_id,
+            });
         }
+        let result = match response.result {
+            Some(raw) => match T::deserialize(&raw) {
+                Ok(result) => Some(result),
+                Err(source) => {
+                    return Err(AqaraError::Decode {
+                        source,
+                        snippet: DecodeSnippet::from_value::<T>(&raw),
+                        raw: Some(raw),
+                    })
+                }
+            },
+            None => None,
+        };
+        Ok(AqaraResponse {
+            code: response.code,
+            request_id: response.request_id,
+            message: response.message,
+            msg_details: response.msg_details,
+            result,
+            intent: intent.to_string(),
+        })
     }
 
     /// 获取授权码 (Get auth code)
@@ -137,11 +722,13 @@ impl AqaraClient {
         account: &str,
         account_type: i32,
         access_token_validity: Option<&str>,
-    ) -> Result<String, Error> {
+    ) -> Result<String, AqaraError> {
         let data = json!({
             "account": account,
             "accountType": account_type,
-            "accessTokenValidity": access_token_validity.unwrap_or("7d")
+            "accessTokenValidity": access_token_validity.unwrap_or(
+                self.inner.app_mode.unwrap_or(AppMode::User).default_token_validity()
+            )
         });
         self.send_api_request("config.auth.getAuthCode", data, true)
             .await
@@ -156,7 +743,7 @@ impl AqaraClient {
     ///
     /// # Returns
     /// 成功返回字符串 / Returns response string on success
-    pub async fn config_auth_refresh_token(&self, refresh_token: &str) -> Result<String, Error> {
+    pub async fn config_auth_refresh_token(&self, refresh_token: &str) -> Result<String, AqaraError> {
         let data = json!({
             "refreshToken": refresh_token
         });
@@ -173,7 +760,7 @@ impl AqaraClient {
     ///
     /// # Returns
     /// 成功返回字符串 / Returns response string on success
-    pub async fn query_device_sub_info(&self, gateway_did: &str) -> Result<String, Error> {
+    pub async fn query_device_sub_info(&self, gateway_did: &str) -> Result<String, AqaraError> {
         let data = json!({
             "did": gateway_did
         });
@@ -195,7 +782,7 @@ impl AqaraClient {
         &self,
         model: &str,
         resource_id: Option<&str>,
-    ) -> Result<String, Error> {
+    ) -> Result<String, AqaraError> {
         let mut data = json!({
             "model": model,
         });
@@ -220,7 +807,7 @@ impl AqaraClient {
         &self,
         position_id: &str,
         query_text: &str,
-    ) -> Result<String, Error> {
+    ) -> Result<String, AqaraError> {
         let data = json!({
             "positionId": position_id,
             "queryText": query_text
@@ -245,12 +832,8 @@ impl AqaraClient {
         parent_position_id: Option<&str>,
         page_num: Option<i32>,
         page_size: Option<i32>,
-    ) -> Result<String, Error> {
-        let data = json!({
-            "parentPositionId": parent_position_id.unwrap_or(""),
-            "pageNum": page_num.unwrap_or(1),
-            "pageSize": page_size.unwrap_or(30)
-        });
+    ) -> Result<String, AqaraError> {
+        let data = self.position_info_data(parent_position_id, page_num, page_size);
         self.send_api_request("query.position.info", data, true).await
     }
 
@@ -266,7 +849,8 @@ impl AqaraClient {
     pub async fn query_position_detail(
         &self,
         position_ids: &[&str],
-    ) -> Result<String, Error> {
+    ) -> Result<String, AqaraError> {
+        check_max_items("position_ids", position_ids.len(), POSITION_DETAIL_MAX_IDS)?;
         let data = json!({
             "positionIds": position_ids
         });
@@ -282,7 +866,7 @@ impl AqaraClient {
     ///
     /// # Returns
     /// 成功返回字符串 / Returns response string on success
-    pub async fn query_ota_firmware(&self, model: &str) -> Result<String, Error> {
+    pub async fn query_ota_firmware(&self, model: &str) -> Result<String, AqaraError> {
         let data = json!({
             "model": model
         });
@@ -298,7 +882,7 @@ impl AqaraClient {
     ///
     /// # Returns
     /// 成功返回字符串 / Returns response string on success
-    pub async fn write_ota_upgrade(&self, dids: &[&str]) -> Result<String, Error> {
+    pub async fn write_ota_upgrade(&self, dids: &[&str]) -> Result<String, AqaraError> {
         let data = json!({
             "dids": dids
         });
@@ -314,7 +898,7 @@ impl AqaraClient {
     ///
     /// # Returns
     /// 成功返回字符串 / Returns response string on success
-    pub async fn query_ota_upgrade(&self, dids: &[&str]) -> Result<String, Error> {
+    pub async fn query_ota_upgrade(&self, dids: &[&str]) -> Result<String, AqaraError> {
         let data = json!({
             "dids": dids
         });
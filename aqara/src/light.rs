@@ -0,0 +1,234 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{AqaraError, Device};
+
+/// 开关资源ID (0 关, 1 开) / On/off resource ID (0 off, 1 on)
+pub const LIGHT_POWER_RESOURCE_ID: &str = "4.1.85";
+/// 亮度资源ID (0-100%) / Brightness resource ID (0-100%)
+pub const LIGHT_BRIGHTNESS_RESOURCE_ID: &str = "14.1.85";
+/// 色温资源ID (mired) / Color temperature resource ID (mired)
+pub const LIGHT_COLOR_TEMPERATURE_RESOURCE_ID: &str = "14.2.85";
+/// 颜色资源ID (打包的 0xRRGGBB 整数) / Color resource ID (packed 0xRRGGBB integer)
+pub const LIGHT_COLOR_RESOURCE_ID: &str = "14.8.85";
+
+/// 色温下限 (mired，约 6500K) / Lowest color temperature in mired (about 6500K)
+const MIRED_MIN: u32 = 153;
+/// 色温上限 (mired，约 2700K) / Highest color temperature in mired (about 2700K)
+const MIRED_MAX: u32 = 370;
+
+/// RGB 颜色 (RGB color)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    /// 由色相/饱和度换算 (亮度取最大) (Convert from hue/saturation at full value)
+    ///
+    /// # Parameters 参数
+    /// - `hue`: 色相 (度，按 360 取模) / Hue (degrees, taken modulo 360)
+    /// - `saturation`: 饱和度 (0.0-1.0) / Saturation (0.0-1.0)
+    pub fn from_hs(hue: f64, saturation: f64) -> Self {
+        let hue = hue.rem_euclid(360.0) / 60.0;
+        let saturation = saturation.clamp(0.0, 1.0);
+        let x = 1.0 - (hue % 2.0 - 1.0).abs();
+        let (r, g, b) = match hue as u32 {
+            0 => (1.0, x, 0.0),
+            1 => (x, 1.0, 0.0),
+            2 => (0.0, 1.0, x),
+            3 => (0.0, x, 1.0),
+            4 => (x, 0.0, 1.0),
+            _ => (1.0, 0.0, x),
+        };
+        // 按饱和度向白色混合 / Blend towards white by saturation
+        let channel = |c: f64| ((1.0 - saturation + c * saturation) * 255.0).round() as u8;
+        Rgb {
+            r: channel(r),
+            g: channel(g),
+            b: channel(b),
+        }
+    }
+
+    /// 打包为资源值 / Pack into the resource value
+    pub fn to_packed(self) -> u32 {
+        (u32::from(self.r) << 16) | (u32::from(self.g) << 8) | u32::from(self.b)
+    }
+
+    /// 从资源值解包 / Unpack from the resource value
+    pub fn from_packed(packed: u32) -> Self {
+        Rgb {
+            r: (packed >> 16) as u8,
+            g: (packed >> 8) as u8,
+            b: packed as u8,
+        }
+    }
+}
+
+/// 开尔文转换为 mired (并限制在设备范围内) / Convert Kelvin to mired, clamped to the device range
+fn kelvin_to_mired(kelvin: u32) -> u32 {
+    (1_000_000 / kelvin.max(1)).clamp(MIRED_MIN, MIRED_MAX)
+}
+
+/// 灯光状态 (Light state)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LightState {
+    /// 是否开启 / Whether the light is on
+    pub on: Option<bool>,
+    /// 亮度 (0-100%) / Brightness (0-100%)
+    pub brightness: Option<u8>,
+    /// 色温 (K) / Color temperature (K)
+    pub color_temperature: Option<u32>,
+    /// 颜色 / Color
+    pub color: Option<Rgb>,
+}
+
+/// 灯光控制 (Light control)
+///
+/// 通过 `Device::light` 获取，适用于调光、CCT 与 RGB 灯。
+/// Obtained through `Device::light`; works for dimmable, CCT and RGB lights.
+pub struct LightControl<'a> {
+    device: &'a Device,
+}
+
+impl Device {
+    /// 灯光控制 / Light control
+    pub fn light(&self) -> LightControl<'_> {
+        LightControl { device: self }
+    }
+}
+
+impl LightControl<'_> {
+    /// 开关 / Turn on or off
+    ///
+    /// intent: write.resource.device
+    pub async fn set_on(&self, on: bool) -> Result<(), AqaraError> {
+        let value = if on { "1" } else { "0" };
+        self.device.write(&[(LIGHT_POWER_RESOURCE_ID, value)]).await
+    }
+
+    /// 设置亮度 (Set brightness)
+    ///
+    /// intent: write.resource.device
+    ///
+    /// # Parameters 参数
+    /// - `percent`: 亮度 (0-100，超出按 100 处理) / Brightness (0-100, larger values clamp to 100)
+    pub async fn set_brightness(&self, percent: u8) -> Result<(), AqaraError> {
+        let value = percent.min(100).to_string();
+        self.device
+            .write(&[(LIGHT_BRIGHTNESS_RESOURCE_ID, &value)])
+            .await
+    }
+
+    /// 设置色温 (Set color temperature)
+    ///
+    /// intent: write.resource.device
+    ///
+    /// # Parameters 参数
+    /// - `kelvin`: 色温 (K，限制在约 2700K-6500K) / Color temperature (K, clamped to about 2700K-6500K)
+    pub async fn set_color_temperature(&self, kelvin: u32) -> Result<(), AqaraError> {
+        let value = kelvin_to_mired(kelvin).to_string();
+        self.device
+            .write(&[(LIGHT_COLOR_TEMPERATURE_RESOURCE_ID, &value)])
+            .await
+    }
+
+    /// 设置 RGB 颜色 / Set an RGB color
+    ///
+    /// intent: write.resource.device
+    pub async fn set_rgb(&self, color: Rgb) -> Result<(), AqaraError> {
+        let value = color.to_packed().to_string();
+        self.device
+            .write(&[(LIGHT_COLOR_RESOURCE_ID, &value)])
+            .await
+    }
+
+    /// 设置色相/饱和度 (Set hue/saturation)
+    ///
+    /// intent: write.resource.device
+    ///
+    /// # Parameters 参数
+    /// - `hue`: 色相 (度) / Hue (degrees)
+    /// - `saturation`: 饱和度 (0.0-1.0) / Saturation (0.0-1.0)
+    pub async fn set_hs(&self, hue: f64, saturation: f64) -> Result<(), AqaraError> {
+        self.set_rgb(Rgb::from_hs(hue, saturation)).await
+    }
+
+    /// 读取灯光状态 (Read the light state)
+    ///
+    /// intent: query.resource.value
+    ///
+    /// # Returns
+    /// 成功返回灯光状态，未上报的字段为 `None` / Returns the state; unreported fields are `None`
+    pub async fn state(&self) -> Result<LightState, AqaraError> {
+        let values = self
+            .device
+            .read(&[
+                LIGHT_POWER_RESOURCE_ID,
+                LIGHT_BRIGHTNESS_RESOURCE_ID,
+                LIGHT_COLOR_TEMPERATURE_RESOURCE_ID,
+                LIGHT_COLOR_RESOURCE_ID,
+            ])
+            .await?;
+        let get = |resource_id: &str| values.get(resource_id).and_then(|v| v.parse::<u32>().ok());
+        Ok(LightState {
+            on: get(LIGHT_POWER_RESOURCE_ID).map(|v| v == 1),
+            brightness: get(LIGHT_BRIGHTNESS_RESOURCE_ID).map(|v| v.min(100) as u8),
+            color_temperature: get(LIGHT_COLOR_TEMPERATURE_RESOURCE_ID)
+                .filter(|mired| *mired > 0)
+                .map(|mired| 1_000_000 / mired),
+            color: get(LIGHT_COLOR_RESOURCE_ID).map(Rgb::from_packed),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgb(r: u8, g: u8, b: u8) -> Rgb {
+        Rgb { r, g, b }
+    }
+
+    #[test]
+    fn from_hs_maps_primary_and_secondary_hues() {
+        assert_eq!(Rgb::from_hs(0.0, 1.0), rgb(255, 0, 0));
+        assert_eq!(Rgb::from_hs(60.0, 1.0), rgb(255, 255, 0));
+        assert_eq!(Rgb::from_hs(120.0, 1.0), rgb(0, 255, 0));
+        assert_eq!(Rgb::from_hs(240.0, 1.0), rgb(0, 0, 255));
+        assert_eq!(Rgb::from_hs(30.0, 1.0), rgb(255, 128, 0));
+    }
+
+    #[test]
+    fn from_hs_wraps_hue_and_clamps_saturation() {
+        assert_eq!(Rgb::from_hs(360.0, 1.0), rgb(255, 0, 0));
+        assert_eq!(Rgb::from_hs(-120.0, 1.0), rgb(0, 0, 255));
+        assert_eq!(Rgb::from_hs(0.0, 2.0), rgb(255, 0, 0));
+    }
+
+    #[test]
+    fn from_hs_blends_towards_white() {
+        assert_eq!(Rgb::from_hs(200.0, 0.0), rgb(255, 255, 255));
+        assert_eq!(Rgb::from_hs(0.0, 0.5), rgb(255, 128, 128));
+    }
+
+    #[test]
+    fn packed_round_trip() {
+        let color = rgb(0x12, 0x34, 0x56);
+        assert_eq!(color.to_packed(), 0x123456);
+        assert_eq!(Rgb::from_packed(0x123456), color);
+        // 高位被忽略 / High bits are ignored
+        assert_eq!(Rgb::from_packed(0xff123456), color);
+    }
+
+    #[test]
+    fn kelvin_to_mired_clamps_to_device_range() {
+        assert_eq!(kelvin_to_mired(4000), 250);
+        assert_eq!(kelvin_to_mired(2700), MIRED_MAX);
+        assert_eq!(kelvin_to_mired(10_000), MIRED_MIN);
+        assert_eq!(kelvin_to_mired(1000), MIRED_MAX);
+        assert_eq!(kelvin_to_mired(0), MIRED_MAX);
+    }
+}
@@ -0,0 +1,131 @@
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use serde_json::{json, Value};
+
+use crate::util::value_to_string;
+use crate::{AqaraClient, AqaraError};
+
+/// 批量启停中单个自动化的结果 (Per-linkage result of a bulk toggle)
+#[derive(Debug)]
+pub struct LinkageToggle {
+    /// 自动化ID / Linkage ID
+    pub linkage_id: String,
+    /// 自动化名称 / Linkage name
+    pub name: String,
+    /// 启停结果 / Toggle result
+    pub result: Result<(), AqaraError>,
+}
+
+impl AqaraClient {
+    /// 按位置查询自动化 (List linkages by position)
+    ///
+    /// intent: query.linkage.listByPositionId
+    ///
+    /// # Parameters 参数
+    /// - `position_id`: 位置ID / Position ID
+    /// - `page_num`: 页码 (可选) / Page number (optional)
+    /// - `page_size`: 每页数量 (可选) / Page size (optional)
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn query_linkage_list_by_position(
+        &self,
+        position_id: &str,
+        page_num: Option<i32>,
+        page_size: Option<i32>,
+    ) -> Result<String, AqaraError> {
+        let data = self.linkage_list_data(position_id, page_num, page_size);
+        self.send_api_request("query.linkage.listByPositionId", data, true)
+            .await
+    }
+
+    /// 启用或停用自动化 (Enable or disable a linkage)
+    ///
+    /// intent: config.linkage.enable
+    ///
+    /// # Parameters 参数
+    /// - `linkage_id`: 自动化ID / Linkage ID
+    /// - `enable`: 是否启用 / Whether to enable
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn config_linkage_enable(
+        &self,
+        linkage_id: &str,
+        enable: bool,
+    ) -> Result<String, AqaraError> {
+        let data = linkage_enable_data(linkage_id, enable);
+        self.send_api_request("config.linkage.enable", data, true)
+            .await
+    }
+
+    /// 按位置批量启停自动化 (Enable or disable every linkage under a position)
+    ///
+    /// intent: query.linkage.listByPositionId, config.linkage.enable
+    ///
+    /// 分页列出位置下的全部自动化，最多 `concurrency` 个同时启停；单个失败不影响其他，
+    /// 结果按列表顺序逐个返回。适用于 "度假模式" 等场景。
+    /// Pages through every linkage under the position and toggles them with at
+    /// most `concurrency` in flight; one failure does not stop the others, and
+    /// results come back per linkage in list order. Useful for "vacation mode".
+    ///
+    /// # Parameters 参数
+    /// - `position_id`: 位置ID / Position ID
+    /// - `enable`: 是否启用 / Whether to enable
+    /// - `concurrency`: 并发数 (0 视为 1) / Concurrent toggles (0 is treated as 1)
+    ///
+    /// # Returns
+    /// 成功列出时返回每个自动化的结果 / Returns a result per linkage once listing succeeds
+    pub async fn set_linkages_enabled_by_position(
+        &self,
+        position_id: &str,
+        enable: bool,
+        concurrency: usize,
+    ) -> Result<Vec<LinkageToggle>, AqaraError> {
+        let linkages: Vec<Value> = self
+            .query_linkage_list_all(position_id)
+            .try_collect()
+            .await?;
+
+        let results = stream::iter(linkages)
+            .map(|linkage| async move {
+                let linkage_id = value_to_string(linkage.get("linkageId"));
+                let result = self
+                    .call_api::<Value>(
+                        "config.linkage.enable",
+                        linkage_enable_data(&linkage_id, enable),
+                        true,
+                    )
+                    .await
+                    .map(|_| ());
+                LinkageToggle {
+                    linkage_id,
+                    name: value_to_string(linkage.get("name")),
+                    result,
+                }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+        Ok(results)
+    }
+
+    fn linkage_list_data(
+        &self,
+        position_id: &str,
+        page_num: Option<i32>,
+        page_size: Option<i32>,
+    ) -> Value {
+        json!({
+            "positionId": position_id,
+            "pageNum": page_num.unwrap_or(1),
+            "pageSize": page_size.or(self.inner.defaults.page_size).unwrap_or(50)
+        })
+    }
+}
+
+fn linkage_enable_data(linkage_id: &str, enable: bool) -> Value {
+    json!({
+        "linkageId": linkage_id,
+        "enable": if enable { 1 } else { 0 }
+    })
+}
@@ -0,0 +1,44 @@
+use serde_json::Value;
+
+use crate::AqaraError;
+
+/// 待发送的请求 (Outgoing request seen by middleware)
+#[derive(Debug, Clone)]
+pub struct MiddlewareRequest {
+    /// intent (只读) / Intent (read-only)
+    pub(crate) intent: String,
+    /// 请求参数，可修改 / Request params, may be modified
+    pub data: Value,
+    /// 附加请求头 / Extra headers to send
+    pub headers: Vec<(String, String)>,
+}
+
+impl MiddlewareRequest {
+    /// intent / Intent
+    pub fn intent(&self) -> &str {
+        &self.intent
+    }
+}
+
+/// 请求中间件 (Request middleware)
+///
+/// 每次 HTTP 尝试前后同步调用 (重试时每次都会调用)，实现应尽快返回。
+/// `before_request` 按安装顺序调用，返回 `Some(body)` 时跳过后续中间件与网络请求，
+/// 直接以该响应体作为结果 (例如自定义缓存)；`after_response` 按相反顺序调用。
+/// Invoked synchronously around every HTTP attempt (including retries), so
+/// implementations should return quickly. `before_request` runs in install order,
+/// and returning `Some(body)` skips the remaining middleware and the network,
+/// using that body as the response (e.g. for custom caching); `after_response`
+/// runs in reverse order.
+pub trait Middleware: Send + Sync {
+    /// 发送前调用，可修改参数或附加请求头 / Called before sending; may edit params or add headers
+    fn before_request(&self, request: &mut MiddlewareRequest) -> Option<String> {
+        let _ = request;
+        None
+    }
+
+    /// 收到响应 (或失败) 后调用 / Called once the response (or failure) is in
+    fn after_response(&self, request: &MiddlewareRequest, result: &Result<String, AqaraError>) {
+        let _ = (request, result);
+    }
+}
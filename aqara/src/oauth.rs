@@ -0,0 +1,154 @@
+//! OAuth2 授权码流程 (OAuth2 authorization-code flow)
+//!
+//! 除基于 intent 的授权外，Aqara 还提供 OAuth2 页面授权：构造授权地址引导用户登录，
+//! 回调拿到 code 后换取令牌，并可刷新令牌。产生的 `TokenBundle` 与主客户端通用。
+//! Besides intent-based auth, Aqara offers an OAuth2 page flow: build the
+//! authorize URL for the user to sign in, exchange the code from the callback for
+//! tokens, and refresh them later. The resulting `TokenBundle` is the same one the
+//! main client uses.
+
+use reqwest::{Client, Url};
+use serde_json::Value;
+
+use crate::util::value_to_string;
+use crate::{AqaraError, Region, TokenBundle};
+
+/// OAuth2 客户端 (OAuth2 client)
+#[derive(Debug, Clone)]
+pub struct OAuthClient {
+    http: Client,
+    base_url: String,
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+}
+
+impl OAuthClient {
+    /// 创建 (Create)
+    ///
+    /// # Parameters 参数
+    /// - `region`: 服务区域 / Service region
+    /// - `client_id`: 应用ID (Appid) / App ID (Appid)
+    /// - `client_secret`: 应用密钥 (AppKey) / App key (AppKey)
+    /// - `redirect_uri`: 授权回调地址 (须与开放平台登记的一致) / Callback URL (must match the one registered on the platform)
+    pub fn new(region: Region, client_id: &str, client_secret: &str, redirect_uri: &str) -> Self {
+        let base_url = region.base_url().trim_end_matches("/api").to_string();
+        OAuthClient {
+            http: Client::new(),
+            base_url,
+            client_id: client_id.to_string(),
+            client_secret: client_secret.to_string(),
+            redirect_uri: redirect_uri.to_string(),
+        }
+    }
+
+    /// 覆盖 OAuth 地址前缀 (例如代理或测试服务) / Override the OAuth URL prefix (e.g. for a proxy or test server)
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// 使用自定义 HTTP 客户端 / Use a custom HTTP client
+    pub fn with_http_client(mut self, http: Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// 构造授权地址 (Build the authorize URL)
+    ///
+    /// # Parameters 参数
+    /// - `state`: 回调时原样带回的防 CSRF 值 / Anti-CSRF value echoed back in the callback
+    pub fn authorize_url(&self, state: &str) -> Result<String, AqaraError> {
+        let url = Url::parse_with_params(
+            &format!("{}/authorize", self.base_url),
+            [
+                ("client_id", self.client_id.as_str()),
+                ("response_type", "code"),
+                ("redirect_uri", self.redirect_uri.as_str()),
+                ("state", state),
+            ],
+        )
+        .map_err(|e| AqaraError::InvalidParams(vec![format!("base_url: {}", e)]))?;
+        Ok(url.into())
+    }
+
+    /// 用授权码换取令牌 (Exchange the code for tokens)
+    ///
+    /// # Parameters 参数
+    /// - `code`: 回调中的授权码 / Authorization code from the callback
+    pub async fn exchange_code(&self, code: &str) -> Result<TokenBundle, AqaraError> {
+        self.request_token(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", &self.redirect_uri),
+        ])
+        .await
+    }
+
+    /// 刷新令牌 (Refresh tokens)
+    ///
+    /// # Parameters 参数
+    /// - `refresh_token`: 刷新令牌 / Refresh token
+    pub async fn refresh(&self, refresh_token: &str) -> Result<TokenBundle, AqaraError> {
+        let mut bundle = self
+            .request_token(&[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token),
+            ])
+            .await?;
+        if bundle.refresh_token.is_empty() {
+            bundle.refresh_token = refresh_token.to_string();
+        }
+        Ok(bundle)
+    }
+
+    async fn request_token(&self, params: &[(&str, &str)]) -> Result<TokenBundle, AqaraError> {
+        let mut form = vec![
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+        ];
+        form.extend_from_slice(params);
+        let body: Value = self
+            .http
+            .post(format!("{}/access_token", self.base_url))
+            .form(&form)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        parse_token_response(body)
+    }
+}
+
+// 令牌接口可能返回 Aqara 通用响应或 OAuth2 标准字段 (下划线命名)
+// The token endpoint may answer with the Aqara envelope or standard OAuth2 (snake_case) fields
+fn parse_token_response(body: Value) -> Result<TokenBundle, AqaraError> {
+    let code = body.get("code").and_then(Value::as_i64).unwrap_or(0);
+    if code != 0 {
+        return Err(AqaraError::Api {
+            code: code as i32,
+            message: value_to_string(body.get("message")),
+            request_id: value_to_string(body.get("requestId")),
+        });
+    }
+    let result = match body.get("result") {
+        Some(result) if result.is_object() => result,
+        _ => &body,
+    };
+    let field =
+        |snake: &str, camel: &str| value_to_string(result.get(snake).or_else(|| result.get(camel)));
+    let bundle = TokenBundle {
+        access_token: field("access_token", "accessToken"),
+        refresh_token: field("refresh_token", "refreshToken"),
+        expires_in: field("expires_in", "expiresIn").parse().ok(),
+        open_id: field("openid", "openId"),
+    };
+    if bundle.access_token.is_empty() {
+        return Err(AqaraError::MissingResult {
+            intent: "oauth.access_token".to_string(),
+            request_id: value_to_string(body.get("requestId")),
+        });
+    }
+    Ok(bundle)
+}
@@ -0,0 +1,220 @@
+//! 批量固件升级 (Fleet firmware rollout)
+//!
+//! 按批次升级大量设备，失败率超过阈值时中止，进度可序列化以便中断后继续。
+//! Upgrades large fleets in waves, aborts once the failure rate crosses a
+//! threshold, and exposes serializable progress so an interrupted rollout can
+//! resume.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::{info, warn};
+
+use crate::util::value_to_string;
+use crate::{AqaraClient, AqaraError};
+
+/// 升级状态: 升级中 / Upgrade status: in progress
+const STATUS_UPGRADING: i64 = 1;
+/// 升级状态: 成功 / Upgrade status: succeeded
+const STATUS_SUCCEEDED: i64 = 2;
+/// 升级状态: 失败 / Upgrade status: failed
+const STATUS_FAILED: i64 = 3;
+
+/// 批量升级配置 (Rollout configuration)
+#[derive(Debug, Clone)]
+pub struct RolloutConfig {
+    /// 每批设备数 / Devices per wave
+    pub batch_size: usize,
+    /// 同时升级的最大设备数 / Max devices upgrading at once
+    pub max_concurrent: usize,
+    /// 失败率超过该值 (0.0-1.0) 时中止 / Abort once the failure rate exceeds this (0.0-1.0)
+    pub max_failure_rate: f64,
+    /// 升级状态轮询间隔 / Upgrade status polling interval
+    pub poll_interval: Duration,
+    /// 单个设备升级的超时时间 / Timeout for a single device upgrade
+    pub upgrade_timeout: Duration,
+}
+
+impl Default for RolloutConfig {
+    fn default() -> Self {
+        RolloutConfig {
+            batch_size: 50,
+            max_concurrent: 10,
+            max_failure_rate: 0.1,
+            poll_interval: Duration::from_secs(15),
+            upgrade_timeout: Duration::from_secs(30 * 60),
+        }
+    }
+}
+
+/// 单个设备的升级结果 (Per-device upgrade outcome)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UpgradeOutcome {
+    /// 升级成功 / Upgrade succeeded
+    Upgraded,
+    /// 升级失败 (附原因) / Upgrade failed (with the reason)
+    Failed(String),
+}
+
+/// 批量升级进度 (Rollout progress)
+///
+/// 每批结束后回调一次，调用方可自行持久化并在中断后通过 `FleetRollout::resume` 继续。
+/// Reported after every wave; callers persist it themselves and pass it to
+/// `FleetRollout::resume` after an interruption.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RolloutProgress {
+    /// 已结束的设备 / Devices that have finished
+    pub completed: BTreeMap<String, UpgradeOutcome>,
+    /// 是否因失败率过高而中止 / Whether the rollout was aborted for its failure rate
+    pub aborted: bool,
+}
+
+impl RolloutProgress {
+    /// 失败的设备数 / Number of failed devices
+    pub fn failed(&self) -> usize {
+        self.completed
+            .values()
+            .filter(|outcome| matches!(outcome, UpgradeOutcome::Failed(_)))
+            .count()
+    }
+
+    /// 当前失败率 / Current failure rate
+    pub fn failure_rate(&self) -> f64 {
+        if self.completed.is_empty() {
+            0.0
+        } else {
+            self.failed() as f64 / self.completed.len() as f64
+        }
+    }
+}
+
+/// 批量固件升级 (Fleet firmware rollout)
+pub struct FleetRollout {
+    client: AqaraClient,
+    dids: Vec<String>,
+    config: RolloutConfig,
+    progress: RolloutProgress,
+}
+
+impl FleetRollout {
+    /// 创建批量升级 / Create a rollout
+    pub fn new(client: AqaraClient, dids: Vec<String>) -> Self {
+        FleetRollout {
+            client,
+            dids,
+            config: RolloutConfig::default(),
+            progress: RolloutProgress::default(),
+        }
+    }
+
+    /// 设置配置 / Set the configuration
+    pub fn config(mut self, config: RolloutConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// 从已保存的进度继续 (已结束的设备不再升级) / Resume from saved progress (finished devices are skipped)
+    pub fn resume(mut self, progress: RolloutProgress) -> Self {
+        self.progress = progress;
+        self.progress.aborted = false;
+        self
+    }
+
+    /// 执行批量升级 (Run the rollout)
+    ///
+    /// intent: write.ota.upgrade, query.ota.upgrade
+    ///
+    /// 每批内最多 `max_concurrent` 个设备同时升级，轮询直至成功、失败或超时；
+    /// 每批结束后调用 `on_progress`，失败率超过阈值则中止。
+    /// Within each wave at most `max_concurrent` devices upgrade at once and are
+    /// polled until they succeed, fail or time out; `on_progress` runs after every
+    /// wave and the rollout aborts once the failure rate crosses the threshold.
+    ///
+    /// # Parameters 参数
+    /// - `on_progress`: 进度回调 (用于持久化) / Progress callback (for persistence)
+    ///
+    /// # Returns
+    /// 返回最终进度；请求失败时返回错误，已完成的进度已通过回调报告
+    /// Returns the final progress; request failures return an error, with progress
+    /// so far already reported through the callback
+    pub async fn run(
+        mut self,
+        mut on_progress: impl FnMut(&RolloutProgress),
+    ) -> Result<RolloutProgress, AqaraError> {
+        let pending: Vec<String> = self
+            .dids
+            .iter()
+            .filter(|did| !self.progress.completed.contains_key(*did))
+            .cloned()
+            .collect();
+        for wave in pending.chunks(self.config.batch_size.max(1)) {
+            for group in wave.chunks(self.config.max_concurrent.max(1)) {
+                self.upgrade_group(group).await?;
+            }
+            on_progress(&self.progress);
+            let rate = self.progress.failure_rate();
+            info!(
+                "Rollout wave done: {}/{} devices finished, failure rate {:.1}%",
+                self.progress.completed.len(),
+                self.dids.len(),
+                rate * 100.0
+            );
+            if rate > self.config.max_failure_rate {
+                warn!(
+                    "Aborting rollout: failure rate {:.1}% too high",
+                    rate * 100.0
+                );
+                self.progress.aborted = true;
+                on_progress(&self.progress);
+                break;
+            }
+        }
+        Ok(self.progress)
+    }
+
+    // 升级一组设备并轮询至全部结束 / Upgrade a group and poll until all finish
+    async fn upgrade_group(&mut self, group: &[String]) -> Result<(), AqaraError> {
+        let dids: Vec<&str> = group.iter().map(String::as_str).collect();
+        self.client
+            .call::<Value>("write.ota.upgrade", json!({ "dids": dids }))
+            .await?;
+        let started = Instant::now();
+        let mut upgrading = dids;
+        while !upgrading.is_empty() {
+            if started.elapsed() >= self.config.upgrade_timeout {
+                for did in upgrading.drain(..) {
+                    self.progress.completed.insert(
+                        did.to_string(),
+                        UpgradeOutcome::Failed("upgrade timed out".to_string()),
+                    );
+                }
+                break;
+            }
+            tokio::time::sleep(self.config.poll_interval).await;
+            let result = self
+                .client
+                .call::<Value>("query.ota.upgrade", json!({ "dids": upgrading }))
+                .await?
+                .result
+                .unwrap_or(Value::Null);
+            for status in result.as_array().into_iter().flatten() {
+                let did = value_to_string(status.get("did"));
+                let code = status
+                    .get("status")
+                    .or_else(|| status.get("upgradeStatus"))
+                    .and_then(|v| v.as_i64().or_else(|| v.as_str()?.parse().ok()))
+                    .unwrap_or(STATUS_UPGRADING);
+                let outcome = match code {
+                    STATUS_SUCCEEDED => UpgradeOutcome::Upgraded,
+                    STATUS_FAILED => UpgradeOutcome::Failed(value_to_string(status.get("message"))),
+                    _ => continue,
+                };
+                self.progress.completed.insert(did, outcome);
+            }
+            upgrading.retain(|did| !self.progress.completed.contains_key(*did));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+//! 分页列表 (Paginated lists)
+//!
+//! 将按 `pageNum`/`pageSize` 分页的列表接口包装为流，自动拉取后续分页直到 `totalCount` 用尽。
+//! Wraps `pageNum`/`pageSize` list intents as streams that fetch subsequent pages
+//! until `totalCount` is exhausted.
+
+use futures_util::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::{AqaraClient, AqaraError, DeviceInfo, PositionInfo};
+
+/// 未设置 `ClientDefaults::page_size` 时列表接口的分页大小
+/// Page size used for list intents when `ClientDefaults::page_size` is unset
+const LIST_PAGE_SIZE: i32 = 100;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Page<T> {
+    #[serde(default = "Vec::new")]
+    data: Vec<T>,
+    #[serde(default)]
+    total_count: u64,
+}
+
+impl AqaraClient {
+    /// 全部设备信息 (Every device, page by page)
+    ///
+    /// intent: query.device.info
+    ///
+    /// # Parameters 参数
+    /// - `position_id`: 位置ID (可选) / Position ID (optional)
+    ///
+    /// # Returns
+    /// 逐个返回设备的流 / A stream yielding each device
+    pub fn query_device_info_all(
+        &self,
+        position_id: Option<&str>,
+    ) -> BoxStream<'static, Result<DeviceInfo, AqaraError>> {
+        let data = self.device_info_data(None, position_id, None, None);
+        self.paginate("query.device.info", data)
+    }
+
+    /// 全部子位置 (Every child position, page by page)
+    ///
+    /// intent: query.position.info
+    ///
+    /// # Parameters 参数
+    /// - `parent_position_id`: 父位置ID (可选) / Parent position ID (optional)
+    ///
+    /// # Returns
+    /// 逐个返回位置的流 / A stream yielding each position
+    pub fn query_position_info_all(
+        &self,
+        parent_position_id: Option<&str>,
+    ) -> BoxStream<'static, Result<PositionInfo, AqaraError>> {
+        let data = self.position_info_data(parent_position_id, None, None);
+        self.paginate("query.position.info", data)
+    }
+
+    /// 位置下的全部场景 (Every scene under a position, page by page)
+    ///
+    /// intent: query.scene.listByPositionId
+    ///
+    /// # Parameters 参数
+    /// - `position_id`: 位置ID / Position ID
+    ///
+    /// # Returns
+    /// 逐个返回场景的流 / A stream yielding each scene
+    pub fn query_scene_list_all(
+        &self,
+        position_id: &str,
+    ) -> BoxStream<'static, Result<Value, AqaraError>> {
+        self.paginate(
+            "query.scene.listByPositionId",
+            json!({ "positionId": position_id }),
+        )
+    }
+
+    /// 位置下的全部自动化 (Every linkage under a position, page by page)
+    ///
+    /// intent: query.linkage.listByPositionId
+    ///
+    /// # Parameters 参数
+    /// - `position_id`: 位置ID / Position ID
+    ///
+    /// # Returns
+    /// 逐个返回自动化的流 / A stream yielding each linkage
+    pub fn query_linkage_list_all(
+        &self,
+        position_id: &str,
+    ) -> BoxStream<'static, Result<Value, AqaraError>> {
+        self.paginate(
+            "query.linkage.listByPositionId",
+            json!({ "positionId": position_id }),
+        )
+    }
+
+    // 拉取列表接口的全部分页 / Fetch every page of a list intent
+    pub(crate) async fn fetch_all_pages(
+        &self,
+        intent: &str,
+        data: Value,
+    ) -> Result<Vec<Value>, AqaraError> {
+        self.paginate(intent, data).try_collect().await
+    }
+
+    // 按需拉取分页并逐项返回 / Fetch pages on demand and yield their items
+    fn paginate<T: DeserializeOwned + Send + 'static>(
+        &self,
+        intent: &str,
+        data: Value,
+    ) -> BoxStream<'static, Result<T, AqaraError>> {
+        let client = self.clone();
+        let intent = intent.to_string();
+        let page_size = self.inner.defaults.page_size.unwrap_or(LIST_PAGE_SIZE);
+        // (下一页页码, 已拉取数量, 是否结束) / (next page number, items fetched, finished)
+        stream::try_unfold((1, 0, false), move |(page_num, fetched, done)| {
+            let client = client.clone();
+            let intent = intent.clone();
+            let mut data = data.clone();
+            async move {
+                if done {
+                    return Ok(None);
+                }
+                data["pageNum"] = json!(page_num);
+                data["pageSize"] = json!(page_size);
+                let page: Page<T> = client
+                    .call_api::<Page<T>>(&intent, data, true)
+                    .await?
+                    .result
+                    .unwrap_or(Page {
+                        data: Vec::new(),
+                        total_count: 0,
+                    });
+                let fetched = fetched + page.data.len() as u64;
+                let done = page.data.is_empty() || fetched >= page.total_count;
+                Ok::<_, AqaraError>(Some((page.data, (page_num + 1, fetched, done))))
+            }
+        })
+        .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+    }
+}
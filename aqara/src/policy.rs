@@ -0,0 +1,79 @@
+use crate::util::IntentMap;
+use crate::AqaraError;
+
+/// intent 访问策略 (Intent access policy)
+///
+/// 规则按 `IntentMap` 的方式匹配 (完整匹配优先，其次最长前缀)。没有规则匹配时，
+/// 若配置过允许规则则拒绝，否则放行。
+/// Rules match the way `IntentMap` does (exact matches win, then the longest
+/// prefix). When no rule matches, the intent is denied if any allow rule is
+/// configured and allowed otherwise.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct IntentPolicy {
+    rules: IntentMap<bool>,
+}
+
+impl IntentPolicy {
+    pub(crate) fn insert(&mut self, pattern: &str, allow: bool) {
+        self.rules.insert(pattern, allow);
+    }
+
+    pub(crate) fn check(&self, intent: &str) -> Result<(), AqaraError> {
+        let allowed = match self.rules.get(intent) {
+            Some(allow) => *allow,
+            None => !self.rules.iter().any(|(_, allow)| *allow),
+        };
+        if allowed {
+            Ok(())
+        } else {
+            Err(AqaraError::PolicyDenied(intent.to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(rules: &[(&str, bool)]) -> IntentPolicy {
+        let mut policy = IntentPolicy::default();
+        for (pattern, allow) in rules {
+            policy.insert(pattern, *allow);
+        }
+        policy
+    }
+
+    #[test]
+    fn empty_policy_allows_everything() {
+        assert!(policy(&[]).check("write.device.unbind").is_ok());
+    }
+
+    #[test]
+    fn deny_rules_alone_allow_unmatched_intents() {
+        let policy = policy(&[("write.*", false)]);
+        assert!(policy.check("query.device.info").is_ok());
+        assert!(matches!(
+            policy.check("write.device.unbind"),
+            Err(AqaraError::PolicyDenied(intent)) if intent == "write.device.unbind"
+        ));
+    }
+
+    #[test]
+    fn allow_rules_deny_unmatched_intents() {
+        let policy = policy(&[("query.*", true)]);
+        assert!(policy.check("query.device.info").is_ok());
+        assert!(policy.check("config.scene.create").is_err());
+    }
+
+    #[test]
+    fn exact_rules_override_prefix_rules() {
+        let policy = policy(&[
+            ("query.*", true),
+            ("query.device.*", false),
+            ("query.device.info", true),
+        ]);
+        assert!(policy.check("query.device.info").is_ok());
+        assert!(policy.check("query.device.subInfo").is_err());
+        assert!(policy.check("query.position.info").is_ok());
+    }
+}
@@ -0,0 +1,272 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use futures_util::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::util::{check_max_items, require_id};
+use crate::{AqaraClient, AqaraError, AqaraResponse, PartialResult};
+
+/// 单次位置详情查询的最大数量 / Max position ids per detail query
+pub const POSITION_DETAIL_MAX_IDS: usize = 50;
+
+/// 位置模板 (Position template)
+///
+/// 描述一棵待创建的位置树，例如酒店的楼层/房间结构。
+/// Describes a position tree to create, e.g. the floor/room layout of a hotel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionTemplate {
+    /// 位置名称 / Position name
+    pub name: String,
+    /// 位置描述 (可选) / Position description (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 子位置 / Child positions
+    #[serde(default)]
+    pub children: Vec<PositionTemplate>,
+}
+
+/// 已创建的位置 (Created position)
+///
+/// 与模板结构一一对应，携带服务端返回的位置ID。
+/// Mirrors the template structure and carries the position ids returned by the server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedPosition {
+    /// 位置ID / Position ID
+    pub position_id: String,
+    /// 位置名称 / Position name
+    pub name: String,
+    /// 子位置 / Child positions
+    pub children: Vec<CreatedPosition>,
+}
+
+/// 位置信息 (Position info)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionInfo {
+    /// 位置ID / Position ID
+    pub position_id: String,
+    /// 位置名称 / Position name
+    #[serde(default)]
+    pub position_name: String,
+    /// 父位置ID (顶层为空) / Parent position ID (empty at the top level)
+    #[serde(default)]
+    pub parent_position_id: String,
+    /// 位置描述 / Position description
+    #[serde(default)]
+    pub description: String,
+    /// 时区 / Time zone
+    #[serde(default)]
+    pub time_zone: String,
+    /// 创建时间 (毫秒) / Creation time (milliseconds)
+    #[serde(default)]
+    pub create_time: i64,
+}
+
+/// 位置信息分页 (Position info page)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionPage {
+    /// 当前页位置 / Positions on this page
+    #[serde(default)]
+    pub data: Vec<PositionInfo>,
+    /// 位置总数 / Total number of positions
+    #[serde(default)]
+    pub total_count: u64,
+}
+
+/// 位置详情 (Position detail)
+///
+/// `query.position.detail` 与 `query.position.info` 返回相同字段。
+/// `query.position.detail` returns the same fields as `query.position.info`.
+pub type PositionDetail = PositionInfo;
+
+type CreateTreeFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<CreatedPosition, AqaraError>> + Send + 'a>>;
+
+impl AqaraClient {
+    /// 查询位置信息 (类型化) (Query position info, typed)
+    ///
+    /// intent: query.position.info
+    ///
+    /// 参数同 `query_position_info`。
+    /// Takes the same parameters as `query_position_info`.
+    ///
+    /// # Returns
+    /// 成功返回位置信息分页 / Returns a position info page on success
+    pub async fn query_position_info_typed(
+        &self,
+        parent_position_id: Option<&str>,
+        page_num: Option<i32>,
+        page_size: Option<i32>,
+    ) -> Result<AqaraResponse<PositionPage>, AqaraError> {
+        let data = self.position_info_data(parent_position_id, page_num, page_size);
+        self.call_api("query.position.info", data, true).await
+    }
+
+    /// 查询位置详情 (类型化) (Query position details, typed)
+    ///
+    /// intent: query.position.detail
+    ///
+    /// # Parameters 参数
+    /// - `position_ids`: 位置ID列表 (最多50个) / Up to 50 position IDs
+    ///
+    /// # Returns
+    /// 成功返回位置详情列表 / Returns the position details on success
+    pub async fn query_position_detail_typed(
+        &self,
+        position_ids: &[&str],
+    ) -> Result<AqaraResponse<Vec<PositionDetail>>, AqaraError> {
+        check_max_items("position_ids", position_ids.len(), POSITION_DETAIL_MAX_IDS)?;
+        self.call_api(
+            "query.position.detail",
+            json!({ "positionIds": position_ids }),
+            true,
+        )
+        .await
+    }
+
+    /// 分批查询全部位置详情 (Query every position detail in chunks)
+    ///
+    /// intent: query.position.detail
+    ///
+    /// 按 `POSITION_DETAIL_MAX_IDS` 拆分请求，最多 `concurrency` 批同时执行，结果按请求顺序合并；
+    /// 单批失败不会中断其他批次，失败的批次及其位置ID在结果中单独列出。
+    /// Splits the ids into requests of `POSITION_DETAIL_MAX_IDS`, runs at most
+    /// `concurrency` at once and merges the results in request order; a failed
+    /// chunk does not stop the others, and failed chunks and their position ids
+    /// are listed in the result.
+    ///
+    /// # Parameters 参数
+    /// - `position_ids`: 位置ID列表 / Position IDs
+    /// - `concurrency`: 并发批数 (0 视为 1) / Concurrent requests (0 is treated as 1)
+    ///
+    /// # Returns
+    /// 合并的位置详情及失败的批次 / The merged position details and failed chunks
+    pub async fn query_position_detail_all(
+        &self,
+        position_ids: &[&str],
+        concurrency: usize,
+    ) -> PartialResult<PositionDetail> {
+        let outcomes: Vec<_> = stream::iter(position_ids.chunks(POSITION_DETAIL_MAX_IDS))
+            .map(|chunk| async move {
+                let ids = chunk.iter().map(|id| id.to_string()).collect();
+                (ids, self.query_position_detail_typed(chunk).await)
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+        PartialResult::from_outcomes(outcomes)
+    }
+
+    /// 创建位置 (Create position)
+    ///
+    /// intent: config.position.create
+    ///
+    /// # Parameters 参数
+    /// - `position_name`: 位置名称 / Position name
+    /// - `description`: 位置描述 (可选) / Position description (optional)
+    /// - `parent_position_id`: 父位置ID (可选) / Parent position ID (optional)
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn config_position_create(
+        &self,
+        position_name: &str,
+        description: Option<&str>,
+        parent_position_id: Option<&str>,
+    ) -> Result<String, AqaraError> {
+        let data = position_create_data(position_name, description, parent_position_id);
+        self.send_api_request("config.position.create", data, true)
+            .await
+    }
+
+    /// 按模板批量创建位置 (Create a position hierarchy from a template)
+    ///
+    /// intent: config.position.create
+    ///
+    /// 按深度优先顺序逐个创建；任一创建失败即返回错误，已创建的位置不会回滚。
+    /// Creates positions one by one in depth-first order; the first failure is
+    /// returned and positions created so far are not rolled back.
+    ///
+    /// # Parameters 参数
+    /// - `template`: 位置模板 / Position template
+    /// - `parent_position_id`: 挂载的父位置ID (可选) / Parent position to attach to (optional)
+    ///
+    /// # Returns
+    /// 成功返回与模板对应的位置ID树 / Returns the position id tree matching the template
+    pub async fn create_position_tree(
+        &self,
+        template: &PositionTemplate,
+        parent_position_id: Option<&str>,
+    ) -> Result<CreatedPosition, AqaraError> {
+        self.create_position_node(template, parent_position_id.map(str::to_string))
+            .await
+    }
+
+    fn create_position_node<'a>(
+        &'a self,
+        template: &'a PositionTemplate,
+        parent_position_id: Option<String>,
+    ) -> CreateTreeFuture<'a> {
+        Box::pin(async move {
+            let data = position_create_data(
+                &template.name,
+                template.description.as_deref(),
+                parent_position_id.as_deref(),
+            );
+            let response = self
+                .call_api::<Value>("config.position.create", data, true)
+                .await?;
+            let position_id = require_id(response, "config.position.create", "positionId")?;
+
+            let mut children = Vec::with_capacity(template.children.len());
+            for child in &template.children {
+                children.push(
+                    self.create_position_node(child, Some(position_id.clone()))
+                        .await?,
+                );
+            }
+            Ok(CreatedPosition {
+                position_id,
+                name: template.name.clone(),
+                children,
+            })
+        })
+    }
+
+    pub(crate) fn position_info_data(
+        &self,
+        parent_position_id: Option<&str>,
+        page_num: Option<i32>,
+        page_size: Option<i32>,
+    ) -> Value {
+        json!({
+            "parentPositionId": parent_position_id
+                .or(self.inner.defaults.position_id.as_deref())
+                .unwrap_or(""),
+            "pageNum": page_num.unwrap_or(1),
+            "pageSize": page_size.or(self.inner.defaults.page_size).unwrap_or(30)
+        })
+    }
+}
+
+fn position_create_data(
+    position_name: &str,
+    description: Option<&str>,
+    parent_position_id: Option<&str>,
+) -> Value {
+    let mut data = json!({
+        "positionName": position_name,
+    });
+    if let Some(description) = description {
+        data["description"] = json!(description);
+    }
+    if let Some(parent_position_id) = parent_position_id {
+        data["parentPositionId"] = json!(parent_position_id);
+    }
+    data
+}
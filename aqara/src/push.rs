@@ -0,0 +1,397 @@
+//! 消息推送 (Message push)
+//!
+//! Aqara 推送消息的类型模型与进程内事件总线。
+//! Typed models for Aqara push messages and an in-process event bus.
+
+use std::collections::HashMap;
+
+use futures_util::Stream;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::warn;
+
+use crate::AqaraError;
+
+/// 场景执行结果的 msgType / msgType of scene execution results
+pub const MSG_TYPE_SCENE_EXECUTION: &str = "scene_execute";
+
+/// 自动化执行结果的 msgType / msgType of linkage (automation) execution results
+pub const MSG_TYPE_LINKAGE_EXECUTION: &str = "linkage_execute";
+
+/// 资源上报的 msgType / msgType of resource reports
+pub const MSG_TYPE_RESOURCE_REPORT: &str = "resource_report";
+
+/// 设备事件 (绑定、解绑、上下线) 的 msgType / msgType of device events (bind, unbind, online, offline)
+pub const MSG_TYPE_EVENT_REPORT: &str = "event_report";
+
+/// 推送消息外层结构 (Push message envelope)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PushEnvelope {
+    /// 消息ID / Message ID
+    #[serde(default)]
+    pub msg_id: String,
+    /// 应用ID / App ID
+    #[serde(default)]
+    pub app_id: String,
+    /// 用户 openId / User openId
+    #[serde(default)]
+    pub open_id: String,
+    /// 消息类型 / Message type
+    pub msg_type: String,
+    /// 推送时间 (毫秒) / Push time (millis)
+    #[serde(default)]
+    pub time: Option<Value>,
+    /// 消息内容 / Message payload
+    #[serde(default)]
+    pub data: Value,
+    /// 未建模的字段 / Fields that are not modelled
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// 触发来源 (Trigger source)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggerSource {
+    /// 触发类型 (例如设备、定时、手动) / Trigger type (e.g. device, timer, manual)
+    #[serde(default, rename = "type")]
+    pub trigger_type: Option<Value>,
+    /// 触发设备或条件ID / Triggering device or condition ID
+    #[serde(default)]
+    pub subject_id: Option<String>,
+    /// 触发资源ID / Triggering resource ID
+    #[serde(default)]
+    pub resource_id: Option<String>,
+    /// 触发值 / Triggering value
+    #[serde(default)]
+    pub value: Option<String>,
+    /// 未建模的字段 / Fields that are not modelled
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// 单个动作的执行结果 (Result of one action)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionResult {
+    /// 设备ID / Subject (device) ID
+    #[serde(default)]
+    pub subject_id: String,
+    /// 资源ID / Resource ID
+    #[serde(default)]
+    pub resource_id: Option<String>,
+    /// 写入值 / Written value
+    #[serde(default)]
+    pub value: Option<String>,
+    /// 状态码，0 表示成功 / Status code, 0 means success
+    #[serde(default)]
+    pub status_code: i32,
+    /// 未建模的字段 / Fields that are not modelled
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl ActionResult {
+    /// 动作是否执行成功 / Whether the action succeeded
+    pub fn is_success(&self) -> bool {
+        self.status_code == 0
+    }
+}
+
+/// 场景或自动化执行结果 (Scene or linkage execution result)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationExecution {
+    /// 场景ID或自动化ID / Scene ID or linkage ID
+    #[serde(default, alias = "sceneId", alias = "linkageId")]
+    pub subject_id: String,
+    /// 名称 / Name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// 触发来源 / Trigger source
+    #[serde(default)]
+    pub trigger_source: Option<TriggerSource>,
+    /// 各动作的执行结果 / Per-action results
+    #[serde(default)]
+    pub actions: Vec<ActionResult>,
+    /// 执行时间 (毫秒) / Execution time (millis)
+    #[serde(default)]
+    pub time: Option<Value>,
+    /// 未建模的字段 / Fields that are not modelled
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl AutomationExecution {
+    /// 所有动作是否都执行成功 / Whether every action succeeded
+    pub fn is_success(&self) -> bool {
+        self.actions.iter().all(ActionResult::is_success)
+    }
+}
+
+/// 资源上报 (Resource report)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceReport {
+    /// 设备ID / Subject (device) ID
+    #[serde(default)]
+    pub subject_id: String,
+    /// 资源ID / Resource ID
+    #[serde(default)]
+    pub resource_id: String,
+    /// 资源值 / Resource value
+    #[serde(default)]
+    pub value: String,
+    /// 设备型号 / Device model
+    #[serde(default)]
+    pub model: Option<String>,
+    /// 上报时间 (毫秒) / Report time (millis)
+    #[serde(default)]
+    pub time: Option<Value>,
+    /// 状态码，0 表示成功 / Status code, 0 means success
+    #[serde(default)]
+    pub status_code: i32,
+    /// 触发来源 / Trigger source
+    #[serde(default)]
+    pub trigger_source: Option<TriggerSource>,
+    /// 未建模的字段 / Fields that are not modelled
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// 设备事件 (绑定、解绑、上下线) (Device lifecycle event: bind, unbind, online, offline)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceLifecycle {
+    /// 设备ID / Subject (device) ID
+    #[serde(default)]
+    pub subject_id: String,
+    /// 事件类型 (例如 `gateway_online`) / Event type (e.g. `gateway_online`)
+    #[serde(default)]
+    pub event_type: String,
+    /// 设备型号 / Device model
+    #[serde(default)]
+    pub model: Option<String>,
+    /// 事件时间 (毫秒) / Event time (millis)
+    #[serde(default)]
+    pub time: Option<Value>,
+    /// 未建模的字段 / Fields that are not modelled
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// 推送消息 (Push message)
+#[derive(Debug, Clone, Serialize)]
+pub enum PushMessage {
+    /// 场景执行结果 / Scene execution results
+    SceneExecution(Vec<AutomationExecution>),
+    /// 自动化执行结果 / Linkage execution results
+    LinkageExecution(Vec<AutomationExecution>),
+    /// 资源上报 / Resource reports
+    ResourceReport(Vec<ResourceReport>),
+    /// 设备事件 / Device lifecycle events
+    DeviceLifecycle(Vec<DeviceLifecycle>),
+    /// 未识别的消息，保留原始内容 / Unrecognized message, raw content retained
+    Unknown { msg_type: String, raw: Value },
+}
+
+impl PushMessage {
+    /// 解析推送消息 (Parse a push message)
+    ///
+    /// 未知的 msgType 以及无法按类型解析的内容都会返回 `PushMessage::Unknown`，
+    /// 云端新增字段或消息类型不会导致解析失败。只有外层结构无效时才返回错误。
+    /// Unknown msgTypes and payloads that fail typed decoding come back as
+    /// `PushMessage::Unknown`, so cloud-side schema additions never break parsing.
+    /// Only an invalid envelope is an error.
+    pub fn from_json(body: &str) -> Result<Self, AqaraError> {
+        let raw: Value = serde_json::from_str(body)?;
+        let envelope: PushEnvelope = serde_json::from_value(raw.clone())?;
+        Ok(Self::from_envelope(envelope, raw))
+    }
+
+    /// 从外层结构解析 (Parse from an envelope)
+    ///
+    /// `raw` 为完整的原始消息，用于 `Unknown` 变体。
+    /// `raw` is the complete original message, used for the `Unknown` variant.
+    pub fn from_envelope(envelope: PushEnvelope, raw: Value) -> Self {
+        let parsed = match envelope.msg_type.as_str() {
+            MSG_TYPE_SCENE_EXECUTION => one_or_many(envelope.data).map(PushMessage::SceneExecution),
+            MSG_TYPE_LINKAGE_EXECUTION => {
+                one_or_many(envelope.data).map(PushMessage::LinkageExecution)
+            }
+            MSG_TYPE_RESOURCE_REPORT => one_or_many(envelope.data).map(PushMessage::ResourceReport),
+            MSG_TYPE_EVENT_REPORT => one_or_many(envelope.data).map(PushMessage::DeviceLifecycle),
+            _ => None,
+        };
+        parsed.unwrap_or(PushMessage::Unknown {
+            msg_type: envelope.msg_type,
+            raw,
+        })
+    }
+
+    /// 消息类型 / Message type
+    pub fn msg_type(&self) -> &str {
+        match self {
+            PushMessage::SceneExecution(_) => MSG_TYPE_SCENE_EXECUTION,
+            PushMessage::LinkageExecution(_) => MSG_TYPE_LINKAGE_EXECUTION,
+            PushMessage::ResourceReport(_) => MSG_TYPE_RESOURCE_REPORT,
+            PushMessage::DeviceLifecycle(_) => MSG_TYPE_EVENT_REPORT,
+            PushMessage::Unknown { msg_type, .. } => msg_type,
+        }
+    }
+}
+
+// data 可能是单个对象或数组 / `data` may be a single object or an array
+fn one_or_many<T: DeserializeOwned>(data: Value) -> Option<Vec<T>> {
+    match data {
+        Value::Array(_) => serde_json::from_value(data).ok(),
+        Value::Null => Some(Vec::new()),
+        other => serde_json::from_value(other).ok().map(|e| vec![e]),
+    }
+}
+
+/// 进程内事件总线 (In-process event bus)
+///
+/// 推送接收方发布消息，多个消费者各自订阅。
+/// Push receivers publish messages and any number of consumers subscribe.
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<PushMessage>,
+}
+
+impl EventBus {
+    /// 创建事件总线，`capacity` 为每个订阅者可缓存的消息数
+    /// Create an event bus; `capacity` is the number of messages buffered per subscriber
+    pub fn new(capacity: usize) -> Self {
+        EventBus {
+            sender: broadcast::channel(capacity.max(1)).0,
+        }
+    }
+
+    /// 发布消息，返回收到消息的订阅者数量
+    /// Publish a message, returning how many subscribers received it
+    pub fn publish(&self, message: PushMessage) -> usize {
+        self.sender.send(message).unwrap_or(0)
+    }
+
+    /// 订阅消息 / Subscribe to messages
+    pub fn subscribe(&self) -> broadcast::Receiver<PushMessage> {
+        self.sender.subscribe()
+    }
+
+    /// 以 `Stream` 形式订阅 (Subscribe as a `Stream`)
+    ///
+    /// 消费过慢而丢失的消息会被跳过并记录警告；总线关闭后流结束。
+    /// Messages missed by a slow consumer are skipped with a warning; the stream
+    /// ends once the bus is closed.
+    pub fn stream(&self) -> impl Stream<Item = PushMessage> + Send + 'static {
+        futures_util::stream::unfold(self.subscribe(), |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(message) => return Some((message, receiver)),
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("Push stream lagged, skipped {} messages", skipped)
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+
+    /// 以阻塞迭代器形式订阅 (不可在异步上下文中使用)
+    /// Subscribe as a blocking iterator (must not be used from async code)
+    pub fn iter(&self) -> PushIter {
+        PushIter {
+            receiver: self.subscribe(),
+        }
+    }
+}
+
+/// 推送消息的阻塞迭代器 (Blocking iterator over push messages)
+pub struct PushIter {
+    receiver: broadcast::Receiver<PushMessage>,
+}
+
+impl Iterator for PushIter {
+    type Item = PushMessage;
+
+    fn next(&mut self) -> Option<PushMessage> {
+        loop {
+            match self.receiver.blocking_recv() {
+                Ok(message) => return Some(message),
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("Push iterator lagged, skipped {} messages", skipped)
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        EventBus::new(1024)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_known_message_types() {
+        let body = r#"{"msgType":"resource_report","data":[{"subjectId":"lumi.1","resourceId":"0.1.85","value":"2350","time":"1700000000000"}]}"#;
+        match PushMessage::from_json(body).unwrap() {
+            PushMessage::ResourceReport(reports) => {
+                assert_eq!(reports.len(), 1);
+                assert_eq!(reports[0].subject_id, "lumi.1");
+                assert_eq!(reports[0].value, "2350");
+            }
+            other => panic!("expected ResourceReport, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn single_object_data_becomes_one_item() {
+        let body = r#"{"msgType":"event_report","data":{"subjectId":"lumi.1","eventType":"gateway_online"}}"#;
+        match PushMessage::from_json(body).unwrap() {
+            PushMessage::DeviceLifecycle(events) => {
+                assert_eq!(events[0].event_type, "gateway_online")
+            }
+            other => panic!("expected DeviceLifecycle, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unknown_msg_type_falls_back_to_unknown() {
+        let body = r#"{"msgType":"ota_progress","data":{"progress":40}}"#;
+        let message = PushMessage::from_json(body).unwrap();
+        assert_eq!(message.msg_type(), "ota_progress");
+        match message {
+            PushMessage::Unknown { msg_type, raw } => {
+                assert_eq!(msg_type, "ota_progress");
+                assert_eq!(raw["data"]["progress"], 40);
+            }
+            other => panic!("expected Unknown, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn mistyped_payload_falls_back_to_unknown() {
+        let body = r#"{"msgType":"resource_report","data":"not an object"}"#;
+        assert!(matches!(
+            PushMessage::from_json(body).unwrap(),
+            PushMessage::Unknown { .. }
+        ));
+    }
+
+    #[test]
+    fn invalid_envelope_is_an_error() {
+        assert!(PushMessage::from_json(r#"{"data":[]}"#).is_err());
+        assert!(PushMessage::from_json("not json").is_err());
+    }
+}
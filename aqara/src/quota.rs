@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::AqaraClient;
+
+/// 软限制触发后的行为 (Soft-limit action)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SoftLimitAction {
+    /// 仅记录告警日志 / Only log a warning
+    Warn,
+    /// 延迟请求直到窗口重置 / Delay calls until the window resets
+    Throttle,
+}
+
+/// 配额软限制 (Quota soft limits)
+///
+/// 设置为略低于账号实际配额的值，在触发服务端硬限制前告警或限速。
+/// 按日窗口以 UTC 零点重置；限速模式下超出按日限制的请求会等待到次日。
+/// Set slightly below the account's real quota to warn or throttle before the
+/// server's hard limit is hit. The daily window resets at UTC midnight; in
+/// throttle mode calls over the daily limit wait until the next day.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct QuotaLimits {
+    /// 每日请求数上限 (可选) / Requests per day (optional)
+    pub daily: Option<u64>,
+    /// 每秒请求数上限 (可选) / Requests per second (optional)
+    pub qps: Option<u32>,
+    /// 超出后的行为 / What to do once exceeded
+    pub action: SoftLimitAction,
+}
+
+/// 配额使用情况 (Quota usage snapshot)
+///
+/// 每次实际发出的请求 (包括重试) 都计入配额。
+/// Every request actually sent, retries included, counts against the quota.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaUsage {
+    /// 当前窗口开始时间 / Start of the current window
+    pub window_start: DateTime<Utc>,
+    /// 当前窗口重置时间 / When the current window resets
+    pub resets_at: DateTime<Utc>,
+    /// 窗口内请求总数 / Requests in the window
+    pub total: u64,
+    /// 按 intent 类别 (如 `query`、`write`) 统计的请求数 / Requests per intent class (e.g. `query`, `write`)
+    pub by_class: HashMap<String, u64>,
+    /// 窗口内被限流 (HTTP 429) 的次数 / HTTP 429 responses in the window
+    pub rate_limited: u64,
+    /// 最近一次被限流的时间 / When the last HTTP 429 was seen
+    pub last_rate_limited: Option<DateTime<Utc>>,
+}
+
+impl QuotaUsage {
+    fn new(now: DateTime<Utc>) -> Self {
+        let window_start =
+            Utc.from_utc_datetime(&now.date_naive().and_hms_opt(0, 0, 0).unwrap_or_default());
+        QuotaUsage {
+            window_start,
+            resets_at: window_start + Duration::days(1),
+            total: 0,
+            by_class: HashMap::new(),
+            rate_limited: 0,
+            last_rate_limited: None,
+        }
+    }
+}
+
+/// 客户端侧配额计数 (Client-side quota tracking)
+pub(crate) struct QuotaTracker {
+    limits: Option<QuotaLimits>,
+    state: Mutex<QuotaState>,
+}
+
+struct QuotaState {
+    usage: QuotaUsage,
+    // 当前秒及其请求数 / Current second and its request count
+    second: i64,
+    second_count: u32,
+}
+
+impl QuotaTracker {
+    pub(crate) fn new(limits: Option<QuotaLimits>) -> Self {
+        QuotaTracker {
+            limits,
+            state: Mutex::new(QuotaState {
+                usage: QuotaUsage::new(Utc::now()),
+                second: 0,
+                second_count: 0,
+            }),
+        }
+    }
+
+    /// 计入一次请求，限速模式下必要时先等待 / Count a request, waiting first when throttling
+    pub(crate) async fn admit(&self, intent: &str) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+                let now = Utc::now();
+                state.roll(now);
+                match self.over_limit(&state, intent, now) {
+                    Some(until) => (until - now).to_std().unwrap_or_default(),
+                    None => {
+                        state.record(intent, now.timestamp());
+                        return;
+                    }
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    // 限速模式下返回需要等待到的时间；告警模式下仅在越线时告警
+    // Returns the instant to wait for in throttle mode; warn mode only logs when crossing
+    fn over_limit(
+        &self,
+        state: &QuotaState,
+        intent: &str,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let limits = self.limits?;
+        let second_count = match state.second == now.timestamp() {
+            true => state.second_count,
+            false => 0,
+        };
+        let over_qps = limits.qps.is_some_and(|qps| second_count >= qps);
+        let over_daily = limits.daily.is_some_and(|daily| state.usage.total >= daily);
+        match limits.action {
+            SoftLimitAction::Throttle if over_daily => Some(state.usage.resets_at),
+            SoftLimitAction::Throttle if over_qps => DateTime::from_timestamp(state.second + 1, 0),
+            SoftLimitAction::Throttle => None,
+            SoftLimitAction::Warn => {
+                if limits.qps == Some(second_count) {
+                    warn!(
+                        "Quota soft limit: {} requests this second ({})",
+                        second_count, intent
+                    );
+                }
+                if limits.daily == Some(state.usage.total) {
+                    warn!(
+                        "Quota soft limit: {} requests today, resets at {} ({})",
+                        state.usage.total, state.usage.resets_at, intent
+                    );
+                }
+                None
+            }
+        }
+    }
+
+    /// 记录一次 HTTP 429 / Record an HTTP 429
+    pub(crate) fn record_rate_limited(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Utc::now();
+        state.roll(now);
+        state.usage.rate_limited += 1;
+        state.usage.last_rate_limited = Some(now);
+    }
+
+    pub(crate) fn usage(&self) -> QuotaUsage {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.roll(Utc::now());
+        state.usage.clone()
+    }
+}
+
+impl QuotaState {
+    fn roll(&mut self, now: DateTime<Utc>) {
+        if now >= self.usage.resets_at {
+            self.usage = QuotaUsage::new(now);
+        }
+    }
+
+    fn record(&mut self, intent: &str, second: i64) {
+        if self.second != second {
+            self.second = second;
+            self.second_count = 0;
+        }
+        self.second_count += 1;
+        self.usage.total += 1;
+        let class = intent.split('.').next().unwrap_or(intent);
+        *self.usage.by_class.entry(class.to_string()).or_default() += 1;
+    }
+}
+
+impl AqaraClient {
+    /// 查询配额使用情况 (Query quota usage)
+    ///
+    /// 客户端侧统计，克隆体与 `as_user` 视图共享同一计数。
+    /// Tracked client-side; clones and `as_user` views share the same counters.
+    pub fn quota(&self) -> QuotaUsage {
+        self.inner.quota.usage()
+    }
+}
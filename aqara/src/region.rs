@@ -0,0 +1,73 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::AqaraError;
+
+/// 服务区域 (Service region)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Region {
+    China,
+    Usa,
+    Europe,
+    Korea,
+    Russia,
+    Singapore,
+}
+
+impl Default for Region {
+    // 根据编译特性选择默认区域
+    // Select the default region based on compilation features
+    fn default() -> Self {
+        if cfg!(feature = "china") {
+            Region::China
+        } else if cfg!(feature = "usa") {
+            Region::Usa
+        } else if cfg!(feature = "europe") {
+            Region::Europe
+        } else if cfg!(feature = "korea") {
+            Region::Korea
+        } else if cfg!(feature = "russia") {
+            Region::Russia
+        } else if cfg!(feature = "singapore") {
+            Region::Singapore
+        } else {
+            Region::China
+        }
+    }
+}
+
+impl Region {
+    /// 该区域的接口地址 / API endpoint of this region
+    pub fn base_url(self) -> &'static str {
+        match self {
+            Region::China => "https://open-cn.aqara.com/v3.0/open/api",
+            Region::Usa => "https://open-usa.aqara.com/v3.0/open/api",
+            Region::Europe => "https://open-ger.aqara.com/v3.0/open/api",
+            Region::Korea => "https://open-kr.aqara.com/v3.0/open/api",
+            Region::Russia => "https://open-ru.aqara.com/v3.0/open/api",
+            Region::Singapore => "https://open-sg.aqara.com/v3.0/open/api",
+        }
+    }
+}
+
+impl FromStr for Region {
+    type Err = AqaraError;
+
+    // 接受区域名或其简写，不区分大小写 / Accepts region names or their short forms, case-insensitively
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "china" | "cn" => Ok(Region::China),
+            "usa" | "us" => Ok(Region::Usa),
+            "europe" | "eu" | "ger" => Ok(Region::Europe),
+            "korea" | "kr" => Ok(Region::Korea),
+            "russia" | "ru" => Ok(Region::Russia),
+            "singapore" | "sg" => Ok(Region::Singapore),
+            _ => Err(AqaraError::InvalidParams(vec![format!(
+                "unknown region {:?}",
+                s
+            )])),
+        }
+    }
+}
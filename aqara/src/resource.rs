@@ -0,0 +1,374 @@
+use std::sync::Arc;
+
+use futures_util::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::util::check_max_items;
+use crate::{AqaraClient, AqaraError, AqaraResponse};
+
+/// 单次资源值查询的最大设备数 / Max subjects per resource value query
+pub const RESOURCE_VALUE_MAX_SUBJECTS: usize = 50;
+
+/// 单次资源名称查询的最大设备数 / Max subjects per resource name query
+pub const RESOURCE_NAME_MAX_SUBJECTS: usize = 50;
+
+/// 资源名称 (Resource name)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceName {
+    /// 设备ID / Subject (device) ID
+    #[serde(default)]
+    pub subject_id: String,
+    /// 资源ID / Resource ID
+    #[serde(default)]
+    pub resource_id: String,
+    /// 用户设置的资源名称 / Resource name set by the user
+    #[serde(default)]
+    pub name: String,
+}
+
+/// 资源查询项 (Resource query)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceQuery {
+    /// 设备ID / Subject (device) ID
+    pub subject_id: String,
+    /// 资源ID列表 (为空时查询全部) / Resource IDs (all resources when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resource_ids: Vec<String>,
+}
+
+/// 资源写入项 (Resource write)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceWrite {
+    /// 资源ID / Resource ID
+    pub resource_id: String,
+    /// 资源值 / Resource value
+    pub value: String,
+}
+
+/// 单个设备的资源写入 (Resource writes for one device)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceResourceWrite {
+    /// 设备ID / Subject (device) ID
+    pub subject_id: String,
+    /// 资源写入项 / Resource writes
+    pub resources: Vec<ResourceWrite>,
+}
+
+/// 批量写入结果 (Batch write result)
+#[derive(Debug, Default)]
+pub struct BatchResult {
+    /// 写入成功的设备ID / Devices written successfully
+    pub succeeded: Vec<String>,
+    /// 写入失败的设备及错误 / Devices that failed, with the error
+    ///
+    /// 同一请求中的设备共享错误 (`AqaraError::Shared`)。
+    /// Devices sent in the same request share the error (`AqaraError::Shared`).
+    pub failed: Vec<(String, AqaraError)>,
+}
+
+impl BatchResult {
+    /// 是否全部成功 / Whether every device succeeded
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// 失败的分批请求 (A failed chunk)
+#[derive(Debug)]
+pub struct ChunkFailure {
+    /// 该批请求的ID / Ids sent in the chunk
+    pub ids: Vec<String>,
+    /// 错误 / Error
+    pub error: AqaraError,
+}
+
+/// 分批查询结果 (Chunked query result)
+///
+/// 成功批次的结果合并在 `items` 中，失败批次逐个列在 `failures` 中。
+/// Results of successful chunks are merged into `items`; each failed chunk is
+/// listed in `failures`.
+#[derive(Debug)]
+pub struct PartialResult<T> {
+    /// 成功批次的结果 / Results of the successful chunks
+    pub items: Vec<T>,
+    /// 失败的批次 / Failed chunks
+    pub failures: Vec<ChunkFailure>,
+}
+
+impl<T> PartialResult<T> {
+    /// 是否全部成功 / Whether every chunk succeeded
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    // 按请求顺序合并各批次的 (ID, 结果) / Merge each chunk's (ids, outcome) in request order
+    pub(crate) fn from_outcomes(
+        outcomes: impl IntoIterator<Item = (Vec<String>, Result<AqaraResponse<Vec<T>>, AqaraError>)>,
+    ) -> Self {
+        let mut result = PartialResult {
+            items: Vec::new(),
+            failures: Vec::new(),
+        };
+        for (ids, outcome) in outcomes {
+            match outcome {
+                Ok(response) => result.items.extend(response.result.unwrap_or_default()),
+                Err(error) => result.failures.push(ChunkFailure { ids, error }),
+            }
+        }
+        result
+    }
+}
+
+impl AqaraClient {
+    /// 查询资源值 (Query resource values)
+    ///
+    /// intent: query.resource.value
+    ///
+    /// # Parameters 参数
+    /// - `resources`: 资源查询项 / Resource queries
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn query_resource_value(
+        &self,
+        resources: &[ResourceQuery],
+    ) -> Result<String, AqaraError> {
+        check_max_items("resources", resources.len(), RESOURCE_VALUE_MAX_SUBJECTS)?;
+        let data = json!({
+            "resources": resources
+        });
+        self.send_api_request("query.resource.value", data, true)
+            .await
+    }
+
+    /// 写入设备资源 (Write device resources)
+    ///
+    /// intent: write.resource.device
+    ///
+    /// # Parameters 参数
+    /// - `subject_id`: 设备ID / Subject (device) ID
+    /// - `resources`: 资源写入项 / Resource writes
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn write_resource_device(
+        &self,
+        subject_id: &str,
+        resources: &[ResourceWrite],
+    ) -> Result<String, AqaraError> {
+        let data = json!([{
+            "subjectId": subject_id,
+            "resources": resources
+        }]);
+        self.send_api_request("write.resource.device", data, true)
+            .await
+    }
+
+    /// 分批查询资源值 (Query resource values in chunks)
+    ///
+    /// intent: query.resource.value
+    ///
+    /// 按 `RESOURCE_VALUE_MAX_SUBJECTS` 个设备拆分请求，最多 `concurrency` 批同时执行，
+    /// 结果按请求顺序合并；单批失败 (包括返回的不是数组) 不会中断其他批次，
+    /// 失败的批次及其设备ID在结果中单独列出。
+    /// Splits the queries into requests of `RESOURCE_VALUE_MAX_SUBJECTS` devices,
+    /// runs at most `concurrency` at once and merges the results in request order;
+    /// a failed chunk (including one whose result is not an array) does not stop
+    /// the others, and failed chunks and their subject ids are listed in the result.
+    ///
+    /// # Parameters 参数
+    /// - `resources`: 资源查询项 / Resource queries
+    /// - `concurrency`: 并发批数 (0 视为 1) / Concurrent requests (0 is treated as 1)
+    ///
+    /// # Returns
+    /// 合并的资源值及失败的批次 / The merged resource values and failed chunks
+    pub async fn query_resource_value_batched(
+        &self,
+        resources: &[ResourceQuery],
+        concurrency: usize,
+    ) -> PartialResult<Value> {
+        let outcomes: Vec<_> = stream::iter(resources.chunks(RESOURCE_VALUE_MAX_SUBJECTS))
+            .map(|chunk| async move {
+                let ids = chunk.iter().map(|query| query.subject_id.clone()).collect();
+                let outcome = self
+                    .call_api::<Vec<Value>>(
+                        "query.resource.value",
+                        json!({ "resources": chunk }),
+                        true,
+                    )
+                    .await;
+                (ids, outcome)
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+        PartialResult::from_outcomes(outcomes)
+    }
+
+    /// 分批写入设备资源 (Write device resources in chunks)
+    ///
+    /// intent: write.resource.device
+    ///
+    /// 按 `chunk_size` 个设备一批拆分请求，最多 `concurrency` 批同时执行；
+    /// 单批失败不影响其他批次，结果按设备汇总。
+    /// Splits the writes into requests of `chunk_size` devices and runs at most
+    /// `concurrency` of them at once; a failed chunk does not stop the others,
+    /// and results are aggregated per device.
+    ///
+    /// # Parameters 参数
+    /// - `items`: 每个设备的资源写入 / Resource writes per device
+    /// - `chunk_size`: 每批设备数 (0 视为 1) / Devices per request (0 is treated as 1)
+    /// - `concurrency`: 并发批数 (0 视为 1) / Concurrent requests (0 is treated as 1)
+    ///
+    /// # Returns
+    /// 按设备汇总的结果 / Results aggregated per device
+    pub async fn write_resource_device_batched(
+        &self,
+        items: &[DeviceResourceWrite],
+        chunk_size: usize,
+        concurrency: usize,
+    ) -> BatchResult {
+        let outcomes: Vec<_> = stream::iter(items.chunks(chunk_size.max(1)))
+            .map(|chunk| async move {
+                let result = self
+                    .call_api::<Value>("write.resource.device", json!(chunk), true)
+                    .await;
+                (chunk, result)
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+
+        let mut batch = BatchResult::default();
+        for (chunk, result) in outcomes {
+            match result {
+                Ok(_) => batch
+                    .succeeded
+                    .extend(chunk.iter().map(|item| item.subject_id.clone())),
+                Err(e) if chunk.len() == 1 => batch.failed.push((chunk[0].subject_id.clone(), e)),
+                Err(e) => {
+                    let e = Arc::new(e);
+                    for item in chunk {
+                        batch
+                            .failed
+                            .push((item.subject_id.clone(), AqaraError::Shared(e.clone())));
+                    }
+                }
+            }
+        }
+        batch
+    }
+
+    /// 查询资源名称 (Query resource names)
+    ///
+    /// intent: query.resource.name
+    ///
+    /// # Parameters 参数
+    /// - `subject_ids`: 设备ID列表 (最多50个) / Up to 50 subject (device) IDs
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn query_resource_name(&self, subject_ids: &[&str]) -> Result<String, AqaraError> {
+        check_max_items("subject_ids", subject_ids.len(), RESOURCE_NAME_MAX_SUBJECTS)?;
+        let data = json!({
+            "subjectIds": subject_ids
+        });
+        self.send_api_request("query.resource.name", data, true)
+            .await
+    }
+
+    /// 查询资源名称 (类型化) (Query resource names, typed)
+    ///
+    /// intent: query.resource.name
+    ///
+    /// # Parameters 参数
+    /// - `subject_ids`: 设备ID列表 (最多50个) / Up to 50 subject (device) IDs
+    ///
+    /// # Returns
+    /// 成功返回资源名称列表 / Returns the resource names on success
+    pub async fn query_resource_name_typed(
+        &self,
+        subject_ids: &[&str],
+    ) -> Result<AqaraResponse<Vec<ResourceName>>, AqaraError> {
+        check_max_items("subject_ids", subject_ids.len(), RESOURCE_NAME_MAX_SUBJECTS)?;
+        self.call_api(
+            "query.resource.name",
+            json!({ "subjectIds": subject_ids }),
+            true,
+        )
+        .await
+    }
+
+    /// 分批查询全部资源名称 (Query every resource name in chunks)
+    ///
+    /// intent: query.resource.name
+    ///
+    /// 按 `RESOURCE_NAME_MAX_SUBJECTS` 个设备拆分请求，最多 `concurrency` 批同时执行；
+    /// 单批失败不会中断其他批次，失败的批次及其设备ID在结果中单独列出。
+    /// Splits the ids into requests of `RESOURCE_NAME_MAX_SUBJECTS` devices and runs
+    /// at most `concurrency` at once; a failed chunk does not stop the others, and
+    /// failed chunks and their subject ids are listed in the result.
+    ///
+    /// # Parameters 参数
+    /// - `subject_ids`: 设备ID列表 / Subject (device) IDs
+    /// - `concurrency`: 并发批数 (0 视为 1) / Concurrent requests (0 is treated as 1)
+    ///
+    /// # Returns
+    /// 合并的资源名称及失败的批次 / The merged resource names and failed chunks
+    pub async fn query_resource_name_all(
+        &self,
+        subject_ids: &[&str],
+        concurrency: usize,
+    ) -> PartialResult<ResourceName> {
+        let outcomes: Vec<_> = stream::iter(subject_ids.chunks(RESOURCE_NAME_MAX_SUBJECTS))
+            .map(|chunk| async move {
+                let ids = chunk.iter().map(|id| id.to_string()).collect();
+                (ids, self.query_resource_name_typed(chunk).await)
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+        PartialResult::from_outcomes(outcomes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(result: Option<Vec<i32>>) -> AqaraResponse<Vec<i32>> {
+        AqaraResponse {
+            code: 0,
+            request_id: "req-1".to_string(),
+            message: "Success".to_string(),
+            msg_details: None,
+            result,
+            intent: "query.resource.name".to_string(),
+        }
+    }
+
+    #[test]
+    fn from_outcomes_merges_items_and_lists_failed_chunks() {
+        let result = PartialResult::from_outcomes(vec![
+            (vec!["a".to_string()], Ok(response(Some(vec![1, 2])))),
+            (
+                vec!["b".to_string(), "c".to_string()],
+                Err(AqaraError::MissingResult {
+                    intent: "query.resource.name".to_string(),
+                    request_id: "req-2".to_string(),
+                }),
+            ),
+            (vec!["d".to_string()], Ok(response(None))),
+            (vec!["e".to_string()], Ok(response(Some(vec![3])))),
+        ]);
+        assert_eq!(result.items, vec![1, 2, 3]);
+        assert!(!result.is_complete());
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].ids, vec!["b", "c"]);
+    }
+}
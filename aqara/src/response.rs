@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::AqaraError;
+
+/// Aqara 通用响应结构 (Aqara common response envelope)
+///
+/// 所有接口返回 `code`/`requestId`/`message`/`msgDetails`/`result`,
+/// 其中 `result` 因接口而异。
+/// Every intent returns `code`/`requestId`/`message`/`msgDetails`/`result`,
+/// where `result` differs per intent. Serializable so responses can be cached
+/// or forwarded to other services as-is.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AqaraResponse<T = Value> {
+    pub code: i32,
+    #[serde(default)]
+    pub request_id: String,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub msg_details: Option<String>,
+    pub result: Option<T>,
+    /// 请求的 intent (由客户端填入，不参与序列化) / The requested intent (filled in by the client, not serialized)
+    #[serde(skip)]
+    pub intent: String,
+}
+
+impl<T> AqaraResponse<T> {
+    /// 取出必需的 `result` (Take the required `result`)
+    ///
+    /// `result` 缺失时返回 `AqaraError::MissingResult`。
+    /// Returns `AqaraError::MissingResult` when `result` is absent.
+    pub fn into_result(self) -> Result<T, AqaraError> {
+        self.result.ok_or(AqaraError::MissingResult {
+            intent: self.intent,
+            request_id: self.request_id,
+        })
+    }
+}
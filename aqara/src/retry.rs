@@ -0,0 +1,295 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::{AqaraError, ErrorCode, ErrorKind};
+
+/// 重试配置 (Retry config)
+///
+/// 仅对幂等 intent 生效；遇到 429、5xx、连接失败、超时或可重试的业务码
+/// (参见 `ErrorCode::is_retryable`) 时按指数退避重试。
+/// Only applies to idempotent intents; 429, 5xx, connect failures, timeouts and
+/// retryable business codes (see `ErrorCode::is_retryable`) are retried with
+/// exponential backoff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+    /// 最大重试次数 (不含首次请求) / Max retries (excluding the first attempt)
+    pub max_retries: u32,
+    /// 首次退避时间 / Initial backoff
+    pub initial_backoff: Duration,
+    /// 最大退避时间 / Max backoff
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        RetryConfig {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryConfig {
+    /// 不重试 / Never retry
+    pub fn disabled() -> Self {
+        RetryConfig {
+            max_retries: 0,
+            ..RetryConfig::default()
+        }
+    }
+
+    // 第 `retry` 次重试前的等待时间 (带抖动) / Delay before the `retry`-th retry (with jitter)
+    pub(crate) fn backoff(&self, retry: u32) -> Duration {
+        let base = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_backoff);
+        let jitter = rand::rng().random_range(0..=base.as_millis() as u64 / 2);
+        base + Duration::from_millis(jitter)
+    }
+}
+
+/// 重试策略 (Retry policy)
+///
+/// 每次幂等请求失败后调用，返回 `Some(delay)` 表示等待后重试，`None` 表示放弃。
+/// `RetryConfig` 即默认实现：对 429、5xx、连接失败、超时与可重试业务码做指数退避。
+/// Consulted after every failed attempt of an idempotent request; `Some(delay)`
+/// retries after the delay and `None` gives up. `RetryConfig` is the default
+/// implementation: exponential backoff on 429, 5xx, connect failures, timeouts
+/// and retryable business codes.
+pub trait RetryPolicy: Send + Sync {
+    /// 是否重试 / Whether to retry
+    ///
+    /// # Parameters 参数
+    /// - `error`: 本次失败的错误 / The error of the failed attempt
+    /// - `retries`: 已经进行的重试次数 (首次失败时为 0) / Retries made so far (0 after the first failure)
+    ///
+    /// # Returns
+    /// 重试前的等待时间；`None` 表示不再重试 / Delay before retrying; `None` stops retrying
+    fn should_retry(&self, error: &AqaraError, retries: u32) -> Option<Duration>;
+}
+
+impl RetryPolicy for RetryConfig {
+    fn should_retry(&self, error: &AqaraError, retries: u32) -> Option<Duration> {
+        (retries < self.max_retries && is_retryable(error)).then(|| self.backoff(retries + 1))
+    }
+}
+
+/// 重试预算 (Retry budget)
+///
+/// 整个客户端在每个 `window` 内最多重试 `max_retries` 次，预算耗尽后失败的请求不再重试，
+/// 避免大面积 5xx 时重试成倍放大流量。
+/// The whole client retries at most `max_retries` times per `window`; once the
+/// budget is spent failed requests are not retried, so a mass 5xx event does not
+/// multiply traffic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryBudget {
+    /// 每个窗口内的最大重试次数 / Max retries per window
+    pub max_retries: u32,
+    /// 窗口长度 / Window length
+    pub window: Duration,
+}
+
+impl Default for RetryBudget {
+    fn default() -> Self {
+        RetryBudget {
+            max_retries: 100,
+            window: Duration::from_secs(10),
+        }
+    }
+}
+
+// 按固定窗口计数的重试预算 / Retry budget counted in fixed windows
+pub(crate) struct RetryThrottle {
+    budget: RetryBudget,
+    state: Mutex<(Instant, u32)>,
+}
+
+impl RetryThrottle {
+    pub(crate) fn new(budget: RetryBudget) -> Self {
+        RetryThrottle {
+            budget,
+            state: Mutex::new((Instant::now(), 0)),
+        }
+    }
+
+    // 尝试消耗一次重试 / Try to spend one retry
+    pub(crate) fn try_spend(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let (window_started, spent) = &mut *state;
+        let now = Instant::now();
+        if now.duration_since(*window_started) >= self.budget.window {
+            *window_started = now;
+            *spent = 0;
+        }
+        if *spent >= self.budget.max_retries {
+            return false;
+        }
+        *spent += 1;
+        true
+    }
+}
+
+/// 单次尝试记录 (Attempt record)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    /// 错误分类 / Error kind
+    pub kind: ErrorKind,
+    /// HTTP 状态码 (若有) / HTTP status, if any
+    pub status: Option<u16>,
+    /// 本次失败后到下次尝试前的等待 (最后一次为零) / Delay before the next attempt (zero for the last)
+    pub delay: Duration,
+}
+
+/// 尝试历史 (Attempt history)
+///
+/// 经过重试仍失败时附在最终错误上，便于事后排查。
+/// Attached to the final error when a call still fails after retries, for postmortems.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttemptHistory {
+    /// 按顺序的每次尝试 / Every attempt, in order
+    pub attempts: Vec<Attempt>,
+    /// 总耗时 / Total elapsed time
+    pub elapsed: Duration,
+}
+
+impl Attempt {
+    pub(crate) fn new(error: &AqaraError, delay: Duration) -> Self {
+        let status = match error {
+            AqaraError::Http(e) => e.status().map(|status| status.as_u16()),
+            _ => None,
+        };
+        Attempt {
+            kind: error.kind(),
+            status,
+            delay,
+        }
+    }
+}
+
+/// 默认幂等分类：只读的 `query.*` 与 `fetch.*` 视为幂等
+/// Default idempotency classification: read-only `query.*` and `fetch.*` are idempotent
+pub(crate) fn is_idempotent_by_default(intent: &str) -> bool {
+    intent.starts_with("query.") || intent.starts_with("fetch.")
+}
+
+/// 重试原因 (用于指标标签) / Retry reason (used as a metrics label)
+#[cfg(feature = "metrics")]
+pub(crate) fn retry_reason(error: &AqaraError) -> &'static str {
+    match error {
+        AqaraError::Http(e) if e.is_timeout() => "timeout",
+        AqaraError::Http(e) if e.is_connect() => "connect",
+        AqaraError::Http(e) => match e.status() {
+            Some(status) if status.as_u16() == 429 => "429",
+            Some(status) if status.is_server_error() => "5xx",
+            _ => "other",
+        },
+        AqaraError::Api { .. } => "business_code",
+        _ => "other",
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Envelope {
+    code: i32,
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    request_id: String,
+}
+
+/// HTTP 成功但业务码可重试时返回对应的 `Api` 错误
+/// Returns the `Api` error for a successful HTTP response carrying a retryable business code
+pub(crate) fn retryable_business_error(body: &str) -> Option<AqaraError> {
+    let envelope: Envelope = serde_json::from_str(body).ok()?;
+    ErrorCode::from_code(envelope.code)
+        .is_retryable()
+        .then(|| AqaraError::Api {
+            code: envelope.code,
+            message: envelope.message,
+            request_id: envelope.request_id,
+        })
+}
+
+pub(crate) fn is_retryable(error: &AqaraError) -> bool {
+    let error = match error {
+        AqaraError::Http(e) => e,
+        AqaraError::Api { code, .. } => return ErrorCode::from_code(*code).is_retryable(),
+        _ => return false,
+    };
+    if error.is_timeout() || error.is_connect() {
+        return true;
+    }
+    match error.status() {
+        Some(status) => status.as_u16() == 429 || status.is_server_error(),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn throttle(max_retries: u32, window: Duration) -> RetryThrottle {
+        RetryThrottle::new(RetryBudget {
+            max_retries,
+            window,
+        })
+    }
+
+    #[test]
+    fn throttle_spends_up_to_the_budget() {
+        let throttle = throttle(2, Duration::from_secs(60));
+        assert!(throttle.try_spend());
+        assert!(throttle.try_spend());
+        assert!(!throttle.try_spend());
+        assert!(!throttle.try_spend());
+    }
+
+    #[test]
+    fn throttle_with_zero_budget_never_retries() {
+        assert!(!throttle(0, Duration::from_secs(60)).try_spend());
+    }
+
+    #[test]
+    fn throttle_refills_when_the_window_ends() {
+        let throttle = throttle(1, Duration::from_millis(20));
+        assert!(throttle.try_spend());
+        assert!(!throttle.try_spend());
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(throttle.try_spend());
+        assert!(!throttle.try_spend());
+    }
+
+    #[test]
+    fn retryable_business_codes_become_api_errors() {
+        let error = retryable_business_error(
+            r#"{"code":104,"message":"busy","requestId":"r1","result":null}"#,
+        )
+        .expect("server busy is retryable");
+        assert!(matches!(
+            &error,
+            AqaraError::Api { code: 104, message, request_id }
+                if message == "busy" && request_id == "r1"
+        ));
+        assert!(is_retryable(&error));
+        assert!(retryable_business_error(r#"{"code":500}"#).is_some());
+    }
+
+    #[test]
+    fn other_bodies_are_not_retried() {
+        assert!(retryable_business_error(r#"{"code":0,"result":{}}"#).is_none());
+        assert!(retryable_business_error(r#"{"code":108,"message":"expired"}"#).is_none());
+        assert!(retryable_business_error("not json").is_none());
+        assert!(!is_retryable(&AqaraError::Api {
+            code: 303,
+            message: String::new(),
+            request_id: String::new(),
+        }));
+    }
+}
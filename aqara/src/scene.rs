@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::util::{require_id, value_to_string};
+use crate::{AqaraClient, AqaraError};
+
+/// 场景动作 (Scene action)
+///
+/// 未建模的字段保存在 `extra` 中，以保证导出/导入往返不丢失信息。
+/// Fields that are not modelled are kept in `extra` so export/import
+/// round-trips lose nothing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneAction {
+    /// 设备ID / Subject (device) ID
+    pub subject_id: String,
+    /// 设备型号 (可选) / Device model (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// 资源ID (可选) / Resource ID (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_id: Option<String>,
+    /// 资源值 (可选) / Resource value (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// 延时 (可选) / Delay (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delay_time: Option<String>,
+    /// 延时单位 (可选) / Delay unit (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delay_time_unit: Option<String>,
+    /// 其他字段 / Other fields
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// 场景导出文档 (Scene export document)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneDocument {
+    /// 文档版本 / Document version
+    pub version: u32,
+    /// 场景名称 / Scene name
+    pub name: String,
+    /// 场景动作 / Scene actions
+    pub actions: Vec<SceneAction>,
+}
+
+impl SceneDocument {
+    /// 当前文档版本 / Current document version
+    pub const VERSION: u32 = 1;
+
+    /// 序列化为 JSON / Serialize into JSON
+    pub fn to_json_string(&self) -> Result<String, AqaraError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// 从 JSON 解析 / Parse from JSON
+    pub fn from_json_str(s: &str) -> Result<Self, AqaraError> {
+        Ok(serde_json::from_str(s)?)
+    }
+}
+
+impl AqaraClient {
+    /// 查询场景详情 (Query scene detail)
+    ///
+    /// intent: query.scene.detail
+    ///
+    /// # Parameters 参数
+    /// - `scene_id`: 场景ID / Scene ID
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn query_scene_detail(&self, scene_id: &str) -> Result<String, AqaraError> {
+        let data = json!({
+            "sceneId": scene_id
+        });
+        self.send_api_request("query.scene.detail", data, true)
+            .await
+    }
+
+    /// 执行场景 (Run scene)
+    ///
+    /// intent: config.scene.run
+    ///
+    /// # Parameters 参数
+    /// - `scene_id`: 场景ID / Scene ID
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn config_scene_run(&self, scene_id: &str) -> Result<String, AqaraError> {
+        let data = json!({
+            "sceneId": scene_id
+        });
+        self.send_api_request("config.scene.run", data, true).await
+    }
+
+    /// 创建场景 (Create scene)
+    ///
+    /// intent: config.scene.create
+    ///
+    /// # Parameters 参数
+    /// - `name`: 场景名称 / Scene name
+    /// - `position_id`: 位置ID / Position ID
+    /// - `actions`: 场景动作 / Scene actions
+    ///
+    /// # Returns
+    /// 成功返回字符串 / Returns response string on success
+    pub async fn config_scene_create(
+        &self,
+        name: &str,
+        position_id: &str,
+        actions: &[SceneAction],
+    ) -> Result<String, AqaraError> {
+        let data = json!({
+            "name": name,
+            "positionId": position_id,
+            "actions": actions
+        });
+        self.send_api_request("config.scene.create", data, true)
+            .await
+    }
+
+    /// 导出场景 (Export a scene)
+    ///
+    /// intent: query.scene.detail
+    ///
+    /// # Parameters 参数
+    /// - `scene_id`: 场景ID / Scene ID
+    ///
+    /// # Returns
+    /// 成功返回导出文档 / Returns the export document on success
+    pub async fn export_scene(&self, scene_id: &str) -> Result<SceneDocument, AqaraError> {
+        let detail = self
+            .call_api::<Value>("query.scene.detail", json!({ "sceneId": scene_id }), true)
+            .await?
+            .result
+            .unwrap_or(Value::Null);
+        let actions = match detail.get("actions") {
+            Some(actions) => serde_json::from_value(actions.clone())?,
+            None => Vec::new(),
+        };
+        Ok(SceneDocument {
+            version: SceneDocument::VERSION,
+            name: value_to_string(detail.get("name")),
+            actions,
+        })
+    }
+
+    /// 导入场景 (Import a scene)
+    ///
+    /// intent: config.scene.create
+    ///
+    /// 动作中的设备ID按 `subject_mapping` 重新映射，未映射的ID保持不变。
+    /// Subject ids in the actions are remapped through `subject_mapping`;
+    /// unmapped ids are kept as-is.
+    ///
+    /// # Parameters 参数
+    /// - `doc`: 导出文档 / Export document
+    /// - `target_position`: 目标位置ID / Target position ID
+    /// - `subject_mapping`: 设备ID映射 (旧 -> 新) / Subject id mapping (old -> new)
+    ///
+    /// # Returns
+    /// 成功返回新场景ID / Returns the new scene ID on success
+    pub async fn import_scene(
+        &self,
+        doc: &SceneDocument,
+        target_position: &str,
+        subject_mapping: &HashMap<String, String>,
+    ) -> Result<String, AqaraError> {
+        let actions: Vec<SceneAction> = doc
+            .actions
+            .iter()
+            .map(|action| {
+                let mut action = action.clone();
+                if let Some(subject_id) = subject_mapping.get(&action.subject_id) {
+                    action.subject_id = subject_id.clone();
+                }
+                action
+            })
+            .collect();
+        let data = json!({
+            "name": doc.name,
+            "positionId": target_position,
+            "actions": actions
+        });
+        let response = self
+            .call_api::<Value>("config.scene.create", data, true)
+            .await?;
+        require_id(response, "config.scene.create", "sceneId")
+    }
+
+    /// 复制场景 (Clone a scene)
+    ///
+    /// intent: query.scene.detail, config.scene.create
+    ///
+    /// 读取场景详情，改名并按 `subject_mapping` 重写设备ID后在目标位置创建副本，
+    /// 适合将灯光场景套用到其他房间。
+    /// Fetches the scene detail, renames it and rewrites subject ids through
+    /// `subject_mapping`, then creates the copy under the target position — handy
+    /// for templating lighting scenes across rooms.
+    ///
+    /// # Parameters 参数
+    /// - `scene_id`: 源场景ID / Source scene ID
+    /// - `new_name`: 新场景名称 / Name of the copy
+    /// - `target_position`: 目标位置ID / Target position ID
+    /// - `subject_mapping`: 设备ID映射 (旧 -> 新) / Subject id mapping (old -> new)
+    ///
+    /// # Returns
+    /// 成功返回新场景ID / Returns the new scene ID on success
+    pub async fn clone_scene(
+        &self,
+        scene_id: &str,
+        new_name: &str,
+        target_position: &str,
+        subject_mapping: &HashMap<String, String>,
+    ) -> Result<String, AqaraError> {
+        let doc = SceneDocument {
+            name: new_name.to_string(),
+            ..self.export_scene(scene_id).await?
+        };
+        self.import_scene(&doc, target_position, subject_mapping)
+            .await
+    }
+}
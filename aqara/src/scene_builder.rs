@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::{
+    AqaraClient, AqaraError, SceneAction, SceneDocument, COVER_CONTROL_RESOURCE_ID,
+    SWITCH_POWER_RESOURCE_ID,
+};
+
+/// 单个场景的最大动作数 (客户端限制) / Max actions per scene (client-side limit)
+pub const SCENE_MAX_ACTIONS: usize = 50;
+/// 单个动作的最长延时 (客户端限制) / Longest delay before one action (client-side limit)
+pub const SCENE_MAX_DELAY: Duration = Duration::from_secs(60 * 60);
+
+/// 场景构建器 (Scene builder)
+///
+/// 按顺序组合动作；`delay` 作用于其后添加的下一个动作。
+/// Composes actions in order; a `delay` applies to the next action added after it.
+#[derive(Debug, Clone)]
+pub struct SceneBuilder {
+    name: String,
+    actions: Vec<SceneAction>,
+    pending_delay: Option<Duration>,
+    problems: Vec<String>,
+}
+
+impl SceneBuilder {
+    /// 创建 (Create)
+    ///
+    /// # Parameters 参数
+    /// - `name`: 场景名称 / Scene name
+    pub fn new(name: &str) -> Self {
+        SceneBuilder {
+            name: name.to_string(),
+            actions: Vec::new(),
+            pending_delay: None,
+            problems: Vec::new(),
+        }
+    }
+
+    /// 全部关闭模板 (All-off template)
+    ///
+    /// # Parameters 参数
+    /// - `name`: 场景名称 / Scene name
+    /// - `dids`: 需要关闭的开关、插座或灯 / Switches, plugs or lights to turn off
+    pub fn all_off(name: &str, dids: &[&str]) -> Self {
+        dids.iter().fold(SceneBuilder::new(name), |builder, did| {
+            builder.turn_off(did)
+        })
+    }
+
+    /// 回家模板：打开灯光并打开窗帘 (Arrival template: lights on, curtains open)
+    ///
+    /// # Parameters 参数
+    /// - `name`: 场景名称 / Scene name
+    /// - `light_dids`: 需要打开的灯或开关 / Lights or switches to turn on
+    /// - `cover_dids`: 需要打开的窗帘 / Curtains to open
+    pub fn arrival(name: &str, light_dids: &[&str], cover_dids: &[&str]) -> Self {
+        let builder = light_dids
+            .iter()
+            .fold(SceneBuilder::new(name), |builder, did| builder.turn_on(did));
+        cover_dids
+            .iter()
+            .fold(builder, |builder, did| builder.open_cover(did))
+    }
+
+    /// 离家模板：关闭电器并关闭窗帘 (Leave-home template: power off, curtains closed)
+    ///
+    /// # Parameters 参数
+    /// - `name`: 场景名称 / Scene name
+    /// - `power_dids`: 需要关闭的开关、插座或灯 / Switches, plugs or lights to turn off
+    /// - `cover_dids`: 需要关闭的窗帘 / Curtains to close
+    pub fn leave_home(name: &str, power_dids: &[&str], cover_dids: &[&str]) -> Self {
+        cover_dids
+            .iter()
+            .fold(SceneBuilder::all_off(name, power_dids), |builder, did| {
+                builder.close_cover(did)
+            })
+    }
+
+    /// 写入资源值 (Write a resource value)
+    ///
+    /// # Parameters 参数
+    /// - `subject_id`: 设备ID / Subject (device) ID
+    /// - `resource_id`: 资源ID / Resource ID
+    /// - `value`: 值 / Value
+    pub fn write(self, subject_id: &str, resource_id: &str, value: &str) -> Self {
+        self.action(SceneAction {
+            subject_id: subject_id.to_string(),
+            model: None,
+            resource_id: Some(resource_id.to_string()),
+            value: Some(value.to_string()),
+            delay_time: None,
+            delay_time_unit: None,
+            extra: HashMap::new(),
+        })
+    }
+
+    /// 打开开关 / Turn a switch on
+    pub fn turn_on(self, did: &str) -> Self {
+        self.write(did, SWITCH_POWER_RESOURCE_ID, "1")
+    }
+
+    /// 关闭开关 / Turn a switch off
+    pub fn turn_off(self, did: &str) -> Self {
+        self.write(did, SWITCH_POWER_RESOURCE_ID, "0")
+    }
+
+    /// 打开窗帘 / Open a curtain
+    pub fn open_cover(self, did: &str) -> Self {
+        self.write(did, COVER_CONTROL_RESOURCE_ID, "1")
+    }
+
+    /// 关闭窗帘 / Close a curtain
+    pub fn close_cover(self, did: &str) -> Self {
+        self.write(did, COVER_CONTROL_RESOURCE_ID, "0")
+    }
+
+    /// 在下一个动作前延时 (Delay before the next action)
+    ///
+    /// 连续调用会累加。延时须为整秒，且不超过 `SCENE_MAX_DELAY`。
+    /// Consecutive calls add up. The delay must be whole seconds and at most
+    /// `SCENE_MAX_DELAY`.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.pending_delay = Some(self.pending_delay.unwrap_or_default() + delay);
+        self
+    }
+
+    /// 添加原始动作 (Add a raw action)
+    ///
+    /// 尚未应用的延时会覆盖动作自带的延时。
+    /// A pending delay overrides the action's own delay.
+    pub fn action(mut self, mut action: SceneAction) -> Self {
+        if let Some(delay) = self.pending_delay.take() {
+            let index = self.actions.len();
+            if delay.subsec_nanos() != 0 {
+                self.problems
+                    .push(format!("action {}: delay must be whole seconds", index));
+            } else if delay > SCENE_MAX_DELAY {
+                self.problems.push(format!(
+                    "action {}: delay {}s exceeds {}s",
+                    index,
+                    delay.as_secs(),
+                    SCENE_MAX_DELAY.as_secs()
+                ));
+            }
+            let (time, unit) = delay_fields(delay);
+            action.delay_time = Some(time);
+            action.delay_time_unit = Some(unit.to_string());
+        }
+        self.actions.push(action);
+        self
+    }
+
+    /// 校验并构建 (Validate and build)
+    ///
+    /// 一次性收集所有问题，返回 `AqaraError::InvalidParams`。
+    /// Collects every problem and returns them as `AqaraError::InvalidParams`.
+    pub fn build(self) -> Result<SceneDocument, AqaraError> {
+        let mut problems = self.problems;
+        if self.name.trim().is_empty() {
+            problems.push("name is required".to_string());
+        }
+        if self.actions.is_empty() {
+            problems.push("at least one action is required".to_string());
+        }
+        if self.actions.len() > SCENE_MAX_ACTIONS {
+            problems.push(format!(
+                "{} actions exceed the limit of {}",
+                self.actions.len(),
+                SCENE_MAX_ACTIONS
+            ));
+        }
+        if self.pending_delay.is_some() {
+            problems.push("delay is not followed by an action".to_string());
+        }
+        for (i, action) in self.actions.iter().enumerate() {
+            if action.subject_id.is_empty() {
+                problems.push(format!("action {}: subject_id is required", i));
+            }
+        }
+        if !problems.is_empty() {
+            return Err(AqaraError::InvalidParams(problems));
+        }
+        Ok(SceneDocument {
+            version: SceneDocument::VERSION,
+            name: self.name,
+            actions: self.actions,
+        })
+    }
+
+    /// 校验并创建场景 (Validate and create the scene)
+    ///
+    /// intent: config.scene.create
+    ///
+    /// # Parameters 参数
+    /// - `client`: 客户端 / Client
+    /// - `position_id`: 位置ID / Position ID
+    ///
+    /// # Returns
+    /// 成功返回新场景ID / Returns the new scene ID on success
+    pub async fn create(
+        self,
+        client: &AqaraClient,
+        position_id: &str,
+    ) -> Result<String, AqaraError> {
+        let doc = self.build()?;
+        client
+            .import_scene(&doc, position_id, &HashMap::new())
+            .await
+    }
+}
+
+// 延时单位 (0 秒, 1 分钟) / Delay unit (0 seconds, 1 minutes)
+fn delay_fields(delay: Duration) -> (String, &'static str) {
+    let secs = delay.as_secs();
+    if secs != 0 && secs.is_multiple_of(60) {
+        ((secs / 60).to_string(), "1")
+    } else {
+        (secs.to_string(), "0")
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::oneshot;
+
+/// 请求优先级 (Request priority)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Priority {
+    /// 交互式请求，例如设备控制 / Interactive calls such as device commands
+    Interactive,
+    /// 后台批量任务，例如历史同步、OTA 轮询 / Background bulk jobs such as history sync or OTA polling
+    Background,
+}
+
+impl Priority {
+    /// 默认分类：`fetch.*` 与 OTA 查询为后台，其余为交互
+    /// Default classification: `fetch.*` and OTA queries are background, the rest interactive
+    pub(crate) fn default_for(intent: &str) -> Self {
+        if intent.starts_with("fetch.") || intent.starts_with("query.ota.") {
+            Priority::Background
+        } else {
+            Priority::Interactive
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    in_flight: usize,
+    interactive: VecDeque<oneshot::Sender<()>>,
+    background: VecDeque<oneshot::Sender<()>>,
+}
+
+/// 带优先级的并发限制器 (Priority-aware concurrency limiter)
+///
+/// 并发已满时，释放的名额总是先交给等待中的交互式请求。
+/// When saturated, a released slot always goes to a waiting interactive call first.
+#[derive(Clone)]
+pub(crate) struct Scheduler {
+    max_concurrency: usize,
+    state: Arc<Mutex<State>>,
+}
+
+pub(crate) struct Permit {
+    state: Arc<Mutex<State>>,
+}
+
+impl Scheduler {
+    pub(crate) fn new(max_concurrency: usize) -> Self {
+        Scheduler {
+            max_concurrency: max_concurrency.max(1),
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+
+    pub(crate) async fn acquire(&self, priority: Priority) -> Permit {
+        let receiver = {
+            let mut state = self.state.lock().unwrap();
+            if state.in_flight < self.max_concurrency {
+                state.in_flight += 1;
+                None
+            } else {
+                let (sender, receiver) = oneshot::channel();
+                match priority {
+                    Priority::Interactive => state.interactive.push_back(sender),
+                    Priority::Background => state.background.push_back(sender),
+                }
+                Some(receiver)
+            }
+        };
+        if let Some(receiver) = receiver {
+            // 名额由释放方直接转交 / The slot is handed over directly by the releasing permit
+            let mut waiting = Waiting {
+                receiver,
+                state: self.state.clone(),
+                done: false,
+            };
+            let _ = (&mut waiting.receiver).await;
+            waiting.done = true;
+        }
+        Permit {
+            state: self.state.clone(),
+        }
+    }
+}
+
+// 等待中被取消时归还已转交的名额 / Returns a handed-over slot if the waiter is cancelled
+struct Waiting {
+    receiver: oneshot::Receiver<()>,
+    state: Arc<Mutex<State>>,
+    done: bool,
+}
+
+impl Drop for Waiting {
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+        self.receiver.close();
+        if self.receiver.try_recv().is_ok() {
+            drop(Permit {
+                state: self.state.clone(),
+            });
+        }
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            let next = match state.interactive.pop_front() {
+                Some(sender) => Some(sender),
+                None => state.background.pop_front(),
+            };
+            match next {
+                // 等待方已取消时继续尝试下一个 / Skip waiters that were cancelled
+                Some(sender) => {
+                    if sender.send(()).is_ok() {
+                        return;
+                    }
+                }
+                None => {
+                    state.in_flight -= 1;
+                    return;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::{AqaraClient, Region, UserTokens};
+
+/// 用户会话 (User session)
+///
+/// 表示一个已授权的 Aqara 用户，可序列化后持久化，供多租户后端使用。
+/// Represents one authorized Aqara user and can be serialized for persistence
+/// by multi-tenant backends.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    /// 用户账户 / User account
+    pub account: String,
+    /// 用户 openId (可选) / User openId (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_id: Option<String>,
+    /// 令牌 (含过期时间) / Tokens (including their expiry)
+    pub tokens: UserTokens,
+    /// 服务区域 / Service region
+    pub region: Region,
+}
+
+impl Session {
+    /// 令牌表中使用的用户键 (优先 openId) / User key in the token map (openId preferred)
+    pub fn key(&self) -> &str {
+        self.open_id.as_deref().unwrap_or(&self.account)
+    }
+
+    /// 访问令牌是否已过期 / Whether the access token has expired
+    pub fn is_expired(&self) -> bool {
+        self.expires_within(chrono::Duration::zero())
+    }
+
+    /// 访问令牌是否会在 `margin` 内过期 / Whether the access token expires within `margin`
+    pub fn expires_within(&self, margin: chrono::Duration) -> bool {
+        self.tokens
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= Utc::now() + margin)
+    }
+}
+
+impl AqaraClient {
+    /// 以会话用户身份调用 (Act as a session's user)
+    ///
+    /// 将会话令牌写入令牌表并返回对应的 `as_user` 视图。
+    /// 会话区域应与客户端接口地址一致，跨区域请使用对应区域构建的客户端。
+    /// Writes the session tokens into the token map and returns the matching
+    /// `as_user` view. The session region should match the client's endpoint;
+    /// use a client built for that region otherwise.
+    pub fn as_session(&self, session: &Session) -> AqaraClient {
+        self.tokens().insert(session.key(), session.tokens.clone());
+        self.as_user(session.key())
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use tokio::sync::{watch, Notify};
+
+use crate::{AqaraClient, AqaraError};
+
+/// 客户端生命周期状态 (Client lifecycle state)
+///
+/// 记录进行中的请求数，用于关闭时等待或取消。
+/// Tracks in-flight calls so shutdown can wait for or cancel them.
+pub(crate) struct Lifecycle {
+    closed: AtomicBool,
+    in_flight: AtomicUsize,
+    idle: Notify,
+    closing: watch::Sender<bool>,
+    cancel: watch::Sender<bool>,
+}
+
+pub(crate) struct InFlight<'a> {
+    lifecycle: &'a Lifecycle,
+}
+
+impl Default for Lifecycle {
+    fn default() -> Self {
+        Lifecycle {
+            closed: AtomicBool::new(false),
+            in_flight: AtomicUsize::new(0),
+            idle: Notify::new(),
+            closing: watch::channel(false).0,
+            cancel: watch::channel(false).0,
+        }
+    }
+}
+
+impl Lifecycle {
+    // 关闭后拒绝新请求 / Reject new calls once closed
+    pub(crate) fn enter(&self) -> Result<InFlight<'_>, AqaraError> {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        let guard = InFlight { lifecycle: self };
+        if self.closed.load(Ordering::SeqCst) {
+            return Err(AqaraError::Shutdown);
+        }
+        Ok(guard)
+    }
+
+    // 开始关闭时完成，供后台任务及时退出 / Completes once shutdown starts, so background tasks exit promptly
+    pub(crate) async fn closed(&self) {
+        let mut closing = self.closing.subscribe();
+        let _ = closing.wait_for(|closing| *closing).await;
+    }
+
+    // 在截止时间被取消时完成 / Completes when calls are cancelled at the deadline
+    pub(crate) async fn cancelled(&self) {
+        let mut cancel = self.cancel.subscribe();
+        let _ = cancel.wait_for(|cancelled| *cancelled).await;
+    }
+
+    async fn wait_idle(&self) {
+        loop {
+            let idle = self.idle.notified();
+            if self.in_flight.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+            idle.await;
+        }
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        if self.lifecycle.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.lifecycle.idle.notify_waiters();
+        }
+    }
+}
+
+impl AqaraClient {
+    /// 优雅关闭 (Graceful shutdown)
+    ///
+    /// 立即拒绝新请求 (返回 `AqaraError::Shutdown`)，等待进行中的请求及其重试完成；
+    /// 超过 `grace` 后取消剩余请求。客户端没有离线队列，因此无需额外刷新。
+    /// Immediately rejects new calls (with `AqaraError::Shutdown`) and waits for
+    /// in-flight calls and their retries; whatever is still running after `grace`
+    /// is cancelled. The client keeps no offline queue, so there is nothing else to flush.
+    ///
+    /// # Returns
+    /// 所有请求在宽限期内完成时返回 `true` / Returns `true` if every call finished within `grace`
+    pub async fn shutdown(&self, grace: Duration) -> bool {
+        self.inner.lifecycle.closed.store(true, Ordering::SeqCst);
+        self.inner.lifecycle.closing.send_replace(true);
+        if tokio::time::timeout(grace, self.inner.lifecycle.wait_idle())
+            .await
+            .is_ok()
+        {
+            return true;
+        }
+        self.inner.lifecycle.cancel.send_replace(true);
+        self.inner.lifecycle.wait_idle().await;
+        false
+    }
+
+    /// 是否已关闭 / Whether the client has been shut down
+    pub fn is_shutdown(&self) -> bool {
+        self.inner.lifecycle.closed.load(Ordering::SeqCst)
+    }
+}
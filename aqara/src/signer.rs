@@ -0,0 +1,105 @@
+use rand::distr::Alphanumeric;
+use rand::Rng;
+
+/// 参与签名的请求头 (Signed request headers)
+///
+/// 签名为 `md5(lowercase(canonical_string() + appKey))`。
+/// The signature is `md5(lowercase(canonical_string() + appKey))`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignatureParts<'a> {
+    /// 访问令牌 (不携带时为 `None`) / Access token (`None` when not sent)
+    pub access_token: Option<&'a str>,
+    pub app_id: &'a str,
+    pub key_id: &'a str,
+    pub nonce: &'a str,
+    /// 毫秒时间戳 / Timestamp in milliseconds
+    pub time: &'a str,
+}
+
+impl SignatureParts<'_> {
+    /// 待签名字符串 (不含 appKey) (String to sign, without the appKey)
+    pub fn canonical_string(&self) -> String {
+        let mut sign_str = String::new();
+
+        // 决定是否加入Accesstoken / Decide whether to include Accesstoken
+        if let Some(access_token) = self.access_token.filter(|token| !token.is_empty()) {
+            sign_str.push_str(&format!("Accesstoken={}&", access_token));
+        }
+        sign_str.push_str(&format!(
+            "Appid={}&Keyid={}&Nonce={}&Time={}",
+            self.app_id, self.key_id, self.nonce, self.time
+        ));
+        sign_str
+    }
+
+    /// 使用 appKey 计算签名 (Sign with an appKey)
+    pub fn sign_with(&self, app_key: &str) -> String {
+        let sign_str = (self.canonical_string() + app_key).to_lowercase();
+        format!("{:x}", md5::compute(sign_str.as_bytes()))
+    }
+}
+
+/// 请求签名策略 (Request signing strategy)
+///
+/// 默认使用配置中的 appKey 签名、随机 Nonce 与系统时间；可替换为由 HSM 保管密钥的实现，
+/// 或在测试中固定 Nonce 与时间。开启时钟校正时，偏移叠加在 `time_millis` 之上。
+/// By default requests are signed with the configured appKey, a random nonce and
+/// the system time; substitute an implementation whose key lives in an HSM, or
+/// one with a fixed nonce and time for tests. With clock-skew correction on, the
+/// offset is added on top of `time_millis`.
+pub trait Signer: Send + Sync {
+    /// 生成 Nonce / Generate a nonce
+    fn nonce(&self) -> String {
+        random_nonce()
+    }
+
+    /// 当前毫秒时间戳 / Current timestamp in milliseconds
+    fn time_millis(&self) -> i64 {
+        chrono::Utc::now().timestamp_millis()
+    }
+
+    /// 计算签名 / Compute the signature
+    fn sign(&self, parts: &SignatureParts<'_>) -> String;
+}
+
+/// 使用 appKey 的签名器 (Signer holding an appKey)
+///
+/// 与客户端默认行为一致，便于只替换 Nonce 或时间。
+/// Behaves like the client default, handy when only the nonce or time should change.
+#[derive(Clone)]
+pub struct AppKeySigner {
+    app_key: String,
+}
+
+impl AppKeySigner {
+    /// 创建 (Create)
+    ///
+    /// # Parameters 参数
+    /// - `app_key`: 应用密钥 / App key
+    pub fn new(app_key: impl Into<String>) -> Self {
+        AppKeySigner {
+            app_key: app_key.into(),
+        }
+    }
+}
+
+impl std::fmt::Debug for AppKeySigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AppKeySigner").finish_non_exhaustive()
+    }
+}
+
+impl Signer for AppKeySigner {
+    fn sign(&self, parts: &SignatureParts<'_>) -> String {
+        parts.sign_with(&self.app_key)
+    }
+}
+
+/// 生成 30 位随机 Nonce / Generate a random 30-character nonce
+pub(crate) fn random_nonce() -> String {
+    rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(30)
+        .map(char::from)
+        .collect()
+}
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use futures_util::future::{BoxFuture, FutureExt, Shared};
+use serde_json::Value;
+
+use crate::{AqaraClient, AqaraError};
+
+type SharedResult = Shared<BoxFuture<'static, Result<String, Arc<AqaraError>>>>;
+
+/// 合并并发的相同请求 (Coalesce identical concurrent requests)
+///
+/// 同一用户以相同参数并发调用同一幂等 intent 时只发出一次请求，所有调用方共享结果。
+/// Concurrent calls of the same idempotent intent with the same params by the
+/// same user send one request and share its result.
+#[derive(Default)]
+pub(crate) struct SingleFlight {
+    in_flight: Mutex<HashMap<String, SharedResult>>,
+}
+
+impl AqaraClient {
+    pub(crate) async fn send_coalesced(
+        &self,
+        intent: &str,
+        data: Value,
+        include_access_token: bool,
+    ) -> Result<String, AqaraError> {
+        // 调用选项可能改变响应，不参与合并 / Call options may change the response, so skip them
+        let single_flight = match &self.inner.single_flight {
+            Some(single_flight) if self.options.is_none() && self.is_idempotent(intent) => {
+                single_flight
+            }
+            _ => {
+                return self
+                    .send_api_request_guarded(intent, &data, include_access_token)
+                    .await
+            }
+        };
+
+        let key = format!(
+            "{}\n{}\n{}\n{}",
+            self.user.as_deref().unwrap_or_default(),
+            intent,
+            include_access_token,
+            data
+        );
+        let shared = single_flight
+            .in_flight
+            .lock()
+            .unwrap()
+            .entry(key.clone())
+            .or_insert_with(|| {
+                let client = self.clone();
+                let intent = intent.to_string();
+                async move {
+                    client
+                        .send_api_request_guarded(&intent, &data, include_access_token)
+                        .await
+                        .map_err(Arc::new)
+                }
+                .boxed()
+                .shared()
+            })
+            .clone();
+
+        let result = shared.clone().await;
+        let mut in_flight = single_flight.in_flight.lock().unwrap();
+        if in_flight
+            .get(&key)
+            .is_some_and(|current| current.ptr_eq(&shared))
+        {
+            in_flight.remove(&key);
+        }
+        drop(in_flight);
+        drop(shared);
+        result.map_err(|e| Arc::try_unwrap(e).unwrap_or_else(AqaraError::Shared))
+    }
+}
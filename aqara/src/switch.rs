@@ -0,0 +1,136 @@
+use crate::{AqaraError, Device};
+
+/// 开关资源ID (第一路，0 关, 1 开) / Switch resource ID (first channel, 0 off, 1 on)
+pub const SWITCH_POWER_RESOURCE_ID: &str = "4.1.85";
+
+/// 开关参数 (Switch profile)
+///
+/// 描述某型号每一路开关使用的资源ID，按通道顺序排列。
+/// Describes the resource id of each switch channel of a model, in channel order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwitchProfile {
+    /// 各通道资源ID / Resource ID per channel
+    pub channels: &'static [&'static str],
+}
+
+impl Default for SwitchProfile {
+    fn default() -> Self {
+        SwitchProfile {
+            channels: &[SWITCH_POWER_RESOURCE_ID],
+        }
+    }
+}
+
+impl SwitchProfile {
+    /// 常见开关型号的参数 (Profile for common switch models)
+    ///
+    /// 覆盖智能插座与单/双/三键墙壁开关；未知型号返回 `None`。
+    /// Covers smart plugs and one/two/three-gang wall switches; unknown models
+    /// return `None`.
+    pub fn for_model(model: &str) -> Option<Self> {
+        let channels: &'static [&'static str] = match model {
+            "lumi.plug.v1"
+            | "lumi.plug.maeu01"
+            | "lumi.plug.macn01"
+            | "lumi.switch.b1lacn02"
+            | "lumi.switch.b1nacn02"
+            | "lumi.switch.l1acn1"
+            | "lumi.switch.n1acn1" => &["4.1.85"],
+            "lumi.switch.b2lacn02"
+            | "lumi.switch.b2nacn02"
+            | "lumi.switch.l2acn1"
+            | "lumi.switch.n2acn1" => &["4.1.85", "4.2.85"],
+            "lumi.switch.l3acn1" | "lumi.switch.n3acn1" => &["4.1.85", "4.2.85", "4.3.85"],
+            _ => return None,
+        };
+        Some(SwitchProfile { channels })
+    }
+}
+
+/// 开关控制 (Switch control)
+///
+/// 通过 `Device::switch` 获取，适用于插座、墙壁开关与继电器。通道从 0 开始编号。
+/// Obtained through `Device::switch`; works for plugs, wall switches and relays.
+/// Channels are numbered from 0.
+pub struct SwitchControl<'a> {
+    device: &'a Device,
+    profile: SwitchProfile,
+}
+
+impl Device {
+    /// 开关控制 (按设备型号选择参数，未知时使用默认参数)
+    /// Switch control (profile chosen by the device model, the default when unknown)
+    pub fn switch(&self) -> SwitchControl<'_> {
+        let profile = self
+            .model()
+            .and_then(SwitchProfile::for_model)
+            .unwrap_or_default();
+        self.switch_with(profile)
+    }
+
+    /// 开关控制 (指定参数) / Switch control with a specific profile
+    ///
+    /// # Parameters 参数
+    /// - `profile`: 开关参数 / Switch profile
+    pub fn switch_with(&self, profile: SwitchProfile) -> SwitchControl<'_> {
+        SwitchControl {
+            device: self,
+            profile,
+        }
+    }
+}
+
+impl SwitchControl<'_> {
+    /// 通道数 / Number of channels
+    pub fn channels(&self) -> usize {
+        self.profile.channels.len()
+    }
+
+    /// 打开第一路 / Turn the first channel on
+    ///
+    /// intent: write.resource.device
+    pub async fn turn_on(&self) -> Result<(), AqaraError> {
+        self.set_on(0, true).await
+    }
+
+    /// 关闭第一路 / Turn the first channel off
+    ///
+    /// intent: write.resource.device
+    pub async fn turn_off(&self) -> Result<(), AqaraError> {
+        self.set_on(0, false).await
+    }
+
+    /// 设置指定通道 (Switch a channel)
+    ///
+    /// intent: write.resource.device
+    ///
+    /// # Parameters 参数
+    /// - `channel`: 通道 (从 0 开始) / Channel (0-based)
+    /// - `on`: 是否打开 / Whether to turn it on
+    pub async fn set_on(&self, channel: usize, on: bool) -> Result<(), AqaraError> {
+        let resource_id = self.resource_id(channel)?;
+        let value = if on { "1" } else { "0" };
+        self.device.write(&[(resource_id, value)]).await
+    }
+
+    /// 读取指定通道状态 (Read a channel's state)
+    ///
+    /// intent: query.resource.value
+    ///
+    /// # Returns
+    /// 成功返回是否打开，未上报时为 `None` / Returns whether it is on, or `None` when not reported
+    pub async fn is_on(&self, channel: usize) -> Result<Option<bool>, AqaraError> {
+        let resource_id = self.resource_id(channel)?;
+        let values = self.device.read(&[resource_id]).await?;
+        Ok(values.get(resource_id).map(|raw| raw == "1"))
+    }
+
+    fn resource_id(&self, channel: usize) -> Result<&'static str, AqaraError> {
+        self.profile.channels.get(channel).copied().ok_or_else(|| {
+            AqaraError::InvalidParams(vec![format!(
+                "channel {channel} is out of range; the switch has {} channel(s)",
+                self.profile.channels.len()
+            )])
+        })
+    }
+}
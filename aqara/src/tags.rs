@@ -0,0 +1,138 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::RwLock;
+
+use futures_util::stream::{self, StreamExt};
+
+use crate::{AqaraClient, AqaraError, Device};
+
+/// 本地设备标签表 (Local device tag map)
+///
+/// 在本地为设备打标签 (如 "outdoor")，弥补云端分组的不足。仅保存在内存中，
+/// 通过 `snapshot`/`restore` 由调用方自行持久化。
+/// Tags devices locally (e.g. "outdoor") where server-side grouping falls short.
+/// Kept in memory only; callers persist it through `snapshot`/`restore`.
+#[derive(Debug, Default)]
+pub struct TagMap {
+    tags: RwLock<BTreeMap<String, BTreeSet<String>>>,
+}
+
+impl TagMap {
+    /// 为设备添加标签 / Tag a device
+    pub fn tag(&self, did: &str, tag: &str) {
+        self.tags
+            .write()
+            .unwrap()
+            .entry(tag.to_string())
+            .or_default()
+            .insert(did.to_string());
+    }
+
+    /// 移除设备的标签 / Untag a device
+    pub fn untag(&self, did: &str, tag: &str) {
+        let mut tags = self.tags.write().unwrap();
+        if let Some(dids) = tags.get_mut(tag) {
+            dids.remove(did);
+            if dids.is_empty() {
+                tags.remove(tag);
+            }
+        }
+    }
+
+    /// 带有标签的设备ID / Device IDs carrying a tag
+    pub fn dids(&self, tag: &str) -> Vec<String> {
+        self.tags
+            .read()
+            .unwrap()
+            .get(tag)
+            .map(|dids| dids.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// 设备的全部标签 / All tags of a device
+    pub fn tags_of(&self, did: &str) -> Vec<String> {
+        self.tags
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(_, dids)| dids.contains(did))
+            .map(|(tag, _)| tag.clone())
+            .collect()
+    }
+
+    /// 已使用的标签 / Tags in use
+    pub fn tags(&self) -> Vec<String> {
+        self.tags.read().unwrap().keys().cloned().collect()
+    }
+
+    /// 导出 标签 -> 设备ID (可序列化) / Export tag -> device IDs (serializable)
+    pub fn snapshot(&self) -> BTreeMap<String, BTreeSet<String>> {
+        self.tags.read().unwrap().clone()
+    }
+
+    /// 用导出的内容替换当前标签 / Replace the current tags with an export
+    pub fn restore(&self, snapshot: BTreeMap<String, BTreeSet<String>>) {
+        *self.tags.write().unwrap() = snapshot;
+    }
+}
+
+/// 按标签批量写入中单个设备的结果 (Per-device result of a tagged write)
+#[derive(Debug)]
+pub struct TaggedWrite {
+    /// 设备ID / Device ID
+    pub did: String,
+    /// 写入结果 / Write result
+    pub result: Result<(), AqaraError>,
+}
+
+impl AqaraClient {
+    /// 本地设备标签表 / The local device tag map
+    pub fn tags(&self) -> &TagMap {
+        &self.inner.tags
+    }
+
+    /// 带有标签的设备句柄 / Device handles carrying a tag
+    ///
+    /// # Parameters 参数
+    /// - `tag`: 标签 / Tag
+    pub fn tagged(&self, tag: &str) -> Vec<Device> {
+        self.inner
+            .tags
+            .dids(tag)
+            .iter()
+            .map(|did| self.device(did))
+            .collect()
+    }
+
+    /// 按标签批量写入资源 (Write resources to every tagged device)
+    ///
+    /// intent: write.resource.device
+    ///
+    /// 最多 `concurrency` 个设备同时写入；单个失败不影响其他，结果按设备ID顺序返回。
+    /// Writes with at most `concurrency` devices in flight; one failure does not
+    /// stop the others, and results come back in device ID order.
+    ///
+    /// # Parameters 参数
+    /// - `tag`: 标签 / Tag
+    /// - `values`: (资源ID, 值) 列表 / (resource id, value) pairs
+    /// - `concurrency`: 并发数 (0 视为 1) / Concurrent writes (0 is treated as 1)
+    ///
+    /// # Returns
+    /// 每个设备的写入结果 / A write result per device
+    pub async fn write_tagged(
+        &self,
+        tag: &str,
+        values: &[(&str, &str)],
+        concurrency: usize,
+    ) -> Vec<TaggedWrite> {
+        stream::iter(self.tagged(tag))
+            .map(|device| async move {
+                TaggedWrite {
+                    did: device.did().to_string(),
+                    result: device.write(values).await,
+                }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+}
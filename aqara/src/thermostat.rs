@@ -0,0 +1,335 @@
+use std::fmt;
+
+use chrono::{NaiveTime, Timelike, Weekday};
+
+use crate::{AqaraError, Device, Reading};
+
+/// 目标温度资源ID (0.01 °C) / Target temperature resource ID (0.01 °C)
+pub const THERMOSTAT_TARGET_RESOURCE_ID: &str = "1.8.85";
+/// 每个日程的时段数 / Segments per schedule
+pub const THERMOSTAT_SCHEDULE_SEGMENTS: usize = 4;
+/// 最低设定温度 (°C) / Lowest set point (°C)
+pub const THERMOSTAT_MIN_TEMPERATURE: f64 = 5.0;
+/// 最高设定温度 (°C) / Highest set point (°C)
+pub const THERMOSTAT_MAX_TEMPERATURE: f64 = 30.0;
+
+/// 日程时段 (Schedule segment)
+///
+/// 从 `start` 开始保持 `temperature`，直到下一个时段开始。
+/// Holds `temperature` from `start` until the next segment begins.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScheduleSegment {
+    /// 开始时间 (精确到分钟) / Start time (minute precision)
+    pub start: NaiveTime,
+    /// 设定温度 (°C，步长 0.5) / Set point (°C, 0.5 steps)
+    pub temperature: f64,
+}
+
+/// 温控器周日程 (Thermostat weekly schedule)
+///
+/// 编码为资源值 `mon,tue|8:00,21.0|12:00,18.0|18:00,21.0|23:00,16.0`：
+/// 生效的星期，后接按时间排序的时段。
+/// Encoded into the resource value `mon,tue|8:00,21.0|12:00,18.0|18:00,21.0|23:00,16.0`:
+/// the days it applies to followed by the segments in time order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThermostatSchedule {
+    days: Vec<Weekday>,
+    segments: Vec<ScheduleSegment>,
+}
+
+/// 温控器周日程构建器 (Thermostat weekly schedule builder)
+#[derive(Debug, Clone, Default)]
+pub struct ThermostatScheduleBuilder {
+    days: Vec<Weekday>,
+    segments: Vec<ScheduleSegment>,
+}
+
+impl ThermostatScheduleBuilder {
+    /// 添加生效的星期 / Add the days the schedule applies to
+    pub fn days(mut self, days: &[Weekday]) -> Self {
+        self.days.extend_from_slice(days);
+        self
+    }
+
+    /// 添加时段 (Add a segment)
+    ///
+    /// # Parameters 参数
+    /// - `start`: 开始时间 / Start time
+    /// - `temperature`: 设定温度 (°C) / Set point (°C)
+    pub fn segment(mut self, start: NaiveTime, temperature: f64) -> Self {
+        self.segments.push(ScheduleSegment { start, temperature });
+        self
+    }
+
+    /// 校验并构建 (Validate and build)
+    ///
+    /// 一次性收集所有问题，返回 `AqaraError::InvalidParams`。
+    /// Collects every problem and returns them as `AqaraError::InvalidParams`.
+    pub fn build(self) -> Result<ThermostatSchedule, AqaraError> {
+        let mut problems = Vec::new();
+        if self.days.is_empty() {
+            problems.push("at least one day is required".to_string());
+        }
+        for (i, day) in self.days.iter().enumerate() {
+            if self.days[..i].contains(day) {
+                problems.push(format!("day {} is listed more than once", day_name(*day)));
+            }
+        }
+        if self.segments.len() != THERMOSTAT_SCHEDULE_SEGMENTS {
+            problems.push(format!(
+                "expected {} segments, got {}",
+                THERMOSTAT_SCHEDULE_SEGMENTS,
+                self.segments.len()
+            ));
+        }
+        for (i, segment) in self.segments.iter().enumerate() {
+            if segment.start.second() != 0 || segment.start.nanosecond() != 0 {
+                problems.push(format!("segment {}: start must be on a whole minute", i));
+            }
+            if !(THERMOSTAT_MIN_TEMPERATURE..=THERMOSTAT_MAX_TEMPERATURE)
+                .contains(&segment.temperature)
+            {
+                problems.push(format!(
+                    "segment {}: temperature {} is outside {}-{}",
+                    i, segment.temperature, THERMOSTAT_MIN_TEMPERATURE, THERMOSTAT_MAX_TEMPERATURE
+                ));
+            } else if (segment.temperature * 2.0).fract() != 0.0 {
+                problems.push(format!(
+                    "segment {}: temperature {} is not a multiple of 0.5",
+                    i, segment.temperature
+                ));
+            }
+            if let Some(previous) = i.checked_sub(1).map(|p| self.segments[p]) {
+                if segment.start <= previous.start {
+                    problems.push(format!(
+                        "segment {}: starts at {} which overlaps segment {} starting at {}",
+                        i,
+                        segment.start.format("%H:%M"),
+                        i - 1,
+                        previous.start.format("%H:%M")
+                    ));
+                }
+            }
+        }
+        if !problems.is_empty() {
+            return Err(AqaraError::InvalidParams(problems));
+        }
+        Ok(ThermostatSchedule {
+            days: self.days,
+            segments: self.segments,
+        })
+    }
+}
+
+impl ThermostatSchedule {
+    /// 创建构建器 / Create a builder
+    pub fn builder() -> ThermostatScheduleBuilder {
+        ThermostatScheduleBuilder::default()
+    }
+
+    /// 生效的星期 / Days the schedule applies to
+    pub fn days(&self) -> &[Weekday] {
+        &self.days
+    }
+
+    /// 时段 / Segments
+    pub fn segments(&self) -> &[ScheduleSegment] {
+        &self.segments
+    }
+
+    /// 编码为资源值 / Encode into the resource value
+    pub fn encode(&self) -> String {
+        self.to_string()
+    }
+
+    /// 从资源值解析 (并校验) / Decode from the resource value (and validate)
+    pub fn decode(value: &str) -> Result<Self, AqaraError> {
+        let invalid = |message: String| AqaraError::InvalidParams(vec![message]);
+        let mut parts = value.split('|');
+        let mut builder = ThermostatSchedule::builder();
+        for day in parts.next().unwrap_or_default().split(',') {
+            let day = day
+                .parse::<Weekday>()
+                .map_err(|_| invalid(format!("unknown day {:?}", day)))?;
+            builder = builder.days(&[day]);
+        }
+        for segment in parts {
+            let (start, temperature) = segment
+                .split_once(',')
+                .ok_or_else(|| invalid(format!("malformed segment {:?}", segment)))?;
+            let start = NaiveTime::parse_from_str(start, "%H:%M")
+                .map_err(|_| invalid(format!("malformed time {:?}", start)))?;
+            let temperature = temperature
+                .parse::<f64>()
+                .map_err(|_| invalid(format!("malformed temperature {:?}", temperature)))?;
+            builder = builder.segment(start, temperature);
+        }
+        builder.build()
+    }
+}
+
+impl fmt::Display for ThermostatSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let days: Vec<&str> = self.days.iter().map(|day| day_name(*day)).collect();
+        write!(f, "{}", days.join(","))?;
+        for segment in &self.segments {
+            write!(
+                f,
+                "|{}:{:02},{:.1}",
+                segment.start.hour(),
+                segment.start.minute(),
+                segment.temperature
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn day_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "mon",
+        Weekday::Tue => "tue",
+        Weekday::Wed => "wed",
+        Weekday::Thu => "thu",
+        Weekday::Fri => "fri",
+        Weekday::Sat => "sat",
+        Weekday::Sun => "sun",
+    }
+}
+
+impl Device {
+    /// 写入温控器周日程 (Write a thermostat weekly schedule)
+    ///
+    /// intent: write.resource.device
+    ///
+    /// # Parameters 参数
+    /// - `resource_id`: 日程资源ID (随型号不同，可通过 `query_resource_info` 查询) / Schedule resource ID (model specific, see `query_resource_info`)
+    /// - `schedule`: 周日程 / Weekly schedule
+    pub async fn set_thermostat_schedule(
+        &self,
+        resource_id: &str,
+        schedule: &ThermostatSchedule,
+    ) -> Result<(), AqaraError> {
+        self.write(&[(resource_id, &schedule.encode())]).await
+    }
+
+    /// 设置目标温度 (Set the target temperature)
+    ///
+    /// intent: write.resource.device
+    ///
+    /// # Parameters 参数
+    /// - `celsius`: 目标温度 (°C，5-30，0.5 的倍数) / Target temperature (°C, 5-30, a multiple of 0.5)
+    pub async fn set_target_temperature(&self, celsius: f64) -> Result<(), AqaraError> {
+        if !(THERMOSTAT_MIN_TEMPERATURE..=THERMOSTAT_MAX_TEMPERATURE).contains(&celsius)
+            || (celsius * 2.0).fract() != 0.0
+        {
+            return Err(AqaraError::InvalidParams(vec![format!(
+                "target temperature {} must be a multiple of 0.5 within {}-{}",
+                celsius, THERMOSTAT_MIN_TEMPERATURE, THERMOSTAT_MAX_TEMPERATURE
+            )]));
+        }
+        let value = ((celsius * 100.0).round() as i64).to_string();
+        self.write(&[(THERMOSTAT_TARGET_RESOURCE_ID, &value)]).await
+    }
+
+    /// 读取目标温度 (°C) / Read the target temperature (°C)
+    ///
+    /// intent: query.resource.value
+    pub async fn target_temperature(&self) -> Result<Option<Reading>, AqaraError> {
+        self.read_scaled(THERMOSTAT_TARGET_RESOURCE_ID, "°C").await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENCODED: &str = "mon,tue|8:00,21.0|12:00,18.0|18:00,21.5|23:00,16.0";
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    fn problems(result: Result<ThermostatSchedule, AqaraError>) -> Vec<String> {
+        match result {
+            Err(AqaraError::InvalidParams(problems)) => problems,
+            other => panic!("expected InvalidParams, got {:?}", other),
+        }
+    }
+
+    fn weekday_schedule() -> ThermostatScheduleBuilder {
+        ThermostatSchedule::builder()
+            .days(&[Weekday::Mon, Weekday::Tue])
+            .segment(time(8, 0), 21.0)
+            .segment(time(12, 0), 18.0)
+            .segment(time(18, 0), 21.5)
+            .segment(time(23, 0), 16.0)
+    }
+
+    #[test]
+    fn build_and_encode() {
+        let schedule = weekday_schedule().build().unwrap();
+        assert_eq!(schedule.days(), [Weekday::Mon, Weekday::Tue]);
+        assert_eq!(schedule.segments().len(), THERMOSTAT_SCHEDULE_SEGMENTS);
+        assert_eq!(schedule.encode(), ENCODED);
+    }
+
+    #[test]
+    fn decode_round_trips() {
+        let schedule = ThermostatSchedule::decode(ENCODED).unwrap();
+        assert_eq!(schedule, weekday_schedule().build().unwrap());
+        assert_eq!(schedule.encode(), ENCODED);
+    }
+
+    #[test]
+    fn build_reports_every_problem() {
+        let result = ThermostatSchedule::builder()
+            .days(&[Weekday::Sat, Weekday::Sat])
+            .segment(NaiveTime::from_hms_opt(8, 0, 30).unwrap(), 21.0)
+            .segment(time(7, 0), 40.0)
+            .segment(time(9, 0), 20.2)
+            .build();
+        assert_eq!(
+            problems(result),
+            [
+                "day sat is listed more than once",
+                "expected 4 segments, got 3",
+                "segment 0: start must be on a whole minute",
+                "segment 1: temperature 40 is outside 5-30",
+                "segment 1: starts at 07:00 which overlaps segment 0 starting at 08:00",
+                "segment 2: temperature 20.2 is not a multiple of 0.5",
+            ]
+        );
+    }
+
+    #[test]
+    fn build_requires_a_day() {
+        let result = ThermostatSchedule::builder()
+            .segment(time(6, 0), 20.0)
+            .segment(time(9, 0), 18.0)
+            .segment(time(17, 0), 21.0)
+            .segment(time(22, 0), 17.0)
+            .build();
+        assert_eq!(problems(result), ["at least one day is required"]);
+    }
+
+    #[test]
+    fn decode_rejects_malformed_values() {
+        assert_eq!(
+            problems(ThermostatSchedule::decode("someday|8:00,21.0")),
+            ["unknown day \"someday\""]
+        );
+        assert_eq!(
+            problems(ThermostatSchedule::decode("mon|8:00")),
+            ["malformed segment \"8:00\""]
+        );
+        assert_eq!(
+            problems(ThermostatSchedule::decode("mon|8h,21.0")),
+            ["malformed time \"8h\""]
+        );
+        assert_eq!(
+            problems(ThermostatSchedule::decode("mon|8:00,warm")),
+            ["malformed temperature \"warm\""]
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use std::ops::Range;
+use std::time::SystemTime;
+
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::EventCondition;
+
+/// 时间范围 (Time range)
+///
+/// Aqara 接口以毫秒字符串表示时间，此类型负责换算。
+/// Aqara intents take times as millisecond strings; this type does the conversion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeRange {
+    /// 开始时间 / Start time
+    pub start: DateTime<Utc>,
+    /// 结束时间 / End time
+    pub end: DateTime<Utc>,
+}
+
+impl TimeRange {
+    /// 创建 / Create
+    pub fn new(start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        TimeRange { start, end }
+    }
+
+    /// 截至现在的最近若干小时 / The last `hours` hours up to now
+    pub fn last_hours(hours: i64) -> Self {
+        Self::last(Duration::hours(hours))
+    }
+
+    /// 截至现在的最近若干天 / The last `days` days up to now
+    pub fn last_days(days: i64) -> Self {
+        Self::last(Duration::days(days))
+    }
+
+    /// 截至现在的最近一段时间 / The last `duration` up to now
+    pub fn last(duration: Duration) -> Self {
+        let end = Utc::now();
+        TimeRange {
+            start: end - duration,
+            end,
+        }
+    }
+
+    /// 从指定时间到现在 / From `start` up to now
+    pub fn since(start: DateTime<Utc>) -> Self {
+        TimeRange {
+            start,
+            end: Utc::now(),
+        }
+    }
+
+    /// 时长 / Duration
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+
+    /// 开始时间 (毫秒) / Start time (milliseconds)
+    pub fn start_millis(&self) -> i64 {
+        self.start.timestamp_millis()
+    }
+
+    /// 结束时间 (毫秒) / End time (milliseconds)
+    pub fn end_millis(&self) -> i64 {
+        self.end.timestamp_millis()
+    }
+
+    /// (开始, 结束) 毫秒字符串，即接口中的 `startTime`/`endTime`
+    /// (start, end) as millisecond strings, i.e. `startTime`/`endTime` in the API
+    pub fn to_millis_strings(&self) -> (String, String) {
+        (
+            self.start_millis().to_string(),
+            self.end_millis().to_string(),
+        )
+    }
+}
+
+impl From<(DateTime<Utc>, DateTime<Utc>)> for TimeRange {
+    fn from((start, end): (DateTime<Utc>, DateTime<Utc>)) -> Self {
+        TimeRange::new(start, end)
+    }
+}
+
+impl From<Range<DateTime<Utc>>> for TimeRange {
+    fn from(range: Range<DateTime<Utc>>) -> Self {
+        TimeRange::new(range.start, range.end)
+    }
+}
+
+impl From<Range<SystemTime>> for TimeRange {
+    fn from(range: Range<SystemTime>) -> Self {
+        TimeRange::new(range.start.into(), range.end.into())
+    }
+}
+
+impl EventCondition {
+    /// 设置生效时段 (Set the active window)
+    ///
+    /// # Parameters 参数
+    /// - `begin`: 开始时间 / Window start
+    /// - `end`: 结束时间 / Window end
+    pub fn window(mut self, begin: NaiveTime, end: NaiveTime) -> Self {
+        self.begin_time = Some(begin.format("%H:%M:%S").to_string());
+        self.end_time = Some(end.format("%H:%M:%S").to_string());
+        self
+    }
+}
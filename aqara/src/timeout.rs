@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+/// 超时配置 (Timeout config)
+///
+/// 按 intent 配置时只有 `request` 生效，`connect` 作用于整个连接池。
+/// When configured per intent only `request` applies; `connect` is pool-wide.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeoutConfig {
+    /// 建立连接超时 / Connect timeout
+    pub connect: Option<Duration>,
+    /// 整个请求超时 (含读取响应) / Whole-request timeout (including the response body)
+    pub request: Option<Duration>,
+}
+
+impl TimeoutConfig {
+    /// 仅设置请求超时 / Only set the request timeout
+    pub fn request(timeout: Duration) -> Self {
+        TimeoutConfig {
+            connect: None,
+            request: Some(timeout),
+        }
+    }
+}
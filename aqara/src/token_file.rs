@@ -0,0 +1,99 @@
+//! 加密令牌文件 (Encrypted token file)
+//!
+//! 使用 ChaCha20-Poly1305 加密保存令牌表，供没有系统密钥环、又不允许明文令牌文件的网关使用。
+//! Stores the token map encrypted with ChaCha20-Poly1305, for gateways without
+//! an OS keyring where plaintext token files are unacceptable.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::PathBuf;
+
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+use crate::{AqaraError, TokenMap, UserTokens};
+
+/// 随机数长度 / Nonce length
+const NONCE_LEN: usize = 12;
+
+/// 加密令牌文件 (Encrypted token file)
+///
+/// 文件格式为 12 字节随机数加上密文；每次保存都使用新的随机数，并先写临时文件再替换。
+/// The file holds a 12-byte nonce followed by the ciphertext; every save uses a
+/// fresh nonce and writes a temporary file before replacing the original.
+pub struct EncryptedTokenFile {
+    path: PathBuf,
+    cipher: ChaCha20Poly1305,
+}
+
+impl EncryptedTokenFile {
+    /// 创建 (Create)
+    ///
+    /// # Parameters 参数
+    /// - `path`: 文件路径 / File path
+    /// - `key`: 32 字节密钥 / 32-byte key
+    pub fn new(path: impl Into<PathBuf>, key: &[u8; 32]) -> Self {
+        EncryptedTokenFile {
+            path: path.into(),
+            cipher: ChaCha20Poly1305::new(Key::from_slice(key)),
+        }
+    }
+
+    /// 保存令牌表 / Save the token map
+    pub fn save(&self, tokens: &TokenMap) -> Result<(), AqaraError> {
+        let entries: HashMap<String, UserTokens> = tokens
+            .keys()
+            .into_iter()
+            .filter_map(|key| Some((key.clone(), tokens.get(&key)?)))
+            .collect();
+        let plaintext = serde_json::to_vec(&entries)?;
+        let nonce: [u8; NONCE_LEN] = rand::random();
+        let ciphertext = self
+            .cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+            .map_err(|_| Error::other("failed to encrypt the token file"))?;
+
+        let mut contents = nonce.to_vec();
+        contents.extend(ciphertext);
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, contents)?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    /// 读取令牌并写入令牌表 (Load tokens into the token map)
+    ///
+    /// 文件不存在时不做任何事；密钥不符或文件损坏时返回 `AqaraError::Io`。
+    /// Does nothing when the file does not exist; a wrong key or a corrupted file
+    /// returns `AqaraError::Io`.
+    ///
+    /// # Returns
+    /// 成功返回读取的用户数 / Returns the number of users loaded on success
+    pub fn load(&self, tokens: &TokenMap) -> Result<usize, AqaraError> {
+        let contents = match fs::read(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+        if contents.len() < NONCE_LEN {
+            return Err(Error::new(ErrorKind::InvalidData, "token file is truncated").into());
+        }
+        let (nonce, ciphertext) = contents.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    "token file could not be decrypted; wrong key or corrupted file",
+                )
+            })?;
+        let entries: HashMap<String, UserTokens> = serde_json::from_slice(&plaintext)?;
+        let count = entries.len();
+        for (key, user_tokens) in entries {
+            tokens.insert(&key, user_tokens);
+        }
+        Ok(count)
+    }
+}
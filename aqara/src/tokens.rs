@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{debug, warn};
+
+use crate::util::value_to_string;
+use crate::{AqaraClient, AqaraConfig, AqaraError, ConfigProblem};
+
+/// 单个用户的令牌 (Tokens of one user)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserTokens {
+    /// 访问令牌 / Access token
+    pub access_token: String,
+    /// 刷新令牌 (可选) / Refresh token (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    /// 访问令牌过期时间 (可选) / Access token expiry (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// 令牌接口返回的令牌 (Tokens returned by the token intents)
+///
+/// 由 `config.auth.getToken`、`config.auth.refreshToken` 及 OAuth2 令牌接口产生。
+/// Produced by `config.auth.getToken`, `config.auth.refreshToken` and the OAuth2 token endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenBundle {
+    /// 访问令牌 / Access token
+    pub access_token: String,
+    /// 刷新令牌 / Refresh token
+    pub refresh_token: String,
+    /// 有效期 (秒；未返回时为 `None`) / Validity in seconds (`None` when not returned)
+    pub expires_in: Option<i64>,
+    /// 用户 openId (未返回时为空) / The user's openId (empty when not returned)
+    pub open_id: String,
+}
+
+impl TokenBundle {
+    // 从令牌接口的 `result` 解析 (数字字段可能是字符串) / Parse a token intent's `result` (numbers may be strings)
+    pub(crate) fn from_result(result: &Value) -> Self {
+        TokenBundle {
+            access_token: value_to_string(result.get("accessToken")),
+            refresh_token: value_to_string(result.get("refreshToken")),
+            expires_in: value_to_string(result.get("expiresIn")).parse().ok(),
+            open_id: value_to_string(result.get("openId")),
+        }
+    }
+
+    /// 按当前时间计算的过期时间 / Expiry counted from now
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.expires_in
+            .map(|seconds| Utc::now() + chrono::Duration::seconds(seconds))
+    }
+
+    /// 转为令牌表条目 (Convert into a token map entry)
+    ///
+    /// 过期时间从调用时刻起算。
+    /// The expiry is counted from the moment of the call.
+    pub fn to_user_tokens(&self) -> UserTokens {
+        UserTokens {
+            access_token: self.access_token.clone(),
+            refresh_token: Some(self.refresh_token.clone()).filter(|token| !token.is_empty()),
+            expires_at: self.expires_at(),
+        }
+    }
+}
+
+// 客户端自身的令牌槽，初始为配置中的访问令牌 / The client's own token slot, seeded from the configured access token
+pub(crate) fn primary_slot(config: &AqaraConfig) -> RwLock<UserTokens> {
+    RwLock::new(UserTokens {
+        access_token: config.access_token.clone(),
+        refresh_token: None,
+        expires_at: None,
+    })
+}
+
+/// 多用户令牌表 (Multi-user token map)
+///
+/// 按用户键 (通常为 openId) 保存令牌，供代表多个 Aqara 用户调用的服务端应用使用。
+/// Stores tokens per user key (usually the openId) for server applications that
+/// act on behalf of many Aqara end users.
+#[derive(Debug, Default)]
+pub struct TokenMap {
+    tokens: RwLock<HashMap<String, UserTokens>>,
+}
+
+impl TokenMap {
+    /// 保存或替换用户令牌 / Insert or replace a user's tokens
+    pub fn insert(&self, key: &str, tokens: UserTokens) {
+        self.tokens.write().unwrap().insert(key.to_string(), tokens);
+    }
+
+    /// 获取用户令牌 / Get a user's tokens
+    pub fn get(&self, key: &str) -> Option<UserTokens> {
+        self.tokens.read().unwrap().get(key).cloned()
+    }
+
+    /// 移除用户令牌 / Remove a user's tokens
+    pub fn remove(&self, key: &str) -> Option<UserTokens> {
+        self.tokens.write().unwrap().remove(key)
+    }
+
+    /// 已保存的用户键 / Stored user keys
+    pub fn keys(&self) -> Vec<String> {
+        self.tokens.read().unwrap().keys().cloned().collect()
+    }
+}
+
+impl AqaraClient {
+    /// 多用户令牌表 / The multi-user token map
+    pub fn tokens(&self) -> &TokenMap {
+        &self.inner.tokens
+    }
+
+    /// 以指定用户身份调用 (Act as a user)
+    ///
+    /// 返回共享连接池的客户端视图，其请求使用令牌表中该用户的访问令牌签名。
+    /// Returns a client view sharing the connection pool whose requests are signed
+    /// with that user's access token from the token map.
+    ///
+    /// # Parameters 参数
+    /// - `key`: 用户键 (通常为 openId) / User key (usually the openId)
+    pub fn as_user(&self, key: &str) -> AqaraClient {
+        AqaraClient {
+            user: Some(key.to_string()),
+            ..self.clone()
+        }
+    }
+
+    /// 当前视图对应的用户键 / User key of this view
+    pub fn user_key(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
+    // 当前视图的令牌：`as_user` 视图取令牌表，否则取客户端自身的令牌
+    // Tokens of this view: the token map for `as_user` views, else the client's own tokens
+    pub(crate) fn current_tokens(&self) -> Result<UserTokens, AqaraError> {
+        match &self.user {
+            Some(key) => self
+                .inner
+                .tokens
+                .get(key)
+                .ok_or_else(|| AqaraError::UnknownUser(key.clone())),
+            None => Ok(self.inner.primary_tokens.read().unwrap().clone()),
+        }
+    }
+
+    // 写入当前视图的令牌 / Store the tokens of this view
+    pub(crate) fn store_tokens(&self, tokens: UserTokens) {
+        match &self.user {
+            Some(key) => self.inner.tokens.insert(key, tokens),
+            None => *self.inner.primary_tokens.write().unwrap() = tokens,
+        }
+    }
+
+    // 当前视图使用的访问令牌 / Access token used by this view
+    pub(crate) fn access_token(&self) -> Result<String, AqaraError> {
+        self.current_tokens().map(|tokens| tokens.access_token)
+    }
+
+    /// 设置当前视图的令牌 (Set the tokens of this view)
+    ///
+    /// `as_user` 视图写入令牌表，否则替换客户端自身使用的令牌 (所有克隆共享)。
+    /// `as_user` views write into the token map; otherwise the client's own tokens
+    /// (shared by every clone) are replaced.
+    ///
+    /// # Parameters 参数
+    /// - `tokens`: 新令牌 / New tokens
+    pub fn set_tokens(&self, tokens: UserTokens) {
+        self.store_tokens(tokens);
+    }
+
+    /// 设置当前视图的访问令牌及过期时间 (Set this view's access token with its expiry)
+    ///
+    /// 保留已保存的刷新令牌；`as_user` 视图写入令牌表，否则替换客户端自身的令牌。
+    /// The stored refresh token is kept; `as_user` views write into the token map,
+    /// otherwise the client's own token is replaced.
+    ///
+    /// # Parameters 参数
+    /// - `access_token`: 访问令牌 / Access token
+    /// - `expires_at`: 过期时间 / Expiry time
+    pub fn set_access_token_with_expiry(&self, access_token: &str, expires_at: DateTime<Utc>) {
+        let refresh_token = self
+            .current_tokens()
+            .ok()
+            .and_then(|tokens| tokens.refresh_token);
+        self.store_tokens(UserTokens {
+            access_token: access_token.to_string(),
+            refresh_token,
+            expires_at: Some(expires_at),
+        });
+    }
+
+    /// 当前视图访问令牌的过期时间 (Expiry of this view's access token)
+    ///
+    /// # Returns
+    /// 未记录过期时间时为 `None` / `None` when no expiry is known
+    pub fn token_expires_at(&self) -> Option<DateTime<Utc>> {
+        self.current_tokens().ok()?.expires_at
+    }
+
+    /// 刷新当前视图的令牌 (Refresh this view's tokens)
+    ///
+    /// intent: config.auth.refreshToken
+    ///
+    /// 使用保存的刷新令牌，并将新令牌写回 (`as_user` 视图写入令牌表，否则替换客户端自身的令牌)。
+    /// Uses the stored refresh token and writes the new tokens back (into the token
+    /// map for `as_user` views, otherwise replacing the client's own tokens).
+    ///
+    /// # Returns
+    /// 成功返回新令牌 / Returns the new tokens on success
+    pub async fn refresh_user_token(&self) -> Result<UserTokens, AqaraError> {
+        let refresh_token = self.current_tokens()?.refresh_token.ok_or_else(|| {
+            AqaraError::InvalidConfig(vec![ConfigProblem {
+                field: "refresh_token".to_string(),
+                message: match &self.user {
+                    Some(key) => format!("no refresh token stored for user {}", key),
+                    None => "no refresh token stored for the client".to_string(),
+                },
+            }])
+        })?;
+        let tokens = self
+            .config_auth_refresh_token_typed(&refresh_token)
+            .await?
+            .to_user_tokens();
+        // 关闭 `auto_install_tokens` 时也写回 / Write back even with `auto_install_tokens` off
+        self.store_tokens(tokens.clone());
+        Ok(tokens)
+    }
+}
+
+/// 刷新失败后的重试间隔 / Delay before retrying a failed refresh
+const FAILURE_BACKOFF: Duration = Duration::from_secs(30);
+/// 最长检查间隔 / Longest wait between checks
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+/// 令牌主动刷新器 (Proactive token refresher)
+///
+/// 在客户端自身及令牌表中各用户的访问令牌过期前 `margin` 使用保存的刷新令牌自动刷新；
+/// 没有过期时间或刷新令牌的跳过。
+/// Refreshes the client's own access token and each user's in the token map
+/// `margin` ahead of expiry using the stored refresh token; tokens without an
+/// expiry or a refresh token are skipped.
+pub struct TokenRefresher {
+    client: AqaraClient,
+    margin: Duration,
+}
+
+impl TokenRefresher {
+    /// 创建刷新器 (默认提前 5 分钟) / Create a refresher (5 minutes ahead by default)
+    pub fn new(client: AqaraClient) -> Self {
+        TokenRefresher {
+            client,
+            margin: Duration::from_secs(300),
+        }
+    }
+
+    /// 设置提前刷新的时间 / Set how long before expiry to refresh
+    pub fn margin(mut self, margin: Duration) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// 运行刷新器 (Run the refresher)
+    ///
+    /// 持续运行直到客户端关闭，可交给 `tokio::spawn` 在后台执行；单次刷新失败只记录告警并稍后重试。
+    /// Runs until the client shuts down and can be handed to `tokio::spawn`; a
+    /// failed refresh is only logged and retried later.
+    pub async fn run(self) {
+        let margin = chrono::Duration::from_std(self.margin).unwrap_or_default();
+        while !self.client.is_shutdown() {
+            let mut wait = MAX_SLEEP;
+            // 客户端自身的令牌与令牌表中的各用户 / The client's own tokens plus every user in the map
+            let primary = AqaraClient {
+                user: None,
+                ..self.client.clone()
+            };
+            let views = std::iter::once(primary).chain(
+                self.client
+                    .tokens()
+                    .keys()
+                    .into_iter()
+                    .map(|key| self.client.as_user(&key)),
+            );
+            for view in views {
+                let Ok(tokens) = view.current_tokens() else {
+                    continue;
+                };
+                let (Some(expires_at), Some(_)) = (tokens.expires_at, &tokens.refresh_token) else {
+                    continue;
+                };
+                let due = expires_at - margin;
+                if due > Utc::now() {
+                    wait = wait.min((due - Utc::now()).to_std().unwrap_or_default());
+                    continue;
+                }
+                let name = view.user_key().unwrap_or("the client").to_string();
+                match view.refresh_user_token().await {
+                    Ok(_) => debug!("Refreshed access token of {}", name),
+                    Err(e) => {
+                        warn!("Refreshing access token of {} failed: {}", name, e);
+                        wait = wait.min(FAILURE_BACKOFF);
+                    }
+                }
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(wait) => {}
+                _ = self.client.inner.lifecycle.closed() => break,
+            }
+        }
+    }
+}
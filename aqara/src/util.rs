@@ -0,0 +1,185 @@
+use serde_json::Value;
+
+use crate::{AqaraError, AqaraResponse};
+
+/// 将 JSON 值转换为字符串 / Convert a JSON value into a string
+pub(crate) fn value_to_string(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Null) | None => String::new(),
+        Some(other) => other.to_string(),
+    }
+}
+
+// 结果可能是字符串或包含指定字段的对象 / Result may be a plain string or an object holding `key`
+pub(crate) fn extract_id(result: &Value, key: &str) -> Option<String> {
+    match result {
+        Value::String(id) => Some(id.clone()),
+        Value::Object(map) => map.get(key).and_then(Value::as_str).map(str::to_string),
+        _ => None,
+    }
+}
+
+/// 从响应中取出新建对象的ID / Take the id of a newly created object out of a response
+///
+/// 业务码为 0 但缺少ID时返回 `AqaraError::MissingResult`，而不是携带成功码的 `Api` 错误。
+/// A response with code 0 but no id is `AqaraError::MissingResult`, not an `Api`
+/// error carrying the success code.
+pub(crate) fn require_id(
+    response: AqaraResponse<Value>,
+    intent: &str,
+    key: &str,
+) -> Result<String, AqaraError> {
+    match response
+        .result
+        .as_ref()
+        .and_then(|result| extract_id(result, key))
+    {
+        Some(id) => Ok(id),
+        None => Err(AqaraError::MissingResult {
+            intent: intent.to_string(),
+            request_id: response.request_id,
+        }),
+    }
+}
+
+/// 检查单次请求的数量上限 / Check a per-request item cap
+pub(crate) fn check_max_items(field: &str, len: usize, max: usize) -> Result<(), AqaraError> {
+    if len > max {
+        return Err(AqaraError::InvalidParams(vec![format!(
+            "{} has {} items; at most {} are allowed per request",
+            field, len, max
+        )]));
+    }
+    Ok(())
+}
+
+/// 按 intent 匹配的配置表 (Per-intent config map)
+///
+/// 键可以是完整 intent (如 `config.scene.run`) 或以 `*` 结尾的前缀 (如 `fetch.*`)，
+/// 完整匹配优先，其次最长前缀。
+/// Keys are either full intents (e.g. `config.scene.run`) or prefixes ending in `*`
+/// (e.g. `fetch.*`); exact matches win, then the longest prefix.
+#[derive(Debug, Clone)]
+pub(crate) struct IntentMap<T> {
+    entries: Vec<(String, T)>,
+}
+
+impl<T> Default for IntentMap<T> {
+    fn default() -> Self {
+        IntentMap {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<T> IntentMap<T> {
+    pub(crate) fn insert(&mut self, pattern: &str, value: T) {
+        self.entries.retain(|(p, _)| p != pattern);
+        self.entries.push((pattern.to_string(), value));
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.entries.iter().map(|(p, v)| (p.as_str(), v))
+    }
+
+    pub(crate) fn get(&self, intent: &str) -> Option<&T> {
+        let mut best: Option<(usize, &T)> = None;
+        for (pattern, value) in &self.entries {
+            let score = match pattern.strip_suffix('*') {
+                None if pattern == intent => usize::MAX,
+                Some(prefix) if intent.starts_with(prefix) => prefix.len(),
+                _ => continue,
+            };
+            if best.is_none_or(|(s, _)| score > s) {
+                best = Some((score, value));
+            }
+        }
+        best.map(|(_, value)| value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn response(result: Option<Value>) -> AqaraResponse<Value> {
+        AqaraResponse {
+            code: 0,
+            request_id: "req-1".to_string(),
+            message: "Success".to_string(),
+            msg_details: None,
+            result,
+            intent: "config.scene.create".to_string(),
+        }
+    }
+
+    #[test]
+    fn require_id_accepts_a_string_or_an_object() {
+        let id = require_id(
+            response(Some(json!("AL.1"))),
+            "config.scene.create",
+            "sceneId",
+        );
+        assert_eq!(id.unwrap(), "AL.1");
+        let id = require_id(
+            response(Some(json!({ "sceneId": "AL.2" }))),
+            "config.scene.create",
+            "sceneId",
+        );
+        assert_eq!(id.unwrap(), "AL.2");
+    }
+
+    #[test]
+    fn require_id_reports_a_missing_id_as_missing_result() {
+        for result in [None, Some(json!({ "other": "x" }))] {
+            match require_id(response(result), "config.scene.create", "sceneId") {
+                Err(AqaraError::MissingResult { intent, request_id }) => {
+                    assert_eq!(intent, "config.scene.create");
+                    assert_eq!(request_id, "req-1");
+                }
+                other => panic!("expected MissingResult, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn intent_map_prefers_exact_matches() {
+        let mut map = IntentMap::default();
+        map.insert("query.*", 1);
+        map.insert("query.device.info", 2);
+        map.insert("query.device.*", 3);
+        assert_eq!(map.get("query.device.info"), Some(&2));
+    }
+
+    #[test]
+    fn intent_map_picks_the_longest_prefix() {
+        let mut map = IntentMap::default();
+        map.insert("query.device.*", 3);
+        map.insert("query.*", 1);
+        map.insert("*", 0);
+        assert_eq!(map.get("query.device.subInfo"), Some(&3));
+        assert_eq!(map.get("query.position.info"), Some(&1));
+        assert_eq!(map.get("write.resource.device"), Some(&0));
+    }
+
+    #[test]
+    fn intent_map_without_a_match_returns_none() {
+        let mut map = IntentMap::default();
+        map.insert("query.device.info", 1);
+        map.insert("config.*", 2);
+        assert_eq!(map.get("query.device"), None);
+        assert_eq!(map.get("query.device.info.extra"), None);
+    }
+
+    #[test]
+    fn intent_map_insert_replaces_the_same_pattern() {
+        let mut map = IntentMap::default();
+        map.insert("query.*", 1);
+        map.insert("query.*", 2);
+        assert_eq!(map.get("query.scene.list"), Some(&2));
+        assert_eq!(map.iter().count(), 1);
+    }
+}
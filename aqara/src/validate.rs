@@ -0,0 +1,197 @@
+//! 发送前的参数校验 (Parameter validation before sending)
+//!
+//! 按文档约束检查请求体，在签名并发出请求之前返回 `AqaraError::InvalidParams`。
+//! Checks request bodies against the documented constraints and returns
+//! `AqaraError::InvalidParams` before the request is signed and sent.
+
+use serde_json::Value;
+
+use crate::{
+    AqaraError, HISTORY_MAX_PAGE_SIZE, POSITION_DETAIL_MAX_IDS, RESOURCE_NAME_MAX_SUBJECTS,
+    RESOURCE_VALUE_MAX_SUBJECTS,
+};
+
+/// 单次设备信息查询的最大设备数 / Max dids per device info query
+pub const DEVICE_INFO_MAX_DIDS: usize = 100;
+
+/// 不能为空的ID字段 / Id fields that must not be empty
+const REQUIRED_ID_FIELDS: [&str; 4] = ["did", "subjectId", "sceneId", "linkageId"];
+
+/// 校验请求体，一次性列出所有问题 / Validate a request body, listing every problem
+pub(crate) fn validate_request(intent: &str, data: &Value) -> Result<(), AqaraError> {
+    let mut problems = Vec::new();
+    for field in REQUIRED_ID_FIELDS {
+        if data.get(field).and_then(Value::as_str) == Some("") {
+            problems.push(format!("{} must not be empty", field));
+        }
+    }
+    for field in ["pageNum", "pageSize"] {
+        if let Some(value) = data.get(field).and_then(Value::as_i64) {
+            if value < 1 {
+                problems.push(format!("{} must be at least 1, got {}", field, value));
+            }
+        }
+    }
+
+    match intent {
+        "query.device.info" => max_items(&mut problems, data, "dids", DEVICE_INFO_MAX_DIDS),
+        "query.position.detail" => {
+            max_items(&mut problems, data, "positionIds", POSITION_DETAIL_MAX_IDS);
+            no_empty_items(&mut problems, data, "positionIds");
+        }
+        "query.resource.value" => {
+            max_items(
+                &mut problems,
+                data,
+                "resources",
+                RESOURCE_VALUE_MAX_SUBJECTS,
+            );
+        }
+        "query.resource.name" => {
+            max_items(
+                &mut problems,
+                data,
+                "subjectIds",
+                RESOURCE_NAME_MAX_SUBJECTS,
+            );
+            no_empty_items(&mut problems, data, "subjectIds");
+        }
+        "fetch.resource.history" => {
+            if let Some(size) = data.get("size").and_then(Value::as_i64) {
+                if !(1..=i64::from(HISTORY_MAX_PAGE_SIZE)).contains(&size) {
+                    problems.push(format!(
+                        "size must be within 1-{}, got {}",
+                        HISTORY_MAX_PAGE_SIZE, size
+                    ));
+                }
+            }
+            if data
+                .get("resourceIds")
+                .and_then(Value::as_array)
+                .is_some_and(Vec::is_empty)
+            {
+                problems.push("resourceIds must not be empty".to_string());
+            }
+        }
+        _ => {}
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(AqaraError::InvalidParams(problems))
+    }
+}
+
+fn max_items(problems: &mut Vec<String>, data: &Value, field: &str, max: usize) {
+    if let Some(items) = data.get(field).and_then(Value::as_array) {
+        if items.len() > max {
+            problems.push(format!(
+                "{} has {} items; at most {} are allowed per request",
+                field,
+                items.len(),
+                max
+            ));
+        }
+    }
+}
+
+fn no_empty_items(problems: &mut Vec<String>, data: &Value, field: &str) {
+    if let Some(items) = data.get(field).and_then(Value::as_array) {
+        if items.iter().any(|item| item.as_str() == Some("")) {
+            problems.push(format!("{} must not contain empty ids", field));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn problems(intent: &str, data: Value) -> Vec<String> {
+        match validate_request(intent, &data) {
+            Err(AqaraError::InvalidParams(problems)) => problems,
+            other => panic!("expected InvalidParams, got {:?}", other),
+        }
+    }
+
+    fn ids(count: usize) -> Value {
+        (0..count).map(|i| format!("id.{}", i)).collect()
+    }
+
+    #[test]
+    fn accepts_valid_requests() {
+        assert!(validate_request("query.device.info", &json!({ "dids": ids(100) })).is_ok());
+        assert!(
+            validate_request("write.resource.device", &json!({ "subjectId": "lumi.1" })).is_ok()
+        );
+        assert!(validate_request("unknown.intent", &json!(null)).is_ok());
+    }
+
+    #[test]
+    fn reports_empty_ids_and_bad_paging_together() {
+        let data = json!({ "did": "", "sceneId": "", "pageNum": 0, "pageSize": -5 });
+        assert_eq!(
+            problems("query.scene.detail", data),
+            [
+                "did must not be empty",
+                "sceneId must not be empty",
+                "pageNum must be at least 1, got 0",
+                "pageSize must be at least 1, got -5",
+            ]
+        );
+    }
+
+    #[test]
+    fn enforces_per_intent_item_limits() {
+        assert_eq!(
+            problems("query.device.info", json!({ "dids": ids(101) })),
+            ["dids has 101 items; at most 100 are allowed per request"]
+        );
+        assert_eq!(
+            problems("query.resource.value", json!({ "resources": ids(51) })),
+            ["resources has 51 items; at most 50 are allowed per request"]
+        );
+        // 限制只对对应 intent 生效 / Limits only apply to their intent
+        assert!(validate_request("query.device.subInfo", &json!({ "dids": ids(101) })).is_ok());
+    }
+
+    #[test]
+    fn rejects_empty_ids_in_lists() {
+        let mut position_ids = ids(51);
+        position_ids[0] = json!("");
+        assert_eq!(
+            problems(
+                "query.position.detail",
+                json!({ "positionIds": position_ids })
+            ),
+            [
+                "positionIds has 51 items; at most 50 are allowed per request",
+                "positionIds must not contain empty ids",
+            ]
+        );
+        assert_eq!(
+            problems(
+                "query.resource.name",
+                json!({ "subjectIds": ["lumi.1", ""] })
+            ),
+            ["subjectIds must not contain empty ids"]
+        );
+    }
+
+    #[test]
+    fn checks_history_size_and_resources() {
+        let data = json!({ "subjectId": "lumi.1", "resourceIds": [], "size": 301 });
+        assert_eq!(
+            problems("fetch.resource.history", data),
+            [
+                "size must be within 1-300, got 301",
+                "resourceIds must not be empty",
+            ]
+        );
+        let data = json!({ "subjectId": "lumi.1", "resourceIds": ["0.1.85"], "size": 300 });
+        assert!(validate_request("fetch.resource.history", &data).is_ok());
+    }
+}
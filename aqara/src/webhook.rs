@@ -0,0 +1,301 @@
+//! 推送回调校验 (Webhook verification)
+//!
+//! 校验 Aqara 推送请求头中的签名，并解析为 `PushMessage`。
+//! Verifies the signature in the headers of Aqara push requests and parses
+//! them into `PushMessage`s.
+
+use chrono::{Duration, Utc};
+use subtle::ConstantTimeEq;
+
+#[cfg(feature = "actix-web")]
+pub mod actix;
+#[cfg(feature = "axum")]
+pub mod axum;
+#[cfg(feature = "tower")]
+mod service;
+
+#[cfg(feature = "tower")]
+pub use service::WebhookService;
+
+use crate::push::PushMessage;
+use crate::{compute_sign, AqaraError};
+
+/// 默认允许的推送时间偏差 / Default allowed clock skew for pushes
+pub const DEFAULT_MAX_SKEW: Duration = Duration::minutes(5);
+
+/// 推送签名凭据 (Push signing credentials)
+#[derive(Debug, Clone)]
+pub struct PushCredentials {
+    /// 应用ID / App ID
+    pub app_id: String,
+    /// 密钥ID / Key ID
+    pub key_id: String,
+    /// 应用密钥 / App key
+    pub app_key: String,
+}
+
+/// 推送请求头 (Push request headers)
+#[derive(Debug, Clone, Default)]
+pub struct PushHeaders {
+    pub app_id: String,
+    pub key_id: String,
+    pub nonce: String,
+    pub time: String,
+    pub sign: String,
+}
+
+impl PushHeaders {
+    /// 从请求头读取 (Read from request headers)
+    ///
+    /// `get` 按名称 (不区分大小写) 返回请求头的值。
+    /// `get` returns a header value by (case-insensitive) name.
+    pub fn from_fn<'a>(get: impl Fn(&str) -> Option<&'a str>) -> Self {
+        let header = |name: &str| get(name).unwrap_or_default().to_string();
+        PushHeaders {
+            app_id: header("Appid"),
+            key_id: header("Keyid"),
+            nonce: header("Nonce"),
+            time: header("Time"),
+            sign: header("Sign"),
+        }
+    }
+}
+
+/// 推送校验器 (Push verifier)
+///
+/// 可同时持有多组凭据并按顺序尝试，轮换应用密钥期间新旧密钥签名的推送都能通过。
+/// Holds several credential sets and tries them in order, so pushes signed with
+/// either the old or the new app key pass while the key is being rotated.
+#[derive(Debug, Clone)]
+pub struct PushVerifier {
+    credentials: Vec<PushCredentials>,
+    max_skew: Option<Duration>,
+}
+
+impl PushVerifier {
+    /// 创建校验器 / Create a verifier
+    pub fn new(credentials: PushCredentials) -> Self {
+        PushVerifier {
+            credentials: vec![credentials],
+            max_skew: Some(DEFAULT_MAX_SKEW),
+        }
+    }
+
+    /// 追加一组凭据 (轮换时加入新密钥) / Add a credential set (the new key during rotation)
+    pub fn with_credentials(mut self, credentials: PushCredentials) -> Self {
+        self.credentials.push(credentials);
+        self
+    }
+
+    /// 移除指定密钥ID的凭据 (轮换完成后移除旧密钥) / Remove credentials by key id (the old key once rotation completes)
+    pub fn without_key(mut self, key_id: &str) -> Self {
+        self.credentials.retain(|c| c.key_id != key_id);
+        self
+    }
+
+    /// 设置允许的时间偏差 (`None` 不检查) / Set the allowed clock skew (`None` disables the check)
+    pub fn max_skew(mut self, max_skew: Option<Duration>) -> Self {
+        self.max_skew = max_skew;
+        self
+    }
+
+    /// 校验签名 (Verify the signature)
+    ///
+    /// # Returns
+    /// 成功返回匹配的凭据 / Returns the matching credentials on success
+    pub fn verify(&self, headers: &PushHeaders) -> Result<&PushCredentials, AqaraError> {
+        if let Some(max_skew) = self.max_skew {
+            let time = headers
+                .time
+                .parse::<i64>()
+                .map_err(|_| AqaraError::InvalidSignature("malformed Time header".to_string()))?;
+            if (Utc::now().timestamp_millis() - time).abs() > max_skew.num_milliseconds() {
+                return Err(AqaraError::InvalidSignature(
+                    "Time header is outside the allowed skew".to_string(),
+                ));
+            }
+        }
+        // 解码后按字节常量时间比较 / Decode and compare the bytes in constant time
+        let received = decode_hex(&headers.sign)
+            .ok_or_else(|| AqaraError::InvalidSignature("malformed Sign header".to_string()))?;
+        self.credentials
+            .iter()
+            .filter(|c| c.app_id == headers.app_id && c.key_id == headers.key_id)
+            .find(|c| {
+                let expected = compute_sign(
+                    &c.app_id,
+                    &c.key_id,
+                    &c.app_key,
+                    &headers.nonce,
+                    &headers.time,
+                    None,
+                );
+                decode_hex(&expected).is_some_and(|expected| bool::from(expected.ct_eq(&received)))
+            })
+            .ok_or_else(|| AqaraError::InvalidSignature("signature mismatch".to_string()))
+    }
+
+    /// 校验并解析推送 / Verify and parse a push
+    pub fn verify_message(
+        &self,
+        headers: &PushHeaders,
+        body: &str,
+    ) -> Result<PushMessage, AqaraError> {
+        self.verify(headers)?;
+        PushMessage::from_json(body)
+    }
+
+    /// 从原始请求校验并解析推送 (Verify and parse a push from a raw request)
+    ///
+    /// 与框架无关的入口：`get_header` 按名称返回请求头，`body` 为完整的原始请求体。
+    /// 各框架集成均基于此实现。
+    /// Framework-agnostic entry point: `get_header` looks a header up by name and
+    /// `body` is the complete raw request body. Every framework integration is
+    /// built on top of it.
+    ///
+    /// # Parameters 参数
+    /// - `get_header`: 请求头查询函数 / Header lookup
+    /// - `body`: 原始请求体 / Raw request body
+    ///
+    /// # Returns
+    /// 成功返回校验后的推送消息 / Returns the verified push message on success
+    pub fn parse_and_verify<'a>(
+        &self,
+        get_header: impl Fn(&str) -> Option<&'a str>,
+        body: &[u8],
+    ) -> Result<PushMessage, AqaraError> {
+        let headers = PushHeaders::from_fn(get_header);
+        let body = std::str::from_utf8(body)
+            .map_err(|_| AqaraError::InvalidParams(vec!["body is not UTF-8".to_string()]))?;
+        self.verify_message(&headers, body)
+    }
+}
+
+/// 解析十六进制字符串，大小写均可 / Decode a hex string, either case
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    s.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let hi = (pair[0] as char).to_digit(16)?;
+            let lo = (pair[1] as char).to_digit(16)?;
+            Some((hi * 16 + lo) as u8)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn credentials(app_key: &str) -> PushCredentials {
+        PushCredentials {
+            app_id: "app".to_string(),
+            key_id: "key".to_string(),
+            app_key: app_key.to_string(),
+        }
+    }
+
+    fn signed(app_key: &str, time: i64) -> PushHeaders {
+        let time = time.to_string();
+        PushHeaders {
+            app_id: "app".to_string(),
+            key_id: "key".to_string(),
+            sign: compute_sign("app", "key", app_key, "nonce", &time, None),
+            nonce: "nonce".to_string(),
+            time,
+        }
+    }
+
+    fn now() -> i64 {
+        Utc::now().timestamp_millis()
+    }
+
+    #[test]
+    fn verify_accepts_a_valid_signature_in_either_case() {
+        let verifier = PushVerifier::new(credentials("secret"));
+        let mut headers = signed("secret", now());
+        assert_eq!(verifier.verify(&headers).unwrap().app_key, "secret");
+        headers.sign = headers.sign.to_ascii_uppercase();
+        assert!(verifier.verify(&headers).is_ok());
+    }
+
+    #[test]
+    fn verify_tries_every_credential_set() {
+        let verifier = PushVerifier::new(credentials("old")).with_credentials(credentials("new"));
+        assert_eq!(
+            verifier.verify(&signed("new", now())).unwrap().app_key,
+            "new"
+        );
+        let verifier = verifier.without_key("key");
+        assert!(verifier.verify(&signed("new", now())).is_err());
+    }
+
+    #[test]
+    fn verify_rejects_bad_signatures() {
+        let verifier = PushVerifier::new(credentials("secret"));
+        let reject = |headers: &PushHeaders| {
+            matches!(
+                verifier.verify(headers),
+                Err(AqaraError::InvalidSignature(_))
+            )
+        };
+        assert!(reject(&signed("other", now())));
+
+        let mut headers = signed("secret", now());
+        headers.app_id = "other".to_string();
+        assert!(reject(&headers));
+
+        for sign in ["", "abc", "zz", &"0".repeat(32)] {
+            let mut headers = signed("secret", now());
+            headers.sign = sign.to_string();
+            assert!(reject(&headers), "accepted sign {:?}", sign);
+        }
+    }
+
+    #[test]
+    fn verify_enforces_the_clock_skew() {
+        let stale = now() - Duration::minutes(10).num_milliseconds();
+        let verifier = PushVerifier::new(credentials("secret"));
+        assert!(verifier.verify(&signed("secret", stale)).is_err());
+
+        let mut headers = signed("secret", now());
+        headers.time = "yesterday".to_string();
+        assert!(verifier.verify(&headers).is_err());
+
+        let verifier = verifier.max_skew(None);
+        assert!(verifier.verify(&signed("secret", stale)).is_ok());
+    }
+
+    #[test]
+    fn parse_and_verify_checks_headers_and_body() {
+        let verifier = PushVerifier::new(credentials("secret"));
+        let headers = signed("secret", now());
+        let get = |name: &str| match name {
+            "Appid" => Some(headers.app_id.as_str()),
+            "Keyid" => Some(headers.key_id.as_str()),
+            "Nonce" => Some(headers.nonce.as_str()),
+            "Time" => Some(headers.time.as_str()),
+            "Sign" => Some(headers.sign.as_str()),
+            _ => None,
+        };
+        let message = verifier
+            .parse_and_verify(get, br#"{"msgType":"custom","data":{}}"#)
+            .unwrap();
+        assert_eq!(message.msg_type(), "custom");
+        assert!(matches!(
+            verifier.parse_and_verify(get, &[0xff, 0xfe]),
+            Err(AqaraError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn decode_hex_accepts_both_cases() {
+        assert_eq!(decode_hex("0aFf"), Some(vec![0x0a, 0xff]));
+        assert_eq!(decode_hex(""), Some(Vec::new()));
+        assert_eq!(decode_hex("abc"), None);
+        assert_eq!(decode_hex("zz"), None);
+    }
+}
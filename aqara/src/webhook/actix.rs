@@ -0,0 +1,82 @@
+//! actix-web 集成 (actix-web integration)
+//!
+//! 将 `PushVerifier` 注册为 `web::Data` 后，在处理函数中使用 `VerifiedPush` 提取器：
+//! Register the `PushVerifier` as `web::Data`, then take `VerifiedPush` in a handler:
+//!
+//! ```ignore
+//! async fn push(VerifiedPush(message): VerifiedPush) -> HttpResponse { ... }
+//! App::new().app_data(web::Data::new(verifier)).route("/aqara/push", web::post().to(push))
+//! ```
+
+use std::fmt;
+
+use actix_web::dev::Payload;
+use actix_web::http::StatusCode;
+use actix_web::web::{Bytes, Data};
+use actix_web::{FromRequest, HttpRequest, ResponseError};
+use futures_util::future::LocalBoxFuture;
+
+use super::PushVerifier;
+use crate::push::PushMessage;
+use crate::{AqaraError, ConfigProblem, ErrorKind};
+
+/// 已校验的推送消息 (Verified push message)
+pub struct VerifiedPush(pub PushMessage);
+
+/// 推送被拒绝 (Rejected push)
+///
+/// 签名无效返回 401，校验器未注册返回 500，其余返回 400。
+/// Responds 401 for bad signatures, 500 when no verifier is registered and 400 otherwise.
+#[derive(Debug)]
+pub struct WebhookRejection(pub AqaraError);
+
+impl fmt::Display for WebhookRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ResponseError for WebhookRejection {
+    fn status_code(&self) -> StatusCode {
+        match self.0.kind() {
+            ErrorKind::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorKind::Config => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl FromRequest for VerifiedPush {
+    type Error = WebhookRejection;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        let body = Bytes::from_request(&req, payload);
+        Box::pin(async move {
+            let verifier = req.app_data::<Data<PushVerifier>>().ok_or_else(|| {
+                WebhookRejection(AqaraError::InvalidConfig(vec![ConfigProblem {
+                    field: "app_data".to_string(),
+                    message: "no web::Data<PushVerifier> registered".to_string(),
+                }]))
+            })?;
+            let body = body.await.map_err(|e| {
+                WebhookRejection(AqaraError::InvalidParams(vec![format!(
+                    "unreadable body: {}",
+                    e
+                )]))
+            })?;
+            verifier
+                .parse_and_verify(
+                    |name| {
+                        req.headers()
+                            .get(name)
+                            .and_then(|value| value.to_str().ok())
+                    },
+                    &body,
+                )
+                .map(VerifiedPush)
+                .map_err(WebhookRejection)
+        })
+    }
+}
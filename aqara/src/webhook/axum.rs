@@ -0,0 +1,82 @@
+//! axum 集成 (axum integration)
+//!
+//! 将 `Arc<PushVerifier>` 放入路由状态 (或可通过 `FromRef` 取得) 后，在处理函数中使用
+//! `VerifiedPush` 提取器：
+//! Put an `Arc<PushVerifier>` in the router state (or make it reachable via
+//! `FromRef`), then take `VerifiedPush` in a handler:
+//!
+//! ```ignore
+//! async fn push(VerifiedPush(message): VerifiedPush) -> StatusCode { ... }
+//! Router::new().route("/aqara/push", post(push)).with_state(Arc::new(verifier))
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use axum::extract::{FromRef, FromRequest, Request};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+
+use super::PushVerifier;
+use crate::push::PushMessage;
+use crate::{AqaraError, ErrorKind};
+
+/// 已校验的推送消息 (Verified push message)
+pub struct VerifiedPush(pub PushMessage);
+
+/// 推送被拒绝 (Rejected push)
+///
+/// 签名无效返回 401，其余返回 400。
+/// Responds 401 for bad signatures and 400 otherwise.
+#[derive(Debug)]
+pub struct WebhookRejection(pub AqaraError);
+
+impl fmt::Display for WebhookRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl IntoResponse for WebhookRejection {
+    fn into_response(self) -> Response {
+        let status = match self.0.kind() {
+            ErrorKind::Unauthorized => StatusCode::UNAUTHORIZED,
+            _ => StatusCode::BAD_REQUEST,
+        };
+        (status, self.0.to_string()).into_response()
+    }
+}
+
+impl<S> FromRequest<S> for VerifiedPush
+where
+    Arc<PushVerifier>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = WebhookRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let verifier = Arc::<PushVerifier>::from_ref(state);
+        let (parts, body) = req.into_parts();
+        let body = Bytes::from_request(Request::from_parts(parts.clone(), body), state)
+            .await
+            .map_err(|e| {
+                WebhookRejection(AqaraError::InvalidParams(vec![format!(
+                    "unreadable body: {}",
+                    e
+                )]))
+            })?;
+        verifier
+            .parse_and_verify(
+                |name| {
+                    parts
+                        .headers
+                        .get(name)
+                        .and_then(|value| value.to_str().ok())
+                },
+                &body,
+            )
+            .map(VerifiedPush)
+            .map_err(WebhookRejection)
+    }
+}
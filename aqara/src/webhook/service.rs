@@ -0,0 +1,71 @@
+//! 通用 Service 集成 (Framework-agnostic service)
+//!
+//! `WebhookService` 同时实现 `tower::Service` 与 `hyper::service::Service`，
+//! 接收已读取完整请求体的 `http::Request<Bytes>`，返回校验后的 `PushMessage`。
+//! `WebhookService` implements both `tower::Service` and `hyper::service::Service`;
+//! it takes an `http::Request<Bytes>` with the body already collected and returns
+//! the verified `PushMessage`.
+
+use std::future::{ready, Ready};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use http::Request;
+
+use super::PushVerifier;
+use crate::push::PushMessage;
+use crate::AqaraError;
+
+/// 推送校验服务 (Push verification service)
+#[derive(Debug, Clone)]
+pub struct WebhookService {
+    verifier: Arc<PushVerifier>,
+}
+
+impl PushVerifier {
+    /// 转换为 `WebhookService` / Turn into a `WebhookService`
+    pub fn into_service(self) -> WebhookService {
+        WebhookService {
+            verifier: Arc::new(self),
+        }
+    }
+}
+
+impl WebhookService {
+    fn handle(&self, request: &Request<Bytes>) -> Result<PushMessage, AqaraError> {
+        self.verifier.parse_and_verify(
+            |name| {
+                request
+                    .headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+            },
+            request.body(),
+        )
+    }
+}
+
+impl tower_service::Service<Request<Bytes>> for WebhookService {
+    type Response = PushMessage;
+    type Error = AqaraError;
+    type Future = Ready<Result<PushMessage, AqaraError>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<Bytes>) -> Self::Future {
+        ready(self.handle(&request))
+    }
+}
+
+impl hyper::service::Service<Request<Bytes>> for WebhookService {
+    type Response = PushMessage;
+    type Error = AqaraError;
+    type Future = Ready<Result<PushMessage, AqaraError>>;
+
+    fn call(&self, request: Request<Bytes>) -> Self::Future {
+        ready(self.handle(&request))
+    }
+}